@@ -2,29 +2,82 @@ use crate::commands::task::state_paths;
 use crate::config::Config;
 use crate::devops::client::DevOpsClient;
 use crate::devops::models::WorkItem;
+use crate::devops::store::WorkItemStore;
+use crate::error::ApiError;
 use crate::state::State;
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Read};
 use std::path::PathBuf;
 
-#[derive(Serialize, Deserialize)]
+/// Type used for decomposed items that don't name one
+const DEFAULT_DECOMPOSE_TYPE: &str = "Task";
+
+/// Input for `decompose`: child work items to create under a parent
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct DecomposeInput {
+    /// Work item the new items are linked under (overridden by --parent)
     pub parent_id: u32,
+    /// Items to create, in order
     pub tasks: Vec<DecomposeTask>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// One child work item to create
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct DecomposeTask {
+    /// Work item title
     pub title: String,
+    /// Description (HTML is accepted)
     pub description: Option<String>,
+    /// Effort estimate (Microsoft.VSTS.Scheduling.Effort)
     pub effort: Option<f32>,
-    pub work_item_type: Option<String>, // e.g. "Task"
+    /// Work item type, e.g. "Task" or "Bug"
+    #[schemars(extend("default" = DEFAULT_DECOMPOSE_TYPE))]
+    pub work_item_type: Option<String>,
+}
+
+/// JSON Schema describing the `decompose` input file
+pub fn decompose_schema() -> serde_json::Value {
+    schemars::schema_for!(DecomposeInput).to_value()
+}
+
+/// Work item fields an agent needs to orient itself
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CompactWorkItem {
+    pub id: u32,
+    pub title: String,
+    pub state: String,
+    #[serde(rename = "type")]
+    pub work_item_type: String,
+}
+
+impl From<&WorkItem> for CompactWorkItem {
+    fn from(wi: &WorkItem) -> Self {
+        Self {
+            id: wi.id,
+            title: wi.get_title().unwrap_or("?").to_string(),
+            state: wi.get_state().unwrap_or("?").to_string(),
+            work_item_type: wi.get_type().unwrap_or("?").to_string(),
+        }
+    }
+}
+
+/// The current task with its parent and siblings (`context --format json`)
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct AgentContext {
+    pub current_task: Option<CompactWorkItem>,
+    /// Set when the current task is past its expiry and may be stale
+    pub expired: bool,
+    pub parent: Option<CompactWorkItem>,
+    pub siblings: Vec<CompactWorkItem>,
 }
 
 pub fn agent_context(config: &Config, format: &str) -> Result<()> {
-    if format != "llm" {
-        anyhow::bail!("Only 'llm' format is currently supported");
+    if format != "llm" && format != "json" {
+        anyhow::bail!("Unsupported format '{}'. Use 'llm' or 'json'", format);
     }
 
     let (_lock_path, state_path) = match state_paths(config) {
@@ -38,69 +91,134 @@ pub fn agent_context(config: &Config, format: &str) -> Result<()> {
     // We don't necessarily need lock to read for context dump, but safer.
     // However, for speed, just loading state.json is fine.
     let state = State::load(&state_path)?;
-    let current_task_id = match state.current_task.as_ref() {
-        Some(task) => task.id,
-        None => {
-            println!("No active task.");
-            return Ok(());
-        }
+    let context = match state.current_task.as_ref() {
+        Some(task) => build_context(config, task.id, task.is_expired(chrono::Utc::now()))?,
+        None => AgentContext::default(),
+    };
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&context)?);
+        return Ok(());
+    }
+
+    let Some(current) = &context.current_task else {
+        println!("No active task.");
+        return Ok(());
     };
 
+    if context.expired {
+        // Stale tasks are still shown, but flagged so agents don't assume it's current work
+        println!("Current Task (EXPIRED - may be stale, confirm with the user):");
+    } else {
+        println!("Current Task:");
+    }
+    print_compact(current);
+
+    match &context.parent {
+        Some(parent) => {
+            println!("\nParent:");
+            print_compact(parent);
+
+            println!("\nSiblings:");
+            if context.siblings.is_empty() {
+                println!("(None)");
+            }
+            for sibling in &context.siblings {
+                print_compact(sibling);
+            }
+        }
+        None => println!("\nParent: (None)"),
+    }
+
+    Ok(())
+}
+
+fn build_context(config: &Config, current_task_id: u32, expired: bool) -> Result<AgentContext> {
     let pat = config.get_devops_pat()?;
-    let client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
     let work_item = client.get_work_item(current_task_id)?;
 
-    println!("Current Task:");
-    print_compact(&work_item);
+    let mut context = AgentContext {
+        current_task: Some(CompactWorkItem::from(&work_item)),
+        expired,
+        ..Default::default()
+    };
 
     if let Some(parent_id) = work_item.get_parent_id() {
-        println!("\nParent:");
         let parent = client.get_work_item(parent_id)?;
-        print_compact(&parent);
 
-        println!("\nSiblings:");
-        if let Some(relations) = parent.relations {
-            let siblings: Vec<_> = relations
+        if let Some(relations) = &parent.relations {
+            let sibling_ids: Vec<u32> = relations
                 .iter()
                 .filter(|r| r.rel == "System.LinkTypes.Hierarchy-Forward")
                 .filter_map(|r| parse_id_from_url(&r.url))
                 .filter(|&id| id != current_task_id)
                 .collect();
 
-            if siblings.is_empty() {
-                println!("(None)");
-            } else {
-                for sibling_id in siblings {
-                    // Fetch sibling details. In future, use batch API or WIQL for perf.
-                    // For now, simple fetch is acceptable for typical <10 siblings.
-                    if let Ok(sibling) = client.get_work_item(sibling_id) {
-                        print_compact(&sibling);
-                    }
-                }
+            // One batch call for all siblings, kept in the parent's link order.
+            // Siblings are best-effort context, so a failed fetch leaves them out
+            if !sibling_ids.is_empty()
+                && let Ok(siblings) = client.get_work_items_batch(&sibling_ids)
+            {
+                let mut by_id: HashMap<u32, WorkItem> =
+                    siblings.into_iter().map(|item| (item.id, item)).collect();
+                context.siblings = sibling_ids
+                    .iter()
+                    .filter_map(|id| by_id.remove(id))
+                    .map(|sibling| CompactWorkItem::from(&sibling))
+                    .collect();
             }
         }
-    } else {
-        println!("\nParent: (None)");
+        context.parent = Some(CompactWorkItem::from(&parent));
     }
 
-    Ok(())
+    Ok(context)
 }
 
 // Use imported state_paths
 
-pub fn agent_decompose(config: &Config, input_path: PathBuf, dry_run: bool) -> Result<()> {
-    let content = fs::read_to_string(&input_path)
-        .with_context(|| format!("Failed to read input file: {:?}", input_path))?;
+pub fn agent_decompose(
+    config: &Config,
+    input_path: PathBuf,
+    parent_override: Option<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    let content = if input_path.as_os_str() == "-" {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read decomposition JSON from stdin")?;
+        content
+    } else {
+        fs::read_to_string(&input_path)
+            .with_context(|| format!("Failed to read input file: {:?}", input_path))?
+    };
 
     let input: DecomposeInput =
         serde_json::from_str(&content).context("Failed to parse decomposition JSON")?;
 
     let pat = config.get_devops_pat()?;
-    let client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
 
-    // Validate parent
+    decompose_with_store(&client, input, parent_override, dry_run)
+}
+
+/// Create `input.tasks` under the parent, after checking every type exists
+pub fn decompose_with_store(
+    client: &impl WorkItemStore,
+    input: DecomposeInput,
+    parent_override: Option<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    // Validate parent (--parent takes precedence over the JSON parent_id)
     let parent = client
-        .get_work_item(input.parent_id)
+        .get_work_item(parent_override.unwrap_or(input.parent_id))
         .context("Parent work item not found")?;
 
     println!(
@@ -109,8 +227,35 @@ pub fn agent_decompose(config: &Config, input_path: PathBuf, dry_run: bool) -> R
         parent.get_title().unwrap_or("?")
     );
 
+    // Check every type up front so a typo doesn't leave a half-created breakdown
+    let invalid = invalid_work_item_types(client, &input.tasks, dry_run)?;
+    if !invalid.is_empty() {
+        let valid = client
+            .list_work_item_types()
+            .map(|names| names.join(", "))
+            .unwrap_or_else(|e| format!("(could not list types: {})", e));
+        let invalid = invalid
+            .iter()
+            .map(|t| format!("'{}'", t))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if dry_run {
+            println!("[DRY-RUN] Unknown work item type(s): {}", invalid);
+            println!("[DRY-RUN] Valid types: {}", valid);
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Unknown work item type(s): {}. Nothing was created. Valid types: {}",
+            invalid,
+            valid
+        );
+    }
+
     for task in input.tasks {
-        let wi_type = task.work_item_type.as_deref().unwrap_or("Task");
+        let wi_type = task
+            .work_item_type
+            .as_deref()
+            .unwrap_or(DEFAULT_DECOMPOSE_TYPE);
         println!(
             "{} Creating '{}': {}",
             if dry_run { "[DRY-RUN]" } else { "[CREATE]" },
@@ -143,27 +288,9 @@ pub fn agent_decompose(config: &Config, input_path: PathBuf, dry_run: bool) -> R
                 );
             }
 
-            match client.create_work_item(fields) {
-                Ok(new_wi) => {
-                    println!("  -> Created #{}", new_wi.id);
-                    // Link to parent
-                    let parent_url = &parent.url;
-                    let link_op = serde_json::json!({
-                        "op": "add",
-                        "path": "/relations/-",
-                        "value": {
-                            "rel": "System.LinkTypes.Hierarchy-Reverse",
-                            "url": parent_url,
-                             "attributes": {
-                                "comment": "Created via ao_no_out7ook decompose"
-                            }
-                        }
-                    });
-                    // Using update_work_item which takes Vec<Value> (operations)
-                    if let Err(e) = client.update_work_item(new_wi.id, vec![link_op]) {
-                        eprintln!("  -> Failed to link parent: {}", e);
-                    }
-                }
+            // Link to parent in the create request so no item is left orphaned
+            match client.create_work_item_with_parent(fields, Some(&parent.url)) {
+                Ok(new_wi) => println!("  -> Created #{}", new_wi.id),
                 Err(e) => eprintln!("  -> Failed: {}", e),
             }
         }
@@ -172,16 +299,106 @@ pub fn agent_decompose(config: &Config, input_path: PathBuf, dry_run: bool) -> R
     Ok(())
 }
 
-fn print_compact(wi: &WorkItem) {
+/// Distinct types requested by `tasks` that the project doesn't define
+fn invalid_work_item_types(
+    client: &impl WorkItemStore,
+    tasks: &[DecomposeTask],
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    let mut seen = Vec::new();
+    let mut invalid = Vec::new();
+    for task in tasks {
+        let wi_type = task
+            .work_item_type
+            .as_deref()
+            .unwrap_or(DEFAULT_DECOMPOSE_TYPE);
+        if seen
+            .iter()
+            .any(|t: &String| t.eq_ignore_ascii_case(wi_type))
+        {
+            continue;
+        }
+        seen.push(wi_type.to_string());
+
+        match client.get_work_item_type(wi_type) {
+            Ok(_) => {
+                if dry_run {
+                    println!("[DRY-RUN] Type '{}': valid", wi_type);
+                }
+            }
+            Err(e) if matches!(e.downcast_ref::<ApiError>(), Some(ApiError::NotFound(_))) => {
+                if dry_run {
+                    println!("[DRY-RUN] Type '{}': not found", wi_type);
+                }
+                invalid.push(wi_type.to_string());
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to validate work item type '{}'", wi_type));
+            }
+        }
+    }
+    Ok(invalid)
+}
+
+fn print_compact(wi: &CompactWorkItem) {
     println!(
         "- #{} {} [{}] ({})",
-        wi.id,
-        wi.get_title().unwrap_or("?"),
-        wi.get_state().unwrap_or("?"),
-        wi.get_type().unwrap_or("?")
+        wi.id, wi.title, wi.state, wi.work_item_type
     );
 }
 
 fn parse_id_from_url(url: &str) -> Option<u32> {
     url.split('/').next_back()?.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devops::store::mock::MockStore;
+
+    fn decompose_input(types: &[Option<&str>]) -> DecomposeInput {
+        serde_json::from_value(serde_json::json!({
+            "parent_id": 42,
+            "tasks": types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| serde_json::json!({ "title": format!("Step {}", i + 1), "work_item_type": t }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decompose_creates_children_linked_to_parent() {
+        let store = MockStore::with_task(42, 3, "Active");
+        decompose_with_store(&store, decompose_input(&[None, Some("Bug")]), None, false).unwrap();
+
+        let creates = store.creates.borrow();
+        assert_eq!(creates.len(), 2);
+        assert_eq!(creates[0].0["System.WorkItemType"], "Task");
+        assert_eq!(creates[1].0["System.WorkItemType"], "Bug");
+        let parent_url = store.items[&42].url.clone();
+        assert!(
+            creates
+                .iter()
+                .all(|(_, parent)| parent.as_deref() == Some(&*parent_url))
+        );
+    }
+
+    #[test]
+    fn test_decompose_invalid_type_creates_nothing() {
+        let store = MockStore::with_task(42, 3, "Active");
+        let err = decompose_with_store(
+            &store,
+            decompose_input(&[Some("Task"), Some("Stroy")]),
+            None,
+            false,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("'Stroy'"));
+        assert!(err.to_string().contains("Nothing was created"));
+        assert!(store.creates.borrow().is_empty());
+    }
+}
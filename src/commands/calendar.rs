@@ -2,13 +2,21 @@ use crate::OutputFormat;
 use crate::config::Config;
 use crate::graph::auth::GraphAuthenticator;
 use crate::graph::client::GraphClient;
-use crate::graph::models::{CalendarEvent, DateTimeTimeZone};
+use crate::graph::models::{
+    CalendarEvent, DateTimeTimeZone, ExtendedProperty, PatternedRecurrence, RecurrenceEnd,
+};
+use crate::graph::scheduler::{self, local_to_utc, parse_event_time};
+use crate::pace::duration::format_duration;
+use crate::state::{CalendarMapping, State, with_state_lock_timeout};
+use crate::ui;
+use crate::utils::text::truncate_chars;
 use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
-use home::home_dir;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use clap::ValueEnum;
 
 /// OAuth login command - initiate device code flow
-pub async fn oauth_login(config: &Config) -> Result<()> {
+pub async fn oauth_login(config: &Config, resume: bool) -> Result<()> {
     if config.graph.client_id.is_empty() {
         anyhow::bail!(
             "Graph API client_id not configured. \
@@ -18,30 +26,34 @@ pub async fn oauth_login(config: &Config) -> Result<()> {
         );
     }
 
-    let token_cache_path = home_dir()
-        .context("Could not find home directory")?
-        .join(".ao-no-out7ook")
-        .join("tokens.json");
-
-    let auth = GraphAuthenticator::new(config.graph.client_id.clone(), token_cache_path);
-    auth.login().await?;
+    let auth = GraphAuthenticator::from_config(config)?;
+    if resume {
+        auth.resume_login().await?;
+    } else {
+        auth.login().await?;
+    }
 
     Ok(())
 }
 
 /// OAuth status command - show authentication status
 pub async fn oauth_status(config: &Config, format: OutputFormat) -> Result<()> {
-    let token_cache_path = home_dir()
-        .context("Could not find home directory")?
-        .join(".ao-no-out7ook")
-        .join("tokens.json");
+    let auth = GraphAuthenticator::from_config(config)?;
 
-    if !token_cache_path.exists() {
-        println!("❌ Not authenticated. Run 'task oauth login' first.");
+    if !auth.has_cached_tokens() {
+        println!(
+            "{} Not authenticated. Run 'task oauth login' first.",
+            ui::Symbol::Fail
+        );
         return Ok(());
     }
 
-    let auth = GraphAuthenticator::new(config.graph.client_id.clone(), token_cache_path.clone());
+    // Legacy file is only present until tokens migrate to the keyring
+    let token_cache = if auth.token_cache_path().exists() {
+        auth.token_cache_path().display().to_string()
+    } else {
+        "system keyring".to_string()
+    };
 
     match auth.get_access_token().await {
         Ok(_) => {
@@ -50,12 +62,12 @@ pub async fn oauth_status(config: &Config, format: OutputFormat) -> Result<()> {
                     "{}",
                     serde_json::json!({
                         "authenticated": true,
-                        "token_cache": token_cache_path
+                        "token_cache": token_cache
                     })
                 );
             } else {
-                println!("✓ Authenticated with Microsoft Graph");
-                println!("  Token cache: {}", token_cache_path.display());
+                println!("{} Authenticated with Microsoft Graph", ui::Symbol::Ok);
+                println!("  Token cache: {}", token_cache);
             }
         }
         Err(e) => {
@@ -68,7 +80,11 @@ pub async fn oauth_status(config: &Config, format: OutputFormat) -> Result<()> {
                     })
                 );
             } else {
-                println!("❌ Authentication expired or invalid: {}", e);
+                println!(
+                    "{} Authentication expired or invalid: {}",
+                    ui::Symbol::Fail,
+                    e
+                );
                 println!("  Run 'task oauth login' to re-authenticate.");
             }
         }
@@ -77,25 +93,56 @@ pub async fn oauth_status(config: &Config, format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// Human-readable length of an event: "all day", "all day (N days)" for
+/// all-day or midnight-to-midnight events, otherwise e.g. "1h 30m"
+fn event_duration(event: &CalendarEvent) -> String {
+    if let Some((first, last)) = event.all_day_dates() {
+        return all_day_label((last - first).num_days());
+    }
+
+    let (Ok(start), Ok(end)) = (parse_event_time(&event.start), parse_event_time(&event.end))
+    else {
+        return "N/A".to_string();
+    };
+
+    let secs = (end - start).num_seconds().max(0);
+    if secs > 0 && secs % 86_400 == 0 && start.time() == chrono::NaiveTime::MIN {
+        return all_day_label(secs / 86_400);
+    }
+    format_duration(secs.min(u32::MAX as i64) as u32)
+}
+
+fn all_day_label(days: i64) -> String {
+    if days > 1 {
+        format!("all day ({} days)", days)
+    } else {
+        "all day".to_string()
+    }
+}
+
 /// List calendar events
 pub async fn calendar_list(
     config: &Config,
     days: u32,
     work_item: Option<u32>,
+    limit: Option<usize>,
     format: OutputFormat,
 ) -> Result<()> {
-    let token_cache_path = home_dir()
-        .context("Could not find home directory")?
-        .join(".ao-no-out7ook")
-        .join("tokens.json");
-
-    let auth = GraphAuthenticator::new(config.graph.client_id.clone(), token_cache_path);
+    let auth = GraphAuthenticator::from_config(config)?;
     let client = GraphClient::new(auth);
 
     let start = Utc::now();
     let end = start + Duration::days(days as i64);
 
-    let events = client.list_events(start, end).await?;
+    // With a work item filter the limit applies to matches, so every page is needed
+    let fetch_limit = if work_item.is_some() { None } else { limit };
+    let mut events = client.list_events_limited(start, end, fetch_limit).await?;
+    if let Some(filter_id) = work_item {
+        events.retain(|event| event.work_item_id() == Some(filter_id));
+    }
+    if let Some(limit) = limit {
+        events.truncate(limit);
+    }
 
     if events.is_empty() {
         if let OutputFormat::Json = format {
@@ -121,25 +168,9 @@ pub async fn calendar_list(
     for event in &events {
         let event_id = event.id.as_deref().unwrap_or("N/A");
         let subject = &event.subject;
-
-        // Skip if filtering by work_item and this event doesn't match
-        if let Some(filter_id) = work_item {
-            // Check if event has work_item_id in extended properties
-            let has_match = event
-                .extended_properties
-                .as_ref()
-                .and_then(|props| props.iter().find(|p| p.value == filter_id.to_string()))
-                .is_some();
-
-            if !has_match {
-                continue;
-            }
-        }
-
         let start_time = &event.start.date_time;
 
-        // Calculate duration (simplified)
-        let duration = "N/A"; // TODO: parse start/end times
+        let duration = event_duration(event);
 
         println!(
             "{:<8} {:<50} {:<20} {:<12}",
@@ -148,11 +179,7 @@ pub async fn calendar_list(
             } else {
                 event_id
             },
-            if subject.len() > 48 {
-                format!("{}...", &subject[..45])
-            } else {
-                subject.clone()
-            },
+            truncate_chars(subject, 48),
             start_time,
             duration
         );
@@ -163,46 +190,203 @@ pub async fn calendar_list(
     Ok(())
 }
 
+/// How often a scheduled Focus Block repeats
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    /// Same weekday as the first occurrence
+    Weekly,
+}
+
+/// Options for `calendar schedule`
+#[derive(Debug, Clone)]
+pub struct ScheduleOptions {
+    /// ISO 8601 start time; a bare date is allowed for all-day blocks
+    pub start_time: Option<String>,
+    pub duration_mins: u32,
+    pub title: Option<String>,
+    /// Block out whole days instead of `duration_mins`
+    pub all_day: bool,
+    pub recurrence: Option<Recurrence>,
+    /// Stop after this many occurrences
+    pub count: Option<u32>,
+    /// Stop after this date (YYYY-MM-DD), inclusive
+    pub until: Option<String>,
+    /// Start right after this event ends instead of at `start_time`
+    pub after: Option<String>,
+}
+
+/// Parse a `--start` value: RFC 3339 with an offset, or a local time without
+/// one (`2026-01-08T14:00[:00]`) interpreted in the work hours time zone
+fn parse_start_time(value: &str, tz: &Tz) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .map(|local| local_to_utc(tz, local.date(), local.time()))
+        .with_context(|| {
+            format!(
+                "Invalid start time '{}'. Use ISO 8601, e.g. 2026-01-08T14:00:00 (work hours time zone) or 2026-01-08T14:00:00-07:00",
+                value
+            )
+        })
+}
+
+/// Start/end of an all-day block on `date` in the work hours time zone.
+/// Graph requires midnight boundaries in the event's own zone.
+fn all_day_bounds(
+    config: &Config,
+    date: NaiveDate,
+) -> Result<(DateTimeTimeZone, DateTimeTimeZone)> {
+    let tz = config.work_hours.tz()?;
+    let midnight = |d: NaiveDate| DateTimeTimeZone {
+        date_time: format!("{}T00:00:00", d),
+        time_zone: tz.name().to_string(),
+    };
+    Ok((midnight(date), midnight(date + Duration::days(1))))
+}
+
+/// Build the Graph recurrence for `options`, anchored on the first occurrence's date
+fn build_recurrence(
+    options: &ScheduleOptions,
+    first: NaiveDate,
+) -> Result<Option<PatternedRecurrence>> {
+    let Some(recurrence) = options.recurrence else {
+        if options.count.is_some() || options.until.is_some() {
+            anyhow::bail!("--count and --until require --recurrence");
+        }
+        return Ok(None);
+    };
+
+    let end = match (options.count, options.until.as_deref()) {
+        (Some(_), Some(_)) => anyhow::bail!("Use either --count or --until, not both"),
+        (Some(0), None) => anyhow::bail!("--count must be at least 1"),
+        (Some(n), None) => RecurrenceEnd::Count(n),
+        (None, Some(until)) => {
+            let until = NaiveDate::parse_from_str(until, "%Y-%m-%d").with_context(|| {
+                format!("Invalid --until date '{}'. Expected YYYY-MM-DD", until)
+            })?;
+            if until < first {
+                anyhow::bail!(
+                    "--until {} is before the first occurrence on {}",
+                    until,
+                    first
+                );
+            }
+            RecurrenceEnd::Until(until)
+        }
+        (None, None) => RecurrenceEnd::Never,
+    };
+
+    Ok(Some(match recurrence {
+        Recurrence::Daily => PatternedRecurrence::daily(first, end),
+        Recurrence::Weekly => PatternedRecurrence::weekly(first, end),
+    }))
+}
+
+/// One-line summary of a recurrence for previews
+fn describe_recurrence(recurrence: &PatternedRecurrence) -> String {
+    let pattern = match recurrence.pattern.days_of_week.as_slice() {
+        [] => recurrence.pattern.pattern_type.clone(),
+        days => format!("{} on {}", recurrence.pattern.pattern_type, days.join(", ")),
+    };
+    match (
+        recurrence.range.number_of_occurrences,
+        &recurrence.range.end_date,
+    ) {
+        (Some(n), _) => format!("{}, {} occurrences", pattern, n),
+        (None, Some(until)) => format!("{}, until {}", pattern, until),
+        (None, None) => format!("{}, no end date", pattern),
+    }
+}
+
 /// Schedule Focus Block for work item
 pub async fn calendar_schedule(
     config: &Config,
     work_item_id: u32,
-    start_time: Option<String>,
-    duration_mins: u32,
-    custom_title: Option<String>,
+    options: ScheduleOptions,
     dry_run: bool,
+    format: OutputFormat,
 ) -> Result<()> {
-    let token_cache_path = home_dir()
-        .context("Could not find home directory")?
-        .join(".ao-no-out7ook")
-        .join("tokens.json");
-
-    let auth = GraphAuthenticator::new(config.graph.client_id.clone(), token_cache_path);
+    let auth = GraphAuthenticator::from_config(config)?;
     let client = GraphClient::new(auth);
 
-    // Get work item title from DevOps
+    // Get work item title from DevOps. The client is blocking (it owns its own
+    // runtime), so build and use it off the async runtime's threads
     let pat = config.get_devops_pat()?;
-    let devops_client = crate::devops::client::DevOpsClient::new(
-        &pat,
-        &config.devops.organization,
-        &config.devops.project,
-    );
-    let work_item = devops_client.get_work_item(work_item_id)?;
+    let devops = config.devops.clone();
+    let work_item = tokio::task::spawn_blocking(move || {
+        let mut devops_client =
+            crate::devops::client::DevOpsClient::new(&pat, &devops.organization, &devops.project);
+        if let Some(url) = &devops.api_url {
+            devops_client = devops_client.with_base_url(url);
+        }
+        devops_client.get_work_item(work_item_id)
+    })
+    .await
+    .context("Work item lookup task failed")??;
     let work_item_title = work_item.get_title().unwrap_or("Unknown");
 
-    // Parse start time or use now
-    let start = if let Some(time_str) = start_time {
-        chrono::DateTime::parse_from_rfc3339(&time_str)
-            .context("Invalid start time format. Use ISO 8601: 2026-01-08T14:00:00-07:00")?
-            .with_timezone(&Utc)
+    let (event_start, event_end, first_date) = if options.all_day {
+        // All-day blocks take a date (or a timestamp, whose local date is used)
+        let date = match options.start_time.as_deref() {
+            Some(s) => match NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+                Ok(date) => date,
+                Err(_) => {
+                    let tz = config.work_hours.tz()?;
+                    parse_start_time(s, &tz)?.with_timezone(&tz).date_naive()
+                }
+            },
+            None => Utc::now()
+                .with_timezone(&config.work_hours.tz()?)
+                .date_naive(),
+        };
+        let (start, end) = all_day_bounds(config, date)?;
+        (start, end, date)
     } else {
-        Utc::now()
+        // Parse start time or use now
+        let start = if let Some(anchor_id) = &options.after {
+            let anchor = client.get_event(anchor_id).await?;
+            scheduler::start_after(&anchor, config.focus_blocks.interval_minutes)?
+        } else if let Some(time_str) = &options.start_time {
+            parse_start_time(time_str, &config.work_hours.tz()?)?
+        } else {
+            Utc::now()
+        };
+        let end = start + Duration::minutes(options.duration_mins as i64);
+
+        if options.after.is_some() {
+            // The events query only matches events fully inside its window, so
+            // widen it to catch meetings that straddle the block
+            let nearby = client
+                .list_events(start - Duration::days(1), end + Duration::days(1))
+                .await?;
+            let conflicts = scheduler::overlapping_events(&nearby, start, end);
+            if !conflicts.is_empty() {
+                let subjects: Vec<&str> = conflicts.iter().map(|e| e.subject.as_str()).collect();
+                anyhow::bail!(
+                    "A {}-minute block from {} conflicts with: {}",
+                    options.duration_mins,
+                    start.to_rfc3339(),
+                    subjects.join(", ")
+                );
+            }
+        }
+        (
+            DateTimeTimeZone::from_utc(start, "UTC"),
+            DateTimeTimeZone::from_utc(end, "UTC"),
+            start.date_naive(),
+        )
     };
 
-    let end = start + Duration::minutes(duration_mins as i64);
+    let recurrence = build_recurrence(&options, first_date)?;
 
-    let subject =
-        custom_title.unwrap_or_else(|| format!("🎯 Focus: {} - {}", work_item_id, work_item_title));
+    let subject = options
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("🎯 Focus: {} - {}", work_item_id, work_item_title));
 
     // FR3.9: Build event body with checkin action URLs
     let checkin_body = format!(
@@ -226,27 +410,47 @@ pub async fn calendar_schedule(
     let event = CalendarEvent {
         id: None,
         subject: subject.clone(),
-        start: DateTimeTimeZone::from_utc(start, "UTC"),
-        end: DateTimeTimeZone::from_utc(end, "UTC"),
+        start: event_start,
+        end: event_end,
         body: Some(crate::graph::models::ItemBody {
             content_type: "html".to_string(),
             content: checkin_body,
         }),
-        categories: vec!["Focus Block".to_string()],
-        extended_properties: None, // TODO: Add work_item_id as extended property
+        categories: config.focus_blocks.categories(),
+        extended_properties: Some(vec![ExtendedProperty::work_item_id(work_item_id)]),
+        is_all_day: options.all_day,
+        recurrence,
+        show_as: Some(config.focus_blocks.show_as.as_graph_str().to_string()),
     };
 
-    if dry_run {
+    if dry_run && matches!(format, OutputFormat::Json) {
+        let mut output = scheduled_json(None, &event);
+        output["dry_run"] = serde_json::json!(true);
+        println!("{}", output);
+    } else if dry_run {
         println!("--- DRY RUN: Calendar Schedule Preview ---");
         println!("  Subject: {}", subject);
         println!("  Start: {}", event.start.date_time);
         println!("  End: {}", event.end.date_time);
-        println!("  Duration: {} minutes", duration_mins);
+        if event.is_all_day {
+            println!("  All day ({})", event.start.time_zone);
+        } else {
+            println!("  Duration: {} minutes", options.duration_mins);
+        }
+        if let Some(recurrence) = &event.recurrence {
+            println!("  Repeats: {}", describe_recurrence(recurrence));
+        }
+        if !event.categories.is_empty() {
+            println!("  Categories: {}", event.categories.join(", "));
+        }
+        if let Some(show_as) = &event.show_as {
+            println!("  Show as: {}", show_as);
+        }
         println!("  Check-in URLs:");
         println!("    - ao7://checkin?id={}&action=continue", work_item_id);
         println!("    - ao7://checkin?id={}&action=blocked", work_item_id);
         println!("    - ao7://checkin?id={}&action=stop", work_item_id);
-        println!("✓ [DRY RUN] Would create focus block");
+        println!("{} [DRY RUN] Would create focus block", ui::Symbol::Ok);
     } else {
         let created = client.create_event(event).await?;
         let event_id = created.id.clone().unwrap_or_default();
@@ -254,16 +458,29 @@ pub async fn calendar_schedule(
         // FR3.3: Store calendar mapping in state
         let (lock_path, state_path) =
             crate::platform::state_paths(config.state.state_dir_override.as_ref())?;
-        crate::state::with_state_lock(&lock_path, &state_path, |state| {
-            state.upsert_calendar_mapping(work_item_id, event_id.clone());
-            Ok(())
-        })?;
+        crate::state::with_state_lock_timeout(
+            &lock_path,
+            &state_path,
+            config.state.lock_timeout(),
+            |state| {
+                state.upsert_calendar_mapping(work_item_id, event_id.clone());
+                Ok(())
+            },
+        )?;
+
+        if let OutputFormat::Json = format {
+            println!("{}", scheduled_json(created.id.as_deref(), &created));
+            return Ok(());
+        }
 
-        println!("✓ Focus Block scheduled");
+        println!("{} Focus Block scheduled", ui::Symbol::Ok);
         println!("  Event ID: {}", created.id.as_deref().unwrap_or("N/A"));
         println!("  Subject: {}", created.subject);
         println!("  Start: {}", created.start.date_time);
         println!("  End: {}", created.end.date_time);
+        if let Some(recurrence) = &created.recurrence {
+            println!("  Repeats: {}", describe_recurrence(recurrence));
+        }
         println!(
             "  Mapping stored: Task {} -> Event {}",
             work_item_id, event_id
@@ -273,18 +490,288 @@ pub async fn calendar_schedule(
     Ok(())
 }
 
-/// Delete calendar event
-pub async fn calendar_delete(config: &Config, event_id: String) -> Result<()> {
-    let token_cache_path = home_dir()
-        .context("Could not find home directory")?
-        .join(".ao-no-out7ook")
-        .join("tokens.json");
+/// `--format json` shape for a scheduled (or previewed) Focus Block
+fn scheduled_json(event_id: Option<&str>, event: &CalendarEvent) -> serde_json::Value {
+    serde_json::json!({
+        "event_id": event_id,
+        "subject": event.subject,
+        "start": event.start,
+        "end": event.end,
+        "categories": event.categories,
+        "show_as": event.show_as,
+    })
+}
+
+/// Reschedule an existing Focus Block (partial PATCH of start/end only)
+pub async fn calendar_reschedule(
+    config: &Config,
+    event_id: String,
+    start_time: String,
+    duration_mins: Option<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    let start = parse_start_time(&start_time, &config.work_hours.tz()?)?;
+    let duration_mins = duration_mins.unwrap_or(config.focus_blocks.duration_minutes);
+    let end = start + Duration::minutes(duration_mins as i64);
+
+    let event = CalendarEvent {
+        id: None,
+        subject: String::new(),
+        start: DateTimeTimeZone::from_utc(start, "UTC"),
+        end: DateTimeTimeZone::from_utc(end, "UTC"),
+        body: None,
+        categories: Vec::new(),
+        extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
+    };
+
+    if dry_run {
+        println!("--- DRY RUN: Calendar Reschedule Preview ---");
+        println!("  Event ID: {}", event_id);
+        println!("  New Start: {}", event.start.date_time);
+        println!("  New End: {}", event.end.date_time);
+        println!("  Duration: {} minutes", duration_mins);
+        println!("{} [DRY RUN] Would reschedule focus block", ui::Symbol::Ok);
+        return Ok(());
+    }
+
+    let auth = GraphAuthenticator::from_config(config)?;
+    let client = GraphClient::new(auth);
+
+    let updated = client.update_event(&event_id, event).await?;
 
-    let auth = GraphAuthenticator::new(config.graph.client_id.clone(), token_cache_path);
+    println!("{} Focus Block rescheduled", ui::Symbol::Ok);
+    println!("  Event ID: {}", event_id);
+    println!("  Start: {}", updated.start.date_time);
+    println!("  End: {}", updated.end.date_time);
+
+    Ok(())
+}
+
+/// Delete calendar event
+pub async fn calendar_delete(
+    config: &Config,
+    event_id: String,
+    format: OutputFormat,
+) -> Result<()> {
+    let auth = GraphAuthenticator::from_config(config)?;
     let client = GraphClient::new(auth);
 
     client.delete_event(&event_id).await?;
-    println!("✓ Event {} deleted", event_id);
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "event_id": event_id,
+                "status": "deleted"
+            })
+        );
+    } else {
+        println!("{} Event {} deleted", ui::Symbol::Ok, event_id);
+    }
+
+    Ok(())
+}
+
+/// Reconcile stored work item -> event mappings with the calendar, dropping
+/// mappings whose event was deleted in Outlook
+pub async fn calendar_sync(config: &Config, days: u32, dry_run: bool) -> Result<()> {
+    let auth = GraphAuthenticator::from_config(config)?;
+    let client = GraphClient::new(auth);
+
+    let stale = prune_stale_mappings(config, &client, days, dry_run).await?;
+
+    if stale.is_empty() {
+        println!("{} All calendar mappings are up to date", ui::Symbol::Ok);
+        return Ok(());
+    }
+
+    let prefix = if dry_run {
+        "[DRY-RUN] Would prune".to_string()
+    } else {
+        format!("{} Pruned", ui::Symbol::Ok)
+    };
+    println!("{} {} stale calendar mapping(s):", prefix, stale.len());
+    for mapping in &stale {
+        println!(
+            "  Task {} -> Event {}",
+            mapping.work_item_id, mapping.event_id
+        );
+    }
 
     Ok(())
 }
+
+/// Remove mappings whose event has been deleted.
+///
+/// Events within `days` either side of now are listed in one go; any other
+/// mapped event (e.g. a block booked further ahead) is looked up on its own and
+/// only pruned if Graph reports it missing. Returns the mappings that were (or,
+/// under `dry_run`, would be) removed.
+pub async fn prune_stale_mappings(
+    config: &Config,
+    client: &GraphClient,
+    days: u32,
+    dry_run: bool,
+) -> Result<Vec<CalendarMapping>> {
+    let (lock_path, state_path) =
+        crate::platform::state_paths(config.state.state_dir_override.as_ref())?;
+
+    let now = Utc::now();
+    let window_start = now - Duration::days(days as i64);
+    let window_end = now + Duration::days(days as i64);
+
+    let mappings = State::load(&state_path)?.calendar_mappings;
+    if mappings.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let events = client.list_events(window_start, window_end).await?;
+    let live: std::collections::HashSet<&str> =
+        events.iter().filter_map(|e| e.id.as_deref()).collect();
+    let mut deleted = std::collections::HashSet::new();
+    for mapping in &mappings {
+        if !live.contains(mapping.event_id.as_str())
+            && !client.event_exists(&mapping.event_id).await?
+        {
+            deleted.insert(mapping.event_id.clone());
+        }
+    }
+    let is_stale = |m: &CalendarMapping| deleted.contains(&m.event_id);
+
+    if dry_run {
+        return Ok(mappings.into_iter().filter(|m| is_stale(m)).collect());
+    }
+
+    // Re-check under the lock so mappings added while listing are kept
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            let (stale, keep) = std::mem::take(&mut state.calendar_mappings)
+                .into_iter()
+                .partition(|m| is_stale(m));
+            state.calendar_mappings = keep;
+            Ok(stale)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(start: &str, end: &str) -> CalendarEvent {
+        CalendarEvent {
+            id: None,
+            subject: "Meeting".to_string(),
+            start: DateTimeTimeZone {
+                date_time: start.to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            end: DateTimeTimeZone {
+                date_time: end.to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            body: None,
+            categories: vec![],
+            extended_properties: None,
+            is_all_day: false,
+            recurrence: None,
+            show_as: None,
+        }
+    }
+
+    #[test]
+    fn test_event_duration_ninety_minutes() {
+        let e = event("2026-01-08T10:00:00.0000000", "2026-01-08T11:30:00.0000000");
+        assert_eq!(event_duration(&e), "1h 30m");
+    }
+
+    #[test]
+    fn test_event_duration_all_day() {
+        assert_eq!(
+            event_duration(&event("2026-01-08", "2026-01-09")),
+            "all day"
+        );
+        assert_eq!(
+            event_duration(&event("2026-01-08T00:00:00", "2026-01-09T00:00:00")),
+            "all day"
+        );
+    }
+
+    #[test]
+    fn test_event_duration_multi_day_all_day() {
+        let mut e = event("2026-01-08T00:00:00.0000000", "2026-01-11T00:00:00.0000000");
+        e.is_all_day = true;
+        assert_eq!(event_duration(&e), "all day (3 days)");
+        assert_eq!(
+            event_duration(&event("2026-01-08", "2026-01-10")),
+            "all day (2 days)"
+        );
+    }
+
+    #[test]
+    fn test_event_duration_cross_day_timed_event() {
+        let e = event("2026-01-08T22:00:00", "2026-01-09T01:15:00");
+        assert_eq!(event_duration(&e), "3h 15m");
+    }
+
+    #[test]
+    fn test_event_duration_unparseable() {
+        assert_eq!(event_duration(&event("soon", "later")), "N/A");
+    }
+
+    fn schedule_options(count: Option<u32>, until: Option<&str>) -> ScheduleOptions {
+        ScheduleOptions {
+            start_time: None,
+            duration_mins: 45,
+            title: None,
+            all_day: false,
+            recurrence: Some(Recurrence::Daily),
+            count,
+            until: until.map(str::to_string),
+            after: None,
+        }
+    }
+
+    #[test]
+    fn test_build_recurrence_until_date() {
+        let first = NaiveDate::from_ymd_opt(2026, 1, 8).unwrap();
+
+        let recurrence = build_recurrence(&schedule_options(None, Some("2026-01-16")), first)
+            .unwrap()
+            .unwrap();
+        assert_eq!(recurrence.range.range_type, "endDate");
+        assert_eq!(describe_recurrence(&recurrence), "daily, until 2026-01-16");
+
+        assert!(build_recurrence(&schedule_options(None, Some("2026-01-01")), first).is_err());
+        assert!(build_recurrence(&schedule_options(Some(0), None), first).is_err());
+    }
+
+    #[test]
+    fn test_parse_start_time_naive_uses_work_hours_zone() {
+        let tz: Tz = "America/Los_Angeles".parse().unwrap();
+        let expected = "2026-01-08T22:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        assert_eq!(
+            parse_start_time("2026-01-08T14:00:00", &tz).unwrap(),
+            expected
+        );
+        assert_eq!(parse_start_time("2026-01-08T14:00", &tz).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_start_time_offset_ignores_work_hours_zone() {
+        let tz: Tz = "America/Los_Angeles".parse().unwrap();
+
+        assert_eq!(
+            parse_start_time("2026-01-08T14:00:00-05:00", &tz).unwrap(),
+            "2026-01-08T19:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert!(parse_start_time("tomorrow", &tz).is_err());
+    }
+}
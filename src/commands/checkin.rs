@@ -1,21 +1,51 @@
 use crate::OutputFormat;
 use crate::commands::task::state_paths;
 use crate::config::Config;
-use crate::state::with_state_lock;
-use anyhow::{Context, Result};
+use crate::devops::client::DevOpsClient;
+use crate::pace::client::PaceClient;
+use crate::state::with_state_lock_timeout;
+use crate::ui;
+use anyhow::Result;
+use clap::ValueEnum;
 
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+
+/// What to do with the current task at a check-in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CheckinChoice {
+    /// Keep working and schedule another Focus Block
+    Continue,
+    /// Stop the timer so the task can be moved to a blocked state
+    Blocked,
+    /// Stop the timer and clear the current task
+    Complete,
+}
 
 /// FR3.8: Interactive check-in prompt after Focus Block
-pub fn checkin(config: &Config, format: OutputFormat) -> Result<()> {
+///
+/// `choice` skips the prompt (for agents and scripts); without it, stdin must
+/// be a terminal. When blocked or complete, `note` (or one typed at the
+/// prompt) is added to the work item's discussion.
+pub fn checkin(
+    config: &Config,
+    choice: Option<CheckinChoice>,
+    note: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    if note.is_some() && choice == Some(CheckinChoice::Continue) {
+        anyhow::bail!("--note is only recorded for --choice blocked or complete");
+    }
     let (lock_path, state_path) = state_paths(config)?;
 
-    // If JSON format is requested, we just return the CurrentTask status
-    // Agents should use 'task state' or 'task stop' for actions
-    if let OutputFormat::Json = format {
-        let current_task = with_state_lock(&lock_path, &state_path, |state| {
-            Ok(state.current_task.clone())
-        })?;
+    // If JSON format is requested without a choice, we just return the CurrentTask
+    // status. Agents should pass --choice or use 'task state'/'task stop' for actions
+    if let (OutputFormat::Json, None) = (format, choice) {
+        let current_task = with_state_lock_timeout(
+            &lock_path,
+            &state_path,
+            config.state.lock_timeout(),
+            |state| Ok(state.current_task.clone()),
+        )?;
 
         if let Some(task) = current_task {
             println!(
@@ -40,142 +70,271 @@ pub fn checkin(config: &Config, format: OutputFormat) -> Result<()> {
     }
 
     // Get current task from state
-    let current_task = with_state_lock(&lock_path, &state_path, |state| {
-        Ok(state.current_task.clone())
-    })?;
+    let current_task = with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| Ok(state.current_task.clone()),
+    )?;
 
     let Some(task_info) = current_task else {
-        println!("❌ No active task found.");
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::json!({ "needs_action": false }));
+            return Ok(());
+        }
+        println!("{} No active task found.", ui::Symbol::Fail);
         println!("   Start a task with: task start <ID>");
         return Ok(());
     };
 
-    // Display Focus Block status
-    println!("\n🎯 Focus Block Status Check");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("Task: #{} - {}", task_info.id, task_info.title);
+    if choice.is_none() && !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "checkin needs an interactive terminal. \
+             Pass --choice continue|blocked|complete to run it non-interactively"
+        );
+    }
 
-    let elapsed = chrono::Utc::now().signed_duration_since(task_info.started_at);
-    let mins = elapsed.num_minutes();
-    println!("Timer running: {} minutes", mins);
+    // Under --format json (only reachable with --choice) the progress lines
+    // are replaced by one result object printed at the end
+    let text = !matches!(format, OutputFormat::Json);
+    let say = |line: String| {
+        if text {
+            println!("{}", line);
+        }
+    };
+    let mut warnings: Vec<String> = Vec::new();
+    let mut warn = |message: String| {
+        if text {
+            println!("{} {}", ui::Symbol::Warn, message);
+        }
+        warnings.push(message);
+    };
 
-    println!();
-    println!("What would you like to do?");
-    println!("  [1] Continue working (schedule another Focus Block)");
-    println!("  [2] I'm blocked (stop timer, update status)");
-    println!("  [3] Task complete (stop timer)");
-    println!("  [q] Cancel");
-    println!();
+    if text {
+        // Display Focus Block status
+        println!("\n{} Focus Block Status Check", ui::Symbol::Focus);
+        println!("{}", ui::rule('━', 39));
+        println!("Task: #{} - {}", task_info.id, task_info.title);
 
-    // Get user choice
-    print!("Your choice: ");
-    io::stdout().flush()?;
+        let elapsed = chrono::Utc::now().signed_duration_since(task_info.started_at);
+        let mins = elapsed.num_minutes();
+        println!("Timer running: {} minutes", mins);
+    }
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let choice = input.trim();
+    let interactive = choice.is_none();
+    let choice = match choice {
+        Some(choice) => choice,
+        None => match prompt_choice()? {
+            Some(choice) => choice,
+            None => return Ok(()),
+        },
+    };
+    let note = match note {
+        Some(note) => Some(note),
+        None if interactive && choice != CheckinChoice::Continue => prompt_note()?,
+        None => None,
+    };
 
+    let mut focus_block = None;
+    let mut timer_stopped = false;
+    let mut note_added = false;
+    let mut task_cleared = false;
     match choice {
-        "1" => {
-            println!("\n✓ Continuing work on Task {}...", task_info.id);
+        CheckinChoice::Continue => {
+            say(format!(
+                "\n{} Continuing work on Task {}...",
+                ui::Symbol::Ok,
+                task_info.id
+            ));
 
             // Schedule another Focus Block
-            println!("📅 Scheduling next Focus Block...");
+            say(format!(
+                "{} Scheduling next Focus Block...",
+                ui::Symbol::Calendar
+            ));
 
             let runtime = tokio::runtime::Runtime::new()?;
             let result = runtime.block_on(async {
-                let token_cache_path = home::home_dir()
-                    .context("Could not find home directory")?
-                    .join(".ao-no-out7ook")
-                    .join("tokens.json");
-
-                let auth = crate::graph::auth::GraphAuthenticator::new(
-                    config.graph.client_id.clone(),
-                    token_cache_path,
-                );
+                let auth = crate::graph::auth::GraphAuthenticator::from_config(config)?;
                 let client = crate::graph::client::GraphClient::new(auth);
 
                 let now = chrono::Utc::now();
                 let end_of_day = now + chrono::Duration::hours(24);
-                let events = client.list_events(now, end_of_day).await?;
+                // From a day back so blocks earlier today count towards max_per_day
+                let events = client
+                    .list_events(now - chrono::Duration::hours(24), end_of_day)
+                    .await?;
 
-                let duration = config.focus_blocks.duration_minutes;
-                let (slot_start, slot_end) = crate::graph::scheduler::find_next_slot(
+                let event = crate::commands::task::focus_block_event(
+                    config,
+                    task_info.id,
+                    &task_info.title,
                     &events,
                     now,
-                    duration,
-                    &config.work_hours,
+                    None,
                 )?;
-
-                let event = crate::graph::models::CalendarEvent {
-                    id: None,
-                    subject: format!("🎯 Focus: {} - {}", task_info.id, task_info.title),
-                    start: crate::graph::models::DateTimeTimeZone::from_utc(slot_start, "UTC"),
-                    end: crate::graph::models::DateTimeTimeZone::from_utc(slot_end, "UTC"),
-                    body: None,
-                    categories: vec!["Focus Block".to_string()],
-                    extended_properties: None,
-                };
-
                 client.create_event(event).await
             });
 
             match result {
                 Ok(created) => {
-                    println!(
-                        "✓ Next Focus Block: {} to {}",
-                        created.start.date_time, created.end.date_time
-                    );
-                }
-                Err(e) => {
-                    println!("⚠ Warning: Could not schedule Focus Block: {}", e);
+                    say(format!(
+                        "{} Next Focus Block: {} to {}",
+                        ui::Symbol::Ok,
+                        created.start.date_time,
+                        created.end.date_time
+                    ));
+                    if let Some(event_id) = &created.id {
+                        with_state_lock_timeout(
+                            &lock_path,
+                            &state_path,
+                            config.state.lock_timeout(),
+                            |state| {
+                                state.upsert_calendar_mapping(task_info.id, event_id.clone());
+                                Ok(())
+                            },
+                        )?;
+                    }
+                    focus_block = Some(serde_json::json!({
+                        "event_id": created.id,
+                        "start": created.start.date_time,
+                        "end": created.end.date_time,
+                    }));
                 }
+                Err(e) => warn(format!("Could not schedule Focus Block: {}", e)),
             }
         }
-        "2" => {
-            println!("\n⚠ Marking task as blocked...");
+        CheckinChoice::Blocked | CheckinChoice::Complete => {
+            if choice == CheckinChoice::Blocked {
+                say(format!("\n{} Marking task as blocked...", ui::Symbol::Warn));
+            } else {
+                say(format!(
+                    "\n{} Completing Task {}...",
+                    ui::Symbol::Ok,
+                    task_info.id
+                ));
+            }
 
             // Stop timer
             let pat = config.get_devops_pat()?;
-            let pace_client =
-                crate::pace::client::PaceClient::new(&pat, &config.devops.organization);
+            let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
 
             match pace_client.stop_timer(0) {
-                Ok(_) => println!("✓ Timer stopped"),
-                Err(e) => println!("⚠ Could not stop timer: {}", e),
+                Ok(_) => {
+                    say(format!("{} Timer stopped", ui::Symbol::Ok));
+                    timer_stopped = true;
+                }
+                Err(e) => warn(format!("Could not stop timer: {}", e)),
+            }
+            if let Some(text) = &note {
+                match add_note(config, &pat, task_info.id, text) {
+                    Ok(()) => {
+                        say(format!(
+                            "{} Note added to Task {}",
+                            ui::Symbol::Ok,
+                            task_info.id
+                        ));
+                        note_added = true;
+                    }
+                    // The timer is already stopped, so a failed note only warns
+                    Err(e) => warn(format!("Could not add note: {:#}", e)),
+                }
             }
 
-            println!("💡 Tip: Update task state with: task state <NEW_STATE>");
+            if choice == CheckinChoice::Blocked {
+                say(format!(
+                    "{} Tip: Update task state with: task state <NEW_STATE>",
+                    ui::Symbol::Tip
+                ));
+            } else {
+                // Clear current task from state
+                with_state_lock_timeout(
+                    &lock_path,
+                    &state_path,
+                    config.state.lock_timeout(),
+                    |state| {
+                        state.finish_current_task(chrono::Utc::now());
+                        state.save(&state_path)
+                    },
+                )?;
+                task_cleared = true;
+
+                say(format!("{} Task cleared from state", ui::Symbol::Ok));
+                say(format!(
+                    "{} Start next task with: task start <ID>",
+                    ui::Symbol::Tip
+                ));
+            }
         }
-        "3" => {
-            println!("\n✓ Completing Task {}...", task_info.id);
+    }
 
-            // Stop timer
-            let pat = config.get_devops_pat()?;
-            let pace_client =
-                crate::pace::client::PaceClient::new(&pat, &config.devops.organization);
+    if !text {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": task_info.id,
+                "choice": choice.to_possible_value().map(|v| v.get_name().to_string()),
+                "timer_stopped": timer_stopped,
+                "note_added": note_added,
+                "focus_block": focus_block,
+                "task_cleared": task_cleared,
+                "warnings": warnings,
+            })
+        );
+    }
 
-            match pace_client.stop_timer(0) {
-                Ok(_) => println!("✓ Timer stopped"),
-                Err(e) => println!("⚠ Could not stop timer: {}", e),
-            }
+    Ok(())
+}
+
+/// Add the check-in note as a work item comment
+fn add_note(config: &Config, pat: &str, id: u32, text: &str) -> Result<()> {
+    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+    client.add_comment(id, text)?;
+    Ok(())
+}
 
-            // Clear current task from state
-            with_state_lock(&lock_path, &state_path, |state| {
-                state.current_task = None;
-                state.save(&state_path)
-            })?;
+/// Ask for an optional note; `None` when left empty
+fn prompt_note() -> Result<Option<String>> {
+    print!("Note (optional, Enter to skip): ");
+    io::stdout().flush()?;
 
-            println!("✓ Task cleared from state");
-            println!("💡 Start next task with: task start <ID>");
-        }
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let note = input.trim();
+    Ok((!note.is_empty()).then(|| note.to_string()))
+}
+
+/// Show the menu and read a choice; `None` when the user cancels
+fn prompt_choice() -> Result<Option<CheckinChoice>> {
+    println!();
+    println!("What would you like to do?");
+    println!("  [1] Continue working (schedule another Focus Block)");
+    println!("  [2] I'm blocked (stop timer, update status)");
+    println!("  [3] Task complete (stop timer)");
+    println!("  [q] Cancel");
+    println!();
+
+    // Get user choice
+    print!("Your choice: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(match input.trim() {
+        "1" => Some(CheckinChoice::Continue),
+        "2" => Some(CheckinChoice::Blocked),
+        "3" => Some(CheckinChoice::Complete),
         "q" | "Q" => {
             println!("\nCancelled.");
+            None
         }
         _ => {
-            println!("\n❌ Invalid choice. Cancelled.");
+            println!("\n{} Invalid choice. Cancelled.", ui::Symbol::Fail);
+            None
         }
-    }
-
-    Ok(())
+    })
 }
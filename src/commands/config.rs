@@ -1,5 +1,8 @@
 use crate::config::Config;
+use crate::ui;
 use anyhow::{Context, Result};
+use std::path::Path;
+use toml_edit::DocumentMut;
 
 pub fn list(config: &Config) -> Result<()> {
     // Pretty print config as TOML
@@ -9,7 +12,28 @@ pub fn list(config: &Config) -> Result<()> {
     Ok(())
 }
 
-pub fn get(key: &str, config: &Config) -> Result<()> {
+/// Show only the last 4 characters of a secret, e.g. `****abcd`
+pub fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 4 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
+
+pub fn get(key: &str, config: &Config, reveal: bool) -> Result<()> {
+    // The PAT normally lives in the keyring, so the serialized config shows null
+    if key == "devops.pat" {
+        let pat = config.get_devops_pat()?;
+        if reveal {
+            println!("{}", pat);
+        } else {
+            println!("{}", mask_secret(&pat));
+        }
+        return Ok(());
+    }
+
     // Use serde_json::to_value to inspect fields dynamically by key path
     // Simple implementation: convert to Value and walk path
     let value = serde_json::to_value(config).context("Failed to serialize config")?;
@@ -32,17 +56,90 @@ pub fn get(key: &str, config: &Config) -> Result<()> {
 }
 
 pub fn set(key: &str, value: &str) -> Result<()> {
-    // For MVP, implementing "set" is tricky because we need to preserve comments in TOML
-    // The `config` crate is mostly for reading.
-    // `toml_edit` crate is better for preserving structure, but we didn't add it.
-    //
-    // Fallback: Load raw TOML string, parse with `toml` (serde), update, save.
-    // This loses comments.
-    // For Phase 1 MVP, we can warn user or just append/update.
-
-    println!(
-        "Config set not fully implemented in MVP. Please edit ~/.ao-no-out7ook/config.toml manually."
-    );
-    println!("Requested change: {} = {}", key, value);
+    // The PAT never touches the config file; it goes straight to the keyring
+    if key == "devops.pat" {
+        crate::keyring::store_devops_pat(value).context("Failed to store PAT in keyring")?;
+        println!("{} devops.pat stored in system keyring", ui::Symbol::Ok);
+        return Ok(());
+    }
+
+    let path = crate::config::config_path()?;
+    set_at_path(&path, key, value)?;
+    println!("{} {} = {}", ui::Symbol::Ok, key, value);
+    Ok(())
+}
+
+/// Set a dotted key in a TOML config file, preserving comments and formatting.
+///
+/// The value is coerced to the type of the matching `Config` field (number, bool,
+/// list); keys without a known type are stored as strings.
+pub fn set_at_path(path: &Path, key: &str, value: &str) -> Result<()> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|p| p.is_empty()) {
+        anyhow::bail!("Invalid config key '{}'", key);
+    }
+
+    let content = if path.exists() {
+        std::fs::read_to_string(path).context("Failed to read config file")?
+    } else {
+        String::new()
+    };
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .context("Failed to parse config file")?;
+
+    let new_value = coerce_value(key, value)?;
+
+    // Walk (and create on demand) the parent tables
+    let (leaf, sections) = parts.split_last().context("Empty config key")?;
+    let mut table = doc.as_table_mut();
+    for section in sections {
+        let item = table.entry(section).or_insert(toml_edit::table());
+        table = item
+            .as_table_mut()
+            .with_context(|| format!("Config key '{}' is not a section", section))?;
+    }
+    table[leaf] = toml_edit::value(new_value);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, doc.to_string()).context("Failed to write config file")?;
+
     Ok(())
 }
+
+/// Convert a CLI string into a TOML value matching the type of the `Config` field
+fn coerce_value(key: &str, value: &str) -> Result<toml_edit::Value> {
+    let defaults = serde_json::to_value(Config::default()).context("Failed to serialize config")?;
+    let expected = key
+        .split('.')
+        .try_fold(&defaults, |current, part| current.get(part));
+
+    let coerced = match expected {
+        Some(serde_json::Value::Bool(_)) => value
+            .parse::<bool>()
+            .map(toml_edit::Value::from)
+            .map_err(|_| anyhow::anyhow!("Invalid value for {}: expected true or false", key))?,
+        Some(serde_json::Value::Number(n)) if n.is_f64() => value
+            .parse::<f64>()
+            .map(toml_edit::Value::from)
+            .map_err(|_| anyhow::anyhow!("Invalid value for {}: expected a number", key))?,
+        Some(serde_json::Value::Number(_)) => value
+            .parse::<u32>()
+            .map(|n| toml_edit::Value::from(i64::from(n)))
+            .map_err(|_| anyhow::anyhow!("Invalid value for {}: expected a whole number", key))?,
+        Some(serde_json::Value::Array(_)) => value
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect::<toml_edit::Array>()
+            .into(),
+        Some(serde_json::Value::Object(_)) => {
+            anyhow::bail!("'{}' is a section; set one of its keys instead", key)
+        }
+        _ => toml_edit::Value::from(value),
+    };
+
+    Ok(coerced)
+}
@@ -1,179 +1,432 @@
 use crate::OutputFormat;
+use crate::commands::task::state_paths;
 use crate::config::Config;
 use crate::devops::client::DevOpsClient;
+use crate::devops::models::{WorkItem, WorkItemComment, WorkItemSummary};
+use crate::devops::store::WorkItemStore;
+use crate::state::{SavedQuery, State, with_state_lock_timeout};
+use crate::ui;
+use crate::utils::csv;
+use crate::utils::markdown::strip_html_tags;
+use crate::utils::text::{format_bytes, truncate_chars};
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+const CSV_LIST_HEADER: &str = "id,title,state,priority,type";
+
+/// Work item filters for `list`, translated into WIQL conditions (FR1.2)
+#[derive(Debug, Default, Clone)]
+pub struct ListFilters {
+    pub state: Option<String>,
+    /// States to leave out, each as a `<>` condition
+    pub exclude_states: Vec<String>,
+    pub assigned_to: Option<String>,
+    pub search: Option<String>,
+    pub tags: Option<String>,
+    /// Iteration path, matched with UNDER so child sprints are included
+    pub iteration: Option<String>,
+    /// Area path, matched with UNDER
+    pub area: Option<String>,
+    /// Raw WIQL condition ANDed with the filters above (e.g. a saved query's WHERE clause)
+    pub query: Option<String>,
+    /// Keep items in `skip_states` (Closed, Completed, ...), as does setting
+    /// `state`; Removed is always hidden
+    pub include_closed: bool,
+    /// Only items changed on or after this day/instant
+    pub changed_since: Option<WiqlDate>,
+    /// Only items created on or after this day/instant
+    pub created_since: Option<WiqlDate>,
+}
 
-pub fn list(
-    config: &Config,
-    state: Option<String>,
-    assigned_to: Option<String>,
-    search: Option<String>,
-    tags: Option<String>,
-    limit: Option<u32>,
-    format: OutputFormat,
-) -> Result<()> {
-    let pat = config
-        .devops
-        .pat
-        .as_deref()
-        .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
-    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
-    if let Some(url) = &config.devops.api_url {
-        client = client.with_base_url(url);
-    }
-
-    let mut conditions = vec![
-        "[System.TeamProject] = @project".to_string(),
-        "[System.State] <> 'Removed'".to_string(),
-    ];
+/// Lower bound for a WIQL date field: a whole day, or an exact instant
+/// (which needs the query to run with time precision)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WiqlDate {
+    Day(NaiveDate),
+    Instant(DateTime<Utc>),
+}
 
-    if let Some(s) = state {
-        conditions.push(format!("[System.State] = '{}'", s));
+impl WiqlDate {
+    /// Parse `YYYY-MM-DD`, RFC 3339, or `YYYY-MM-DDTHH:MM[:SS]` (taken as UTC)
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(Self::Day(day));
+        }
+        if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Self::Instant(ts.with_timezone(&Utc)));
+        }
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+            .map(|naive| Self::Instant(naive.and_utc()))
+            .with_context(|| {
+                format!(
+                    "Expected YYYY-MM-DD or a timestamp like 2026-01-05T09:00:00Z, got '{}'",
+                    value
+                )
+            })
     }
 
-    if let Some(user) = assigned_to {
-        if user == "me" {
-            conditions.push("[System.AssignedTo] = @me".to_string());
-        } else {
-            conditions.push(format!("[System.AssignedTo] = '{}'", user));
+    fn wiql_literal(&self) -> String {
+        match self {
+            Self::Day(day) => day.format("%Y-%m-%d").to_string(),
+            Self::Instant(ts) => ts.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
         }
     }
+}
 
-    // FR1.2: Search by title
-    if let Some(term) = search {
-        // Escape single quotes for SQL injection prevention
-        let escaped = term.replace("'", "''");
-        conditions.push(format!("[System.Title] CONTAINS '{}'", escaped));
-    }
+impl ListFilters {
+    fn conditions(&self, skip_states: &[String]) -> Vec<String> {
+        let mut conditions = vec![
+            "[System.TeamProject] = @project".to_string(),
+            "[System.State] <> 'Removed'".to_string(),
+        ];
+
+        // An explicit --state already picks the states to show, skipped ones included
+        if !self.include_closed && self.state.is_none() && !skip_states.is_empty() {
+            let states: Vec<String> = skip_states
+                .iter()
+                .map(|s| format!("'{}'", s.replace("'", "''")))
+                .collect();
+            conditions.push(format!("[System.State] NOT IN ({})", states.join(", ")));
+        }
 
-    // FR1.2: Filter by tags
-    if let Some(tag) = tags {
-        let escaped = tag.replace("'", "''");
-        conditions.push(format!("[System.Tags] CONTAINS '{}'", escaped));
-    }
+        if let Some(s) = &self.state {
+            conditions.push(format!("[System.State] = '{}'", s));
+        }
 
-    // FR1.15: Default sort by priority then changed date
-    let order_clause = "ORDER BY [Microsoft.VSTS.Common.Priority] ASC, [System.ChangedDate] DESC";
+        for excluded in &self.exclude_states {
+            let escaped = excluded.replace("'", "''");
+            conditions.push(format!("[System.State] <> '{}'", escaped));
+        }
 
-    let query = format!(
-        "SELECT [System.Id] FROM WorkItems WHERE {} {}",
-        conditions.join(" AND "),
-        order_clause
-    );
+        if let Some(user) = &self.assigned_to {
+            if user.eq_ignore_ascii_case("me") || user.eq_ignore_ascii_case("@me") {
+                conditions.push("[System.AssignedTo] = @me".to_string());
+            } else {
+                conditions.push(format!("[System.AssignedTo] = '{}'", user));
+            }
+        }
 
-    let wiql_resp = client.execute_wiql(&query)?;
+        // FR1.2: Search by title
+        if let Some(term) = &self.search {
+            // Escape single quotes for SQL injection prevention
+            let escaped = term.replace("'", "''");
+            conditions.push(format!("[System.Title] CONTAINS '{}'", escaped));
+        }
 
-    let ids: Vec<u32> = wiql_resp
-        .work_items
-        .iter()
-        .take(limit.unwrap_or(50) as usize)
-        .map(|r| r.id)
-        .collect();
+        // FR1.2: Filter by tags
+        if let Some(tag) = &self.tags {
+            let escaped = tag.replace("'", "''");
+            conditions.push(format!("[System.Tags] CONTAINS '{}'", escaped));
+        }
 
-    if ids.is_empty() {
-        println!("No work items found.");
-        return Ok(());
+        if let Some(iteration) = &self.iteration {
+            let escaped = iteration.replace("'", "''");
+            conditions.push(format!("[System.IterationPath] UNDER '{}'", escaped));
+        }
+
+        if let Some(area) = &self.area {
+            let escaped = area.replace("'", "''");
+            conditions.push(format!("[System.AreaPath] UNDER '{}'", escaped));
+        }
+
+        if let Some(since) = &self.changed_since {
+            conditions.push(format!(
+                "[System.ChangedDate] >= '{}'",
+                since.wiql_literal()
+            ));
+        }
+
+        if let Some(since) = &self.created_since {
+            conditions.push(format!(
+                "[System.CreatedDate] >= '{}'",
+                since.wiql_literal()
+            ));
+        }
+
+        if let Some(query) = &self.query {
+            conditions.push(format!("({})", query));
+        }
+
+        conditions
     }
 
-    let items = client.get_work_items_batch(&ids)?;
+    /// Full WIQL query selecting matching ids in the given order.
+    /// `skip_states` are excluded unless `include_closed` is set.
+    pub fn to_wiql(&self, sort: Option<ListSort>, skip_states: &[String]) -> String {
+        format!(
+            "SELECT [System.Id] FROM WorkItems WHERE {} {}",
+            self.conditions(skip_states).join(" AND "),
+            ListSort::order_clause(sort)
+        )
+    }
 
-    if let OutputFormat::Json = format {
-        println!("{}", serde_json::to_string_pretty(&items)?);
-        return Ok(());
+    /// WIQL rejects times in date comparisons unless run with time precision
+    pub fn needs_time_precision(&self) -> bool {
+        [self.changed_since, self.created_since]
+            .iter()
+            .any(|d| matches!(d, Some(WiqlDate::Instant(_))))
     }
 
-    println!(
-        "{:<8} {:<50} {:<15} {:<5} {:<10}",
-        "ID", "Title", "State", "Prio", "Type"
-    );
-    println!("{}", "-".repeat(90));
+    /// Filters for a saved query; dates are parsed on every run so a bad
+    /// value is reported the same way as on the command line
+    pub fn from_saved(query: &SavedQuery) -> Result<Self> {
+        Ok(Self {
+            state: query.state.clone(),
+            exclude_states: query.exclude_states.clone(),
+            assigned_to: query.assigned_to.clone(),
+            search: query.search.clone(),
+            tags: query.tags.clone(),
+            iteration: query.iteration.clone(),
+            area: query.area.clone(),
+            include_closed: query.include_closed,
+            created_since: query
+                .created_after
+                .as_deref()
+                .map(WiqlDate::parse)
+                .transpose()
+                .context("Invalid --created-after")?,
+            changed_since: query
+                .changed_after
+                .as_deref()
+                .map(WiqlDate::parse)
+                .transpose()
+                .context("Invalid --changed-after")?,
+            ..Default::default()
+        })
+    }
+}
 
-    for item in items {
-        let id = item.id;
-        let title = item.get_title().unwrap_or("No Title");
-        let state = item.get_state().unwrap_or("Unknown");
-        let type_ = item.get_type().unwrap_or("Unknown");
-        let prio = item
-            .fields
-            .get("Microsoft.VSTS.Common.Priority")
-            .map(|v| v.to_string())
-            .unwrap_or(" ".to_string());
+/// Sort order for `list` (FR1.15)
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum ListSort {
+    Priority,
+    Changed,
+    Created,
+    Title,
+}
 
-        let title = if title.len() > 48 {
-            format!("{}...", &title[0..45])
-        } else {
-            title.to_string()
-        };
+impl ListSort {
+    /// Name as accepted by `--sort`
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
 
-        println!(
-            "{:<8} {:<50} {:<15} {:<5} {:<10}",
-            id, title, state, prio, type_
-        );
+    pub fn from_name(name: &str) -> Result<Self> {
+        <Self as ValueEnum>::from_str(name, true)
+            .map_err(|_| anyhow::anyhow!("Unknown sort order '{}'", name))
     }
 
-    Ok(())
+    fn order_clause(sort: Option<Self>) -> &'static str {
+        match sort {
+            Some(ListSort::Priority) => "ORDER BY [Microsoft.VSTS.Common.Priority] ASC",
+            Some(ListSort::Changed) => "ORDER BY [System.ChangedDate] DESC",
+            Some(ListSort::Created) => "ORDER BY [System.CreatedDate] DESC",
+            Some(ListSort::Title) => "ORDER BY [System.Title] ASC",
+            // Default: priority then most recently changed
+            None => "ORDER BY [Microsoft.VSTS.Common.Priority] ASC, [System.ChangedDate] DESC",
+        }
+    }
 }
 
-// Helper function for testing custom sort (will be used when we add --sort flag to CLI)
-#[allow(dead_code)]
-pub fn list_with_sort(
+pub fn list(
     config: &Config,
-    state: Option<String>,
-    assigned_to: Option<String>,
-    search: Option<String>,
-    tags: Option<String>,
-    sort_by: &str,
+    filters: ListFilters,
     limit: Option<u32>,
+    format: OutputFormat,
 ) -> Result<()> {
-    let pat = config.devops.pat.as_deref().context("DevOps PAT not set")?;
-    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
-    if let Some(url) = &config.devops.api_url {
-        client = client.with_base_url(url);
-    }
+    list_with_sort(config, filters, None, limit, &[], false, format)
+}
 
-    let mut conditions = vec![
-        "[System.TeamProject] = @project".to_string(),
-        "[System.State] <> 'Removed'".to_string(),
-    ];
+/// Totals for `list --summary`; missing estimates count as 0
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct ListSummary {
+    pub count: usize,
+    pub total_effort: f64,
+    pub total_story_points: f64,
+    pub by_state: BTreeMap<String, usize>,
+}
 
-    if let Some(s) = state {
-        conditions.push(format!("[System.State] = '{}'", s));
+impl ListSummary {
+    pub fn from_items(items: &[WorkItem]) -> Self {
+        let mut summary = Self {
+            count: items.len(),
+            ..Self::default()
+        };
+        for item in items {
+            summary.total_effort += item.get_effort().unwrap_or(0.0);
+            summary.total_story_points += item.get_story_points().unwrap_or(0.0);
+            *summary
+                .by_state
+                .entry(item.get_state().unwrap_or("Unknown").to_string())
+                .or_default() += 1;
+        }
+        summary
     }
+}
 
-    if let Some(user) = assigned_to {
-        if user == "me" {
-            conditions.push("[System.AssignedTo] = @me".to_string());
+/// Store `query` under `name`, replacing any saved query with that name
+pub fn save_query(config: &Config, name: &str, query: SavedQuery) -> Result<()> {
+    if name.trim().is_empty() {
+        anyhow::bail!("Saved query name must not be empty");
+    }
+    let (lock_path, state_path) = state_paths(config)?;
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            state.saved_queries.insert(name.to_string(), query);
+            Ok(())
+        },
+    )
+}
+
+/// Look up a query saved with `list --save`
+pub fn saved_query(config: &Config, name: &str) -> Result<SavedQuery> {
+    let (_lock_path, state_path) = state_paths(config)?;
+    let mut state = State::load(&state_path)?;
+    state.saved_queries.remove(name).with_context(|| {
+        let mut names: Vec<_> = state.saved_queries.keys().cloned().collect();
+        names.sort();
+        if names.is_empty() {
+            format!("No saved query named '{}'. Save one with list --save", name)
         } else {
-            conditions.push(format!("[System.AssignedTo] = '{}'", user));
+            format!(
+                "No saved query named '{}'. Saved queries: {}",
+                name,
+                names.join(", ")
+            )
         }
-    }
+    })
+}
 
-    if let Some(term) = search {
-        let escaped = term.replace("'", "''");
-        conditions.push(format!("[System.Title] CONTAINS '{}'", escaped));
+/// Print the saved queries, sorted by name
+pub fn list_saved_queries(config: &Config, format: OutputFormat) -> Result<()> {
+    let (_lock_path, state_path) = state_paths(config)?;
+    let queries: BTreeMap<_, _> = State::load(&state_path)?
+        .saved_queries
+        .into_iter()
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&queries)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&queries)?),
+        OutputFormat::Csv | OutputFormat::Text if queries.is_empty() => {
+            println!("No saved queries. Save one with list --save <NAME>.");
+        }
+        OutputFormat::Csv | OutputFormat::Text => {
+            for (name, query) in &queries {
+                println!("{:<20} {}", name, saved_query_args(query));
+            }
+        }
     }
+    Ok(())
+}
 
-    if let Some(tag) = tags {
-        let escaped = tag.replace("'", "''");
-        conditions.push(format!("[System.Tags] CONTAINS '{}'", escaped));
+/// The `list` flags a saved query stands for
+fn saved_query_args(query: &SavedQuery) -> String {
+    let mut args = Vec::new();
+    for (flag, value) in [
+        ("--state", &query.state),
+        ("--assigned-to", &query.assigned_to),
+        ("--search", &query.search),
+        ("--tags", &query.tags),
+        ("--iteration", &query.iteration),
+        ("--area", &query.area),
+        ("--created-after", &query.created_after),
+        ("--changed-after", &query.changed_after),
+        ("--sort", &query.sort),
+    ] {
+        if let Some(value) = value {
+            args.push(format!("{} '{}'", flag, value));
+        }
+    }
+    for excluded in &query.exclude_states {
+        args.push(format!("--exclude-state '{}'", excluded));
+    }
+    if query.include_closed {
+        args.push("--include-closed".to_string());
+    }
+    if args.is_empty() {
+        "(no filters)".to_string()
+    } else {
+        args.join(" ")
     }
+}
 
-    // FR1.15: Configurable sorting
-    let order_clause = match sort_by {
-        "priority" => "ORDER BY [Microsoft.VSTS.Common.Priority] ASC",
-        "changed" => "ORDER BY [System.ChangedDate] DESC",
-        "created" => "ORDER BY [System.CreatedDate] DESC",
-        "title" => "ORDER BY [System.Title] ASC",
-        _ => "ORDER BY [Microsoft.VSTS.Common.Priority] ASC, [System.ChangedDate] DESC",
+/// Like [`list`], with an explicit sort order and, when `select` is not
+/// empty, those field reference names as the columns instead of the defaults
+pub fn list_with_sort(
+    config: &Config,
+    filters: ListFilters,
+    sort: Option<ListSort>,
+    limit: Option<u32>,
+    select: &[String],
+    summary: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let pat = config
+        .devops
+        .pat
+        .as_deref()
+        .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
+    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+    let options = ListOptions {
+        sort,
+        limit,
+        select,
+        summary,
     };
+    list_with_store(config, &client, filters, &options, format)
+}
 
-    let query = format!(
-        "SELECT [System.Id] FROM WorkItems WHERE {} {}",
-        conditions.join(" AND "),
-        order_clause
-    );
+/// How `list` orders and shapes its output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListOptions<'a> {
+    pub sort: Option<ListSort>,
+    /// Most items to show (50 when unset)
+    pub limit: Option<u32>,
+    /// Field reference names to show instead of the default columns
+    pub select: &'a [String],
+    /// Wrap JSON output as `{items, summary}`
+    pub summary: bool,
+}
 
-    let wiql_resp = client.execute_wiql(&query)?;
+/// Like [`list_with_sort`], against any [`WorkItemStore`]
+pub fn list_with_store(
+    config: &Config,
+    client: &impl WorkItemStore,
+    filters: ListFilters,
+    options: &ListOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    let ListOptions {
+        sort,
+        limit,
+        select,
+        summary,
+    } = *options;
+    if summary && !matches!(format, OutputFormat::Json) {
+        anyhow::bail!("--summary requires --format json");
+    }
+
+    // FR1.15: Configurable sorting
+    let query = filters.to_wiql(sort, &config.devops.skip_states);
+
+    let wiql_resp = client.execute_wiql_with_precision(&query, filters.needs_time_precision())?;
 
     let ids: Vec<u32> = wiql_resp
         .work_items
@@ -182,13 +435,78 @@ pub fn list_with_sort(
         .map(|r| r.id)
         .collect();
 
+    let select: Vec<&str> = select
+        .iter()
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty())
+        .collect();
+
     if ids.is_empty() {
-        println!("No work items found.");
+        if summary {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "items": [],
+                    "summary": ListSummary::default(),
+                }))?
+            );
+        } else if let OutputFormat::Csv = format {
+            if select.is_empty() {
+                println!("{}", CSV_LIST_HEADER);
+            } else {
+                println!("{}", csv::to_row(&selected_header(&select)));
+            }
+        } else {
+            println!("No work items found.");
+        }
         return Ok(());
     }
 
     let items = client.get_work_items_batch(&ids)?;
 
+    if !select.is_empty() {
+        print_selected(&items, &select, format)?;
+        return Ok(());
+    }
+
+    if summary {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "summary": ListSummary::from_items(&items),
+                "items": items,
+            }))?
+        );
+        return Ok(());
+    }
+
+    if let OutputFormat::Json = format {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+
+    if let OutputFormat::Csv = format {
+        println!("{}", CSV_LIST_HEADER);
+        for item in &items {
+            let prio = item
+                .fields
+                .get("Microsoft.VSTS.Common.Priority")
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            println!(
+                "{}",
+                csv::to_row(&[
+                    item.id.to_string().as_str(),
+                    item.get_title().unwrap_or(""),
+                    item.get_state().unwrap_or(""),
+                    &prio,
+                    item.get_type().unwrap_or(""),
+                ])
+            );
+        }
+        return Ok(());
+    }
+
     println!(
         "{:<8} {:<50} {:<15} {:<5} {:<10}",
         "ID", "Title", "State", "Prio", "Type"
@@ -206,11 +524,7 @@ pub fn list_with_sort(
             .map(|v| v.to_string())
             .unwrap_or(" ".to_string());
 
-        let title = if title.len() > 48 {
-            format!("{}...", &title[0..45])
-        } else {
-            title.to_string()
-        };
+        let title = truncate_chars(title, 48);
 
         println!(
             "{:<8} {:<50} {:<15} {:<5} {:<10}",
@@ -221,17 +535,216 @@ pub fn list_with_sort(
     Ok(())
 }
 
-pub fn show(config: &Config, id: u32, format: OutputFormat) -> Result<()> {
+/// Widest a `--select` column gets in text output
+const MAX_SELECT_COLUMN_WIDTH: usize = 40;
+
+/// Field value for `--select` output: identities collapse to their display
+/// name, everything else is passed through
+fn select_value(value: Option<&serde_json::Value>) -> serde_json::Value {
+    match value {
+        Some(serde_json::Value::Object(map)) => ["displayName", "uniqueName", "name"]
+            .iter()
+            .find_map(|key| map.get(*key).filter(|v| v.is_string()).cloned())
+            .unwrap_or_else(|| serde_json::Value::Object(map.clone())),
+        Some(value) => value.clone(),
+        None => serde_json::Value::Null,
+    }
+}
+
+/// Plain-text rendering of a [`select_value`]
+fn select_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn selected_header<'a>(select: &[&'a str]) -> Vec<&'a str> {
+    std::iter::once("id")
+        .chain(select.iter().copied())
+        .collect()
+}
+
+/// Print `items` with `id` plus the `--select` fields as the only columns
+fn print_selected(
+    items: &[crate::devops::models::WorkItem],
+    select: &[&str],
+    format: OutputFormat,
+) -> Result<()> {
+    let rows: Vec<Vec<serde_json::Value>> = items
+        .iter()
+        .map(|item| {
+            select
+                .iter()
+                .map(|field| select_value(item.fields.get(*field)))
+                .collect()
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let objects: Vec<serde_json::Value> = items
+                .iter()
+                .zip(&rows)
+                .map(|(item, row)| {
+                    let mut object = serde_json::Map::new();
+                    object.insert("id".to_string(), serde_json::json!(item.id));
+                    for (field, value) in select.iter().zip(row) {
+                        object.insert(field.to_string(), value.clone());
+                    }
+                    serde_json::Value::Object(object)
+                })
+                .collect();
+            if let OutputFormat::Yaml = format {
+                print!("{}", serde_yaml::to_string(&objects)?);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&objects)?);
+            }
+        }
+        OutputFormat::Csv => {
+            println!("{}", csv::to_row(&selected_header(select)));
+            for (item, row) in items.iter().zip(&rows) {
+                let cells: Vec<String> = std::iter::once(item.id.to_string())
+                    .chain(row.iter().map(select_text))
+                    .collect();
+                println!("{}", csv::to_row(&cells));
+            }
+        }
+        OutputFormat::Text => {
+            // Short names keep the header readable: "Microsoft.VSTS.Common.Priority" -> "Priority"
+            let headers: Vec<&str> = select
+                .iter()
+                .map(|field| field.rsplit('.').next().unwrap_or(field))
+                .collect();
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|v| truncate_chars(&select_text(v), MAX_SELECT_COLUMN_WIDTH))
+                        .collect()
+                })
+                .collect();
+            let widths: Vec<usize> = headers
+                .iter()
+                .enumerate()
+                .map(|(col, header)| {
+                    cells
+                        .iter()
+                        .map(|row| row[col].chars().count())
+                        .chain(std::iter::once(header.chars().count()))
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+
+            let line = |id: &str, values: &[String]| {
+                let mut out = format!("{:<8}", id);
+                for (value, width) in values.iter().zip(&widths) {
+                    out.push_str(&format!(" {:<width$}", value, width = *width));
+                }
+                out.trim_end().to_string()
+            };
+            let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+            println!("{}", line("ID", &header_cells));
+            println!(
+                "{}",
+                "-".repeat(8 + widths.iter().map(|w| w + 1).sum::<usize>())
+            );
+            for (item, row) in items.iter().zip(&cells) {
+                println!("{}", line(&item.id.to_string(), row));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Open a URL in the default browser, printing it when no browser is available
+/// (headless systems). Structured formats keep stdout clean by using stderr.
+fn open_in_browser(url: &str, format: OutputFormat) {
+    let message = match webbrowser::open(url) {
+        Ok(()) => format!("{} Opened {}", ui::Symbol::Ok, url),
+        Err(_) => format!("Open in browser: {}", url),
+    };
+    if let OutputFormat::Text = format {
+        println!("\n{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+/// Deepest hierarchy `show` will walk; each level costs at least one API call
+pub const MAX_SHOW_DEPTH: u8 = 5;
+
+/// Options for `show`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShowOptions {
+    /// Also open the item in the DevOps web UI
+    pub open: bool,
+    /// Levels of children in the hierarchy
+    pub depth: u8,
+    /// Print the work item JSON exactly as returned
+    pub raw: bool,
+    /// Sum effort across the hierarchy
+    pub effort_rollup: bool,
+    /// Include the discussion, newest first
+    pub comments: bool,
+}
+
+pub fn show(config: &Config, id: u32, options: &ShowOptions, format: OutputFormat) -> Result<()> {
+    let ShowOptions {
+        open,
+        depth,
+        raw,
+        effort_rollup,
+        comments,
+    } = *options;
     let pat = config
         .devops
         .pat
         .as_deref()
         .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
-    let client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
     let item = client.get_work_item(id)?;
 
-    if let OutputFormat::Json = format {
-        println!("{}", serde_json::to_string_pretty(&item)?);
+    // Unprocessed dump for discovering field reference names; going through
+    // `Value` sorts the `fields` keys
+    if raw {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::to_value(&item)?)?
+        );
+        return Ok(());
+    }
+
+    if open && !matches!(format, OutputFormat::Text) {
+        open_in_browser(&config.devops.work_item_url(id), format);
+    }
+
+    let comments = if comments {
+        Some(client.get_comments(id)?)
+    } else {
+        None
+    };
+
+    if let OutputFormat::Json | OutputFormat::Yaml = format {
+        let mut value = if let OutputFormat::Json = format {
+            serde_json::to_value(&item)?
+        } else {
+            serde_json::to_value(WorkItemSummary::from(&item))?
+        };
+        if let (Some(comments), Some(object)) = (&comments, value.as_object_mut()) {
+            object.insert("comments".to_string(), comments_json(comments));
+        }
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        } else {
+            print!("{}", serde_yaml::to_string(&value)?);
+        }
         return Ok(());
     }
 
@@ -247,10 +760,28 @@ pub fn show(config: &Config, id: u32, format: OutputFormat) -> Result<()> {
         item.get_assigned_to().unwrap_or("Unassigned")
     );
 
-    match crate::devops::hierarchy::build_tree(&client, id, 1) {
+    let depth = depth.min(MAX_SHOW_DEPTH);
+    match crate::devops::hierarchy::build_tree_parallel(
+        &client,
+        item.clone(),
+        depth,
+        config.devops.hierarchy_threads,
+    ) {
         Ok(node) => {
             println!("\nHierarchy:");
             crate::devops::hierarchy::print_tree(&node);
+            if effort_rollup {
+                let (count, total) = node.effort_rollup();
+                println!(
+                    "Total effort ({} items): {}h",
+                    count,
+                    (total * 100.0).round() / 100.0
+                );
+            }
+        }
+        // A partial total would be misleading, so only the rollup reports the failure
+        Err(e) if effort_rollup => {
+            return Err(e).context("Failed to fetch the hierarchy for --effort-rollup");
         }
         Err(_e) => {
             // Silently skip if hierarchy can't be built
@@ -278,16 +809,75 @@ pub fn show(config: &Config, id: u32, format: OutputFormat) -> Result<()> {
         println!("(No description)");
     }
 
+    if let Some(comments) = &comments {
+        println!("\nComments ({}):", comments.len());
+        for comment in comments {
+            println!(
+                "- {} ({}):",
+                comment.author().unwrap_or("Unknown"),
+                comment
+                    .created_date
+                    .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "?".to_string())
+            );
+            for line in strip_html_tags(&comment.text).lines() {
+                println!("  {}", line);
+            }
+        }
+    }
+
+    if open {
+        open_in_browser(&config.devops.work_item_url(id), format);
+    }
+
     Ok(())
 }
 
-pub fn state(config: &Config, id: u32, new_state: Option<String>, dry_run: bool) -> Result<()> {
+/// Comments for `show --format json`, with HTML stripped from the text
+fn comments_json(comments: &[WorkItemComment]) -> serde_json::Value {
+    comments
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "id": c.id,
+                "author": c.author(),
+                "created_date": c.created_date,
+                "text": strip_html_tags(&c.text),
+            })
+        })
+        .collect()
+}
+
+/// Show or change a work item's state. `comment` is posted after a successful
+/// transition to record why; a failed comment only warns.
+pub fn state(
+    config: &Config,
+    id: u32,
+    new_state: Option<String>,
+    comment: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
     let pat = config
         .devops
         .pat
         .as_deref()
         .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
-    let client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+    state_with_store(config, &client, id, new_state, comment, dry_run)
+}
+
+/// Like [`state`], against any [`WorkItemStore`]
+pub fn state_with_store(
+    config: &Config,
+    client: &impl WorkItemStore,
+    id: u32,
+    new_state: Option<String>,
+    comment: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
     let item = client.get_work_item(id)?;
     let current_state = item.get_state().unwrap_or("Unknown");
     let type_ = item.get_type().context("Work item has no type")?;
@@ -304,6 +894,9 @@ pub fn state(config: &Config, id: u32, new_state: Option<String>, dry_run: bool)
             return Ok(());
         }
 
+        // Workflow rules aren't exposed by the type definition, so use the configured map
+        config.devops.check_transition(current_state, &target)?;
+
         let patch = serde_json::json!([
             {
                 "op": "add",
@@ -323,9 +916,35 @@ pub fn state(config: &Config, id: u32, new_state: Option<String>, dry_run: bool)
                 "[DRY-RUN] Patch operations: {}",
                 serde_json::to_string_pretty(&patch)?
             );
+            if let Some(text) = &comment {
+                println!("[DRY-RUN] Would comment: {}", text);
+            }
         } else {
             client.update_work_item_with_rev(id, patch_vec, Some(item.rev))?;
-            println!("✓ Task {} updated: {} -> {}", id, current_state, target);
+            println!(
+                "{} Task {} updated: {} -> {}",
+                ui::Symbol::Ok,
+                id,
+                current_state,
+                target
+            );
+
+            // The transition already happened, so don't fail the command over the comment
+            if let Some(text) = &comment {
+                match client.add_comment(id, text) {
+                    Ok(created) => println!(
+                        "{} Comment {} added to Task {}",
+                        ui::Symbol::Ok,
+                        created.id,
+                        id
+                    ),
+                    Err(e) => eprintln!(
+                        "{} Warning: State changed but comment failed: {:#}",
+                        ui::Symbol::Warn,
+                        e
+                    ),
+                }
+            }
         }
     } else {
         println!("Current State: {}", current_state);
@@ -366,15 +985,54 @@ pub fn import(_config: &Config, _file: std::path::PathBuf, _dry_run: bool) -> Re
     )
 }
 
-/// FR1.13: Update work item fields (assigned-to, priority, tags)
-pub fn update(
-    config: &Config,
-    id: u32,
-    assigned_to: Option<String>,
-    priority: Option<u32>,
-    tags: Option<String>,
-    dry_run: bool,
-) -> Result<()> {
+/// Field changes for `update`; unset fields are left untouched
+#[derive(Debug, Default, Clone)]
+pub struct WorkItemUpdate {
+    /// New title; surrounding whitespace is trimmed
+    pub title: Option<String>,
+    pub assigned_to: Option<String>,
+    pub priority: Option<u32>,
+    /// Comma-separated, as on the command line
+    pub tags: Option<String>,
+    /// Comma-separated tags merged into the existing set
+    pub tags_add: Option<String>,
+    /// Comma-separated tags dropped from the existing set
+    pub tags_remove: Option<String>,
+    pub effort: Option<f32>,
+    pub remaining_work: Option<f32>,
+    /// Full iteration path, e.g. `Project\Sprint 12`
+    pub iteration: Option<String>,
+    /// Full area path
+    pub area: Option<String>,
+}
+
+/// FR1.13: Update work item fields (title, assigned-to, priority, tags, effort,
+/// remaining work, iteration, area)
+pub fn update(config: &Config, id: u32, changes: WorkItemUpdate, dry_run: bool) -> Result<()> {
+    let WorkItemUpdate {
+        title,
+        assigned_to,
+        priority,
+        tags,
+        tags_add,
+        tags_remove,
+        effort,
+        remaining_work,
+        iteration,
+        area,
+    } = changes;
+
+    if tags.is_some() && (tags_add.is_some() || tags_remove.is_some()) {
+        anyhow::bail!(
+            "--tags replaces all tags and can't be combined with --tags-add/--tags-remove"
+        );
+    }
+
+    let title = title.map(|t| t.trim().to_string());
+    if title.as_deref().is_some_and(str::is_empty) {
+        anyhow::bail!("--title must not be empty");
+    }
+
     let pat = config
         .devops
         .pat
@@ -388,9 +1046,21 @@ pub fn update(
     // Fetch current work item to get rev
     let item = client.get_work_item(id)?;
 
+    let assigned_to = assigned_to
+        .map(|user| resolve_assignee(config, &client, &user))
+        .transpose()?;
+
     // Build JSON Patch operations
     let mut operations = Vec::new();
 
+    if let Some(ref new_title) = title {
+        operations.push(serde_json::json!({
+            "op": "add",
+            "path": "/fields/System.Title",
+            "value": new_title
+        }));
+    }
+
     if let Some(ref user) = assigned_to {
         operations.push(serde_json::json!({
             "op": "add",
@@ -400,10 +1070,7 @@ pub fn update(
     }
 
     if let Some(p) = priority {
-        // Validate priority range
-        if !(1..=4).contains(&p) {
-            anyhow::bail!("Priority must be between 1 and 4 (inclusive). Got: {}", p);
-        }
+        validate_priority(p)?;
         operations.push(serde_json::json!({
             "op": "add",
             "path": "/fields/Microsoft.VSTS.Common.Priority",
@@ -411,22 +1078,61 @@ pub fn update(
         }));
     }
 
-    if let Some(ref tags_input) = tags {
-        // Convert comma-separated to semicolon-separated (DevOps format)
-        let formatted_tags = tags_input
-            .split(',')
-            .map(|s| s.trim())
-            .collect::<Vec<_>>()
-            .join("; ");
+    let tags = match (tags, &tags_add, &tags_remove) {
+        (Some(tags_input), _, _) => Some(devops_tags(&tags_input)),
+        (None, None, None) => None,
+        (None, add, remove) => Some(merge_tags(
+            &item.get_tags().unwrap_or_default(),
+            add.as_deref().unwrap_or(""),
+            remove.as_deref().unwrap_or(""),
+        )),
+    };
+
+    if let Some(ref tags_value) = tags {
         operations.push(serde_json::json!({
             "op": "add",
             "path": "/fields/System.Tags",
-            "value": formatted_tags
+            "value": tags_value
         }));
     }
 
+    for (flag, field, value) in [
+        ("--effort", "Microsoft.VSTS.Scheduling.Effort", effort),
+        (
+            "--remaining-work",
+            "Microsoft.VSTS.Scheduling.RemainingWork",
+            remaining_work,
+        ),
+    ] {
+        if let Some(v) = value {
+            if !v.is_finite() || v < 0.0 {
+                anyhow::bail!("{} must be a non-negative number. Got: {}", flag, v);
+            }
+            operations.push(serde_json::json!({
+                "op": "add",
+                "path": format!("/fields/{}", field),
+                "value": v
+            }));
+        }
+    }
+
+    for (field, value) in [
+        ("System.IterationPath", &iteration),
+        ("System.AreaPath", &area),
+    ] {
+        if let Some(path) = value {
+            operations.push(serde_json::json!({
+                "op": "add",
+                "path": format!("/fields/{}", field),
+                "value": path
+            }));
+        }
+    }
+
     if operations.is_empty() {
-        println!("No fields to update. Specify --assigned-to, --priority, or --tags");
+        println!(
+            "No fields to update. Specify --title, --assigned-to, --priority, --tags, --tags-add, --tags-remove, --effort, --remaining-work, --iteration, or --area"
+        );
         return Ok(());
     }
 
@@ -438,7 +1144,10 @@ pub fn update(
 
     client.update_work_item_with_rev(id, operations, Some(item.rev))?;
 
-    println!("✓ Task {} updated successfully", id);
+    println!("{} Task {} updated successfully", ui::Symbol::Ok, id);
+    if let Some(t) = title {
+        println!("  - Title: {}", t);
+    }
     if let Some(user) = assigned_to {
         println!("  - Assigned To: {}", user);
     }
@@ -448,6 +1157,621 @@ pub fn update(
     if let Some(t) = tags {
         println!("  - Tags: {}", t);
     }
+    if let Some(e) = effort {
+        println!("  - Effort: {}", e);
+    }
+    if let Some(r) = remaining_work {
+        println!("  - Remaining Work: {}", r);
+    }
+    if let Some(i) = iteration {
+        println!("  - Iteration: {}", i);
+    }
+    if let Some(a) = area {
+        println!("  - Area: {}", a);
+    }
 
     Ok(())
 }
+
+/// Turn an --assigned-to value into something System.AssignedTo accepts.
+/// "me" becomes the PAT owner's account and display names are resolved to a
+/// unique account name; emails pass through unchanged.
+fn resolve_assignee(config: &Config, client: &DevOpsClient, user: &str) -> Result<String> {
+    if user.eq_ignore_ascii_case("me") || user.eq_ignore_ascii_case("@me") {
+        config.current_user_or_resolve(|| client.get_authenticated_user())
+    } else if user.contains('@') {
+        Ok(user.to_string())
+    } else {
+        client.resolve_identity(user)
+    }
+}
+
+fn validate_priority(priority: u32) -> Result<()> {
+    if !(1..=4).contains(&priority) {
+        anyhow::bail!(
+            "Priority must be between 1 and 4 (inclusive). Got: {}",
+            priority
+        );
+    }
+    Ok(())
+}
+
+/// Convert comma-separated tags to semicolon-separated (DevOps format)
+fn devops_tags(input: &str) -> String {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Apply comma-separated `add`/`remove` lists to `existing` tags, returning the
+/// `System.Tags` value. DevOps tags are case-insensitive, so matching is too;
+/// existing tags keep their order and spelling, new ones are appended.
+pub fn merge_tags(existing: &[String], add: &str, remove: &str) -> String {
+    let split = |input: &str| -> Vec<String> {
+        input
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let remove = split(remove);
+    let is_removed = |tag: &str| remove.iter().any(|r| r.eq_ignore_ascii_case(tag));
+
+    let mut merged: Vec<String> = Vec::new();
+    for tag in existing.iter().cloned().chain(split(add)) {
+        if !is_removed(&tag) && !merged.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            merged.push(tag);
+        }
+    }
+    merged.join("; ")
+}
+
+/// Fields for a one-off work item created with `create`
+#[derive(Debug, Default, Clone)]
+pub struct NewWorkItem {
+    pub work_item_type: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub parent: Option<u32>,
+    pub assigned_to: Option<String>,
+    pub priority: Option<u32>,
+    /// Comma-separated, as on the command line
+    pub tags: Option<String>,
+}
+
+/// Create a single work item, optionally linked to a parent
+pub fn create(
+    config: &Config,
+    item: NewWorkItem,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    if item.title.trim().is_empty() {
+        anyhow::bail!("Title must not be empty");
+    }
+    if let Some(p) = item.priority {
+        validate_priority(p)?;
+    }
+
+    let assigned_to = item
+        .assigned_to
+        .map(|user| resolve_assignee(config, &client, &user))
+        .transpose()?;
+
+    let mut fields = serde_json::Map::new();
+    fields.insert(
+        "System.WorkItemType".into(),
+        item.work_item_type.clone().into(),
+    );
+    fields.insert("System.Title".into(), item.title.clone().into());
+    if let Some(description) = item.description {
+        fields.insert("System.Description".into(), description.into());
+    }
+    if let Some(user) = assigned_to {
+        fields.insert("System.AssignedTo".into(), user.into());
+    }
+    if let Some(p) = item.priority {
+        fields.insert("Microsoft.VSTS.Common.Priority".into(), p.into());
+    }
+    if let Some(tags) = &item.tags {
+        fields.insert("System.Tags".into(), devops_tags(tags).into());
+    }
+
+    // Validate the parent up front so a typo doesn't leave an orphaned item
+    let parent = item
+        .parent
+        .map(|id| {
+            client
+                .get_work_item(id)
+                .with_context(|| format!("Parent work item {} not found", id))
+        })
+        .transpose()?;
+
+    if dry_run {
+        println!(
+            "[DRY-RUN] Would create {}: {}",
+            item.work_item_type, item.title
+        );
+        println!("{}", serde_json::to_string_pretty(&fields)?);
+        if let Some(parent) = &parent {
+            println!("[DRY-RUN] Would link to parent #{}", parent.id);
+        }
+        return Ok(());
+    }
+
+    let created =
+        client.create_work_item_with_parent(fields, parent.as_ref().map(|p| p.url.as_str()))?;
+    let url = config.devops.work_item_url(created.id);
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": created.id,
+                "url": url
+            })
+        );
+    } else {
+        println!(
+            "{} Created {} #{}: {}",
+            ui::Symbol::Ok,
+            item.work_item_type,
+            created.id,
+            item.title
+        );
+        if let Some(parent) = &parent {
+            println!("  - Parent: #{}", parent.id);
+        }
+        println!("  {}", url);
+    }
+
+    Ok(())
+}
+
+/// Remove a work item: move it to the `Removed` state, or with `permanent`
+/// delete it (to the recycle bin). Asks first unless `yes` is set, so without
+/// a terminal `yes` is required.
+pub fn delete(config: &Config, id: u32, permanent: bool, yes: bool, dry_run: bool) -> Result<()> {
+    if !yes && !dry_run && !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "delete needs an interactive terminal to confirm. \
+             Pass --yes to run it non-interactively"
+        );
+    }
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    let item = client.get_work_item(id)?;
+    let type_ = item.get_type().unwrap_or("Work item");
+    let title = item.get_title().unwrap_or("Untitled");
+    let action = if permanent {
+        "Delete".to_string()
+    } else {
+        format!(
+            "Move to Removed (from {})",
+            item.get_state().unwrap_or("Unknown")
+        )
+    };
+
+    if dry_run {
+        println!("[DRY-RUN] {} {} #{}: {}", action, type_, id, title);
+        return Ok(());
+    }
+
+    if !yes && !ui::confirm(&format!("{} {} #{} '{}'?", action, type_, id, title))? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    if permanent {
+        client.delete_work_item(id)?;
+        println!("{} {} {} deleted", ui::Symbol::Ok, type_, id);
+    } else {
+        let patch = vec![serde_json::json!({
+            "op": "add",
+            "path": "/fields/System.State",
+            "value": "Removed"
+        })];
+        client.update_work_item_with_rev(id, patch, Some(item.rev))?;
+        println!("{} {} {} moved to Removed", ui::Symbol::Ok, type_, id);
+    }
+
+    Ok(())
+}
+
+/// Relation added by `link`, as seen from the source item
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum LinkType {
+    Related,
+    /// The target must finish before the source
+    Predecessor,
+    /// The target follows the source
+    Successor,
+    /// The target becomes the source's parent
+    Parent,
+}
+
+impl LinkType {
+    /// Azure DevOps link type reference name
+    pub fn rel(self) -> &'static str {
+        match self {
+            LinkType::Related => "System.LinkTypes.Related",
+            LinkType::Predecessor => "System.LinkTypes.Dependency-Reverse",
+            LinkType::Successor => "System.LinkTypes.Dependency-Forward",
+            LinkType::Parent => "System.LinkTypes.Hierarchy-Reverse",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LinkType::Related => "related",
+            LinkType::Predecessor => "predecessor",
+            LinkType::Successor => "successor",
+            LinkType::Parent => "parent",
+        }
+    }
+}
+
+/// Link `from` to `to` with `link_type`, or with `remove` delete that link
+pub fn link(
+    config: &Config,
+    from: u32,
+    to: u32,
+    link_type: LinkType,
+    remove: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if from == to {
+        anyhow::bail!("Can't link work item {} to itself", from);
+    }
+
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    link_with_store(&client, from, to, link_type, remove, dry_run)
+}
+
+pub fn link_with_store(
+    client: &impl WorkItemStore,
+    from: u32,
+    to: u32,
+    link_type: LinkType,
+    remove: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let item = client.get_work_item(from)?;
+    // Relations point at `.../workItems/{id}`; the id is what identifies the target
+    let existing = item.relations.iter().flatten().position(|r| {
+        r.rel == link_type.rel() && r.url.split('/').next_back() == Some(to.to_string().as_str())
+    });
+
+    let operation = if remove {
+        let index = existing.with_context(|| {
+            format!(
+                "Work item {} has no {} link to {}",
+                from,
+                link_type.label(),
+                to
+            )
+        })?;
+        serde_json::json!({ "op": "remove", "path": format!("/relations/{}", index) })
+    } else {
+        if existing.is_some() {
+            println!(
+                "Work item {} already has a {} link to {}",
+                from,
+                link_type.label(),
+                to
+            );
+            return Ok(());
+        }
+        let target = client
+            .get_work_item(to)
+            .with_context(|| format!("Link target #{} not found", to))?;
+        serde_json::json!({
+            "op": "add",
+            "path": "/relations/-",
+            "value": { "rel": link_type.rel(), "url": target.url }
+        })
+    };
+
+    let (action, done) = if remove {
+        ("Unlink", "Unlinked")
+    } else {
+        ("Link", "Linked")
+    };
+    if dry_run {
+        println!(
+            "[DRY-RUN] {} {} -> {} ({})",
+            action,
+            from,
+            to,
+            link_type.label()
+        );
+        return Ok(());
+    }
+
+    // The rev check guards the relation index against concurrent edits
+    client.update_work_item_with_rev(from, vec![operation], Some(item.rev))?;
+    println!(
+        "{} {} {} -> {} ({})",
+        ui::Symbol::Ok,
+        done,
+        from,
+        to,
+        link_type.label()
+    );
+
+    Ok(())
+}
+
+/// List a work item's attachments, downloading all of them into
+/// `download_dir` when given
+pub fn attachments(
+    config: &Config,
+    id: u32,
+    download_dir: Option<&Path>,
+    format: OutputFormat,
+) -> Result<()> {
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    let attachments = client.list_attachments(id)?;
+
+    let mut saved: Vec<Option<PathBuf>> = vec![None; attachments.len()];
+    if let Some(dir) = download_dir {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let mut used = std::collections::HashSet::new();
+        for (attachment, saved) in attachments.iter().zip(saved.iter_mut()) {
+            let dest = dir.join(unique_file_name(&attachment.name, &mut used));
+            let bytes = client
+                .download_attachment(&attachment.url, &dest)
+                .with_context(|| format!("Failed to download '{}'", attachment.name))?;
+            if let OutputFormat::Text = format {
+                println!(
+                    "{} Downloaded {} ({}) -> {}",
+                    ui::Symbol::Ok,
+                    attachment.name,
+                    format_bytes(bytes),
+                    dest.display()
+                );
+            }
+            *saved = Some(dest);
+        }
+    }
+
+    if let OutputFormat::Json = format {
+        let output: Vec<_> = attachments
+            .iter()
+            .zip(&saved)
+            .map(|(attachment, path)| {
+                serde_json::json!({
+                    "name": attachment.name,
+                    "size": attachment.size,
+                    "url": attachment.url,
+                    "path": path,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if download_dir.is_some() {
+        return Ok(());
+    }
+    if attachments.is_empty() {
+        println!("No attachments on #{}.", id);
+        return Ok(());
+    }
+    println!("Attachments on #{}:", id);
+    for attachment in &attachments {
+        let size = attachment.size.map(format_bytes).unwrap_or_default();
+        println!(
+            "  {:<50} {:>10}",
+            truncate_chars(&attachment.name, 50),
+            size
+        );
+    }
+
+    Ok(())
+}
+
+/// File name for a downloaded attachment: path components are stripped so a
+/// name can't escape the target directory, and repeats get a " (n)" suffix
+fn unique_file_name(name: &str, used: &mut std::collections::HashSet<String>) -> String {
+    let base = Path::new(name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .filter(|n| !n.is_empty())
+        .unwrap_or("attachment")
+        .to_string();
+
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while !used.insert(candidate.clone()) {
+        let path = Path::new(&base);
+        candidate = match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(ext)) => format!(
+                "{} ({}).{}",
+                stem.to_string_lossy(),
+                n,
+                ext.to_string_lossy()
+            ),
+            _ => format!("{} ({})", base, n),
+        };
+        n += 1;
+    }
+    candidate
+}
+
+/// Post a progress comment on a work item without changing its state
+pub fn comment(config: &Config, id: u32, text: &str, format: OutputFormat) -> Result<()> {
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    let created = client.add_comment(id, text)?;
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": created.id,
+                "work_item_id": id,
+                "text": created.text
+            })
+        );
+    } else {
+        println!(
+            "{} Comment {} added to Task {}",
+            ui::Symbol::Ok,
+            created.id,
+            id
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devops::store::mock::MockStore;
+
+    #[test]
+    fn test_state_patches_at_current_rev_and_comments() {
+        let store = MockStore::with_task(42, 7, "Active");
+        state_with_store(
+            &Config::default(),
+            &store,
+            42,
+            Some("Closed".to_string()),
+            Some("Shipped".to_string()),
+            false,
+        )
+        .unwrap();
+
+        let updates = store.updates.borrow();
+        assert_eq!(updates.len(), 1);
+        let (id, operations, expected_rev) = &updates[0];
+        assert_eq!((*id, *expected_rev), (42, Some(7)));
+        assert_eq!(operations[0]["path"], "/fields/System.State");
+        assert_eq!(operations[0]["value"], "Closed");
+        assert_eq!(*store.comments.borrow(), vec![(42, "Shipped".to_string())]);
+    }
+
+    #[test]
+    fn test_state_rejects_unknown_state_and_disallowed_transition() {
+        let store = MockStore::with_task(42, 7, "New");
+        state_with_store(
+            &Config::default(),
+            &store,
+            42,
+            Some("Resolved".to_string()),
+            None,
+            false,
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config
+            .devops
+            .transitions
+            .insert("new".to_string(), vec!["Active".to_string()]);
+        let err = state_with_store(&config, &store, 42, Some("Closed".to_string()), None, false)
+            .unwrap_err();
+        assert!(err.to_string().contains("New -> Closed is not allowed"));
+        assert!(store.updates.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_state_dry_run_writes_nothing() {
+        let store = MockStore::with_task(42, 7, "New");
+        state_with_store(
+            &Config::default(),
+            &store,
+            42,
+            Some("Active".to_string()),
+            Some("Starting".to_string()),
+            true,
+        )
+        .unwrap();
+
+        assert!(store.updates.borrow().is_empty());
+        assert!(store.comments.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_list_queries_store_with_filters() {
+        let store = MockStore::with_task(42, 7, "Active");
+        let filters = ListFilters {
+            state: Some("Active".to_string()),
+            ..Default::default()
+        };
+        list_with_store(
+            &Config::default(),
+            &store,
+            filters,
+            &ListOptions {
+                summary: true,
+                ..Default::default()
+            },
+            OutputFormat::Json,
+        )
+        .unwrap();
+
+        let queries = store.queries.borrow();
+        assert_eq!(queries.len(), 1);
+        assert!(queries[0].contains("[System.State] = 'Active'"));
+
+        let err = list_with_store(
+            &Config::default(),
+            &store,
+            ListFilters::default(),
+            &ListOptions {
+                summary: true,
+                ..Default::default()
+            },
+            OutputFormat::Text,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--summary requires --format json"));
+    }
+
+    #[test]
+    fn test_link_adds_relation_at_current_rev() {
+        let mut store = MockStore::with_task(42, 7, "Active");
+        let target = MockStore::with_task(43, 1, "New");
+        store.items.extend(target.items);
+
+        link_with_store(&store, 42, 43, LinkType::Related, false, false).unwrap();
+
+        let updates = store.updates.borrow();
+        assert_eq!(updates.len(), 1);
+        let (id, operations, expected_rev) = &updates[0];
+        assert_eq!((*id, *expected_rev), (42, Some(7)));
+        assert_eq!(operations[0]["op"], "add");
+        assert_eq!(operations[0]["value"]["url"], store.items[&43].url);
+    }
+}
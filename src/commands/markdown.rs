@@ -1,38 +1,185 @@
+use crate::commands::devops::{ListFilters, WiqlDate};
 use crate::config::Config;
 use crate::devops::client::DevOpsClient;
+use crate::devops::hierarchy::build_tree_parallel;
+use crate::devops::models::WorkItem;
+use crate::ui;
 use crate::utils::markdown::{
-    Severity, display_validation_errors, from_markdown, to_markdown, validate_markdown_structure,
+    ExportTemplate, ParsedWorkItem, Severity, display_validation_errors, from_markdown,
+    header_level, strip_html_tags, to_markdown, validate_markdown_structure,
 };
-use anyhow::Result;
+use crate::utils::text::truncate_chars;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Safety cap on how many levels `--hierarchy` descends below each root
+const EXPORT_MAX_DEPTH: u8 = 10;
+
 /// Export work items to markdown (FR4.1)
 /// Exports ALL items including completed (full state snapshot)
-/// If dry_run is true, prints markdown to stdout instead of writing to file
+/// If dry_run is true, prints markdown to stdout instead of writing to file.
+/// `since` (YYYY-MM-DD) limits a query export to items changed on or after that day.
 pub fn export(
     config: &Config,
     ids: Vec<u32>,
+    query: Option<String>,
+    since: Option<&str>,
     hierarchy: bool,
     output: &Path,
     dry_run: bool,
 ) -> Result<()> {
+    let options = ExportOptions {
+        hierarchy,
+        output: Some(output),
+        dry_run,
+        template: None,
+        json: None,
+    };
+    export_with_options(config, ids, query, since, &options)
+}
+
+/// Where and how `export` writes its markdown
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOptions<'a> {
+    /// Include all descendants of each item
+    pub hierarchy: bool,
+    /// Markdown file
+    pub output: Option<&'a Path>,
+    pub dry_run: bool,
+    /// Handlebars template rendered per item instead of the built-in format
+    pub template: Option<&'a Path>,
+    /// Also write the exported work items, as returned by DevOps, as a JSON array
+    pub json: Option<&'a Path>,
+}
+
+/// Like [`export`], with every output option, including a custom template and JSON
+pub fn export_with_options(
+    config: &Config,
+    ids: Vec<u32>,
+    query: Option<String>,
+    since: Option<&str>,
+    options: &ExportOptions,
+) -> Result<()> {
+    let ExportOptions {
+        hierarchy,
+        output,
+        dry_run,
+        template,
+        json,
+    } = *options;
+    if output.is_none() && json.is_none() {
+        anyhow::bail!("Specify an output file with --output and/or --json");
+    }
+    let since = since.map(parse_since).transpose()?;
+    // Read the template before contacting DevOps so mistakes fail fast
+    let template = template
+        .map(|path| {
+            let source = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template {}", path.display()))?;
+            ExportTemplate::parse(&source)
+        })
+        .transpose()?;
+    // --since alone means "everything changed since", resolved through WIQL
+    let by_query = query.is_some() || since.is_some();
+
     let pat = config.get_devops_pat()?;
-    let client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
 
-    // Fetch work items
-    let items: Vec<_> = if hierarchy {
-        // TODO: Use get_hierarchy_items when available
-        ids.iter()
-            .map(|id| client.get_work_item(*id))
-            .collect::<Result<Vec<_>>>()?
+    // Resolve ids from a WIQL condition, reusing the list filters
+    let ids = if by_query {
+        // Export is a full snapshot, so closed items are kept
+        let filters = ListFilters {
+            query: query.clone(),
+            include_closed: true,
+            changed_since: since.map(WiqlDate::Day),
+            ..Default::default()
+        };
+        let wiql_resp = client.execute_wiql(&filters.to_wiql(None, &[]))?;
+        wiql_resp.work_items.iter().map(|r| r.id).collect()
+    } else {
+        ids
+    };
+    if ids.is_empty() {
+        if by_query {
+            println!("No work items match the query.");
+            return Ok(());
+        }
+        anyhow::bail!("Specify work items with --ids or --query");
+    }
+
+    // Fetch the requested items
+    let mut roots: Vec<_> = if by_query {
+        client.get_work_items_batch(&ids)?
     } else {
         ids.iter()
             .map(|id| client.get_work_item(*id))
             .collect::<Result<Vec<_>>>()?
     };
 
-    // Generate markdown using to_markdown
-    let markdown = if hierarchy {
+    // Epics before Features before Stories, keeping relative order otherwise
+    if hierarchy || by_query {
+        roots.sort_by_key(|wi| header_level(wi.get_type().unwrap_or_default()));
+    }
+
+    // Expand descendants depth-first so children follow their parents
+    let items = if hierarchy {
+        let mut seen = HashSet::new();
+        let mut items = Vec::new();
+        for root in roots {
+            if seen.contains(&root.id) {
+                continue;
+            }
+            let tree = build_tree_parallel(
+                &client,
+                root,
+                EXPORT_MAX_DEPTH,
+                config.devops.hierarchy_threads,
+            )?;
+            items.extend(tree.flatten().into_iter().filter(|wi| seen.insert(wi.id)));
+        }
+        items
+    } else {
+        roots
+    };
+
+    if let Some(json_path) = json {
+        if dry_run {
+            println!(
+                "--- Would write {} items as JSON to: {} ---",
+                items.len(),
+                json_path.display()
+            );
+        } else {
+            std::fs::write(json_path, serde_json::to_string_pretty(&items)?)
+                .with_context(|| format!("Failed to write {}", json_path.display()))?;
+            println!(
+                "{} Exported {} items as JSON to {}",
+                ui::Symbol::Ok,
+                items.len(),
+                json_path.display()
+            );
+        }
+    }
+
+    let Some(output) = output else {
+        return Ok(());
+    };
+
+    // Generate markdown using the template, or to_markdown
+    let markdown = if let Some(template) = &template {
+        // The template controls spacing between items
+        items
+            .iter()
+            .map(|wi| template.render(wi))
+            .collect::<Result<Vec<_>>>()?
+            .concat()
+    } else if hierarchy {
         // For hierarchy, we want to maintain structure
         items.iter().map(to_markdown).collect::<Vec<_>>().join("\n")
     } else {
@@ -47,14 +194,29 @@ pub fn export(
         println!("--- DRY RUN: Export Preview ---");
         println!("{}", markdown);
         println!("--- Would write to: {} ---", output.display());
-        println!("✓ [DRY RUN] Would export {} items", items.len());
+        println!(
+            "{} [DRY RUN] Would export {} items",
+            ui::Symbol::Ok,
+            items.len()
+        );
     } else {
         std::fs::write(output, markdown)?;
-        println!("✓ Exported {} items to {}", items.len(), output.display());
+        println!(
+            "{} Exported {} items to {}",
+            ui::Symbol::Ok,
+            items.len(),
+            output.display()
+        );
     }
     Ok(())
 }
 
+/// Parse an `--since` day (YYYY-MM-DD)
+fn parse_since(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .with_context(|| format!("Invalid --since date '{}'. Use YYYY-MM-DD", value))
+}
+
 /// Import work items from markdown (FR4.2, FR4.3)
 /// Skips completed/resolved/closed items by default
 pub fn import(
@@ -63,7 +225,69 @@ pub fn import(
     dry_run: bool,
     validate_only: bool,
     force: bool,
+    default_parent: Option<u32>,
+    create_missing_parents: bool,
 ) -> Result<()> {
+    let options = ImportOptions {
+        dry_run,
+        validate_only,
+        force,
+        default_parent,
+        create_missing_parents,
+        mode: ImportMode::All,
+    };
+    import_with_options(config, file, &options)
+}
+
+/// Which items `import` may touch, to guard against accidental duplicates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    #[default]
+    All,
+    /// Update items that have an id; skip new ones
+    UpdateOnly,
+    /// Create items without an id; skip existing ones
+    CreateOnly,
+}
+
+impl ImportMode {
+    /// Warning for an item this mode leaves alone, or `None` to import it
+    fn skip_reason(self, item: &ParsedWorkItem) -> Option<&'static str> {
+        let is_new = item.id.unwrap_or(0) == 0;
+        match self {
+            ImportMode::UpdateOnly if is_new => Some("no id, --update-only"),
+            ImportMode::CreateOnly if !is_new => Some("has an id, --create-only"),
+            _ => None,
+        }
+    }
+}
+
+/// Options for `import`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Preview changes without applying them
+    pub dry_run: bool,
+    /// Only check the markdown structure
+    pub validate_only: bool,
+    /// Import items in `skip_states` too
+    pub force: bool,
+    /// Parent for new items without a `**Parent:**` entry
+    pub default_parent: Option<u32>,
+    /// Let `#0` link to a higher-level item listed later in the file
+    pub create_missing_parents: bool,
+    pub mode: ImportMode,
+}
+
+/// Like [`import`], with every option, including the update-only/create-only mode
+pub fn import_with_options(config: &Config, file: &Path, options: &ImportOptions) -> Result<()> {
+    let ImportOptions {
+        dry_run,
+        validate_only,
+        force,
+        default_parent,
+        create_missing_parents,
+        mode,
+    } = *options;
     let markdown = std::fs::read_to_string(file)?;
 
     // FR4.3: Validation
@@ -81,7 +305,7 @@ pub fn import(
     }
 
     if validate_only {
-        println!("✓ Markdown is valid");
+        println!("{} Markdown is valid", ui::Symbol::Ok);
         return Ok(());
     }
 
@@ -111,7 +335,8 @@ pub fn import(
 
                 if is_closed {
                     println!(
-                        "⊘ Skipping {} item: {} #{} (state: {}) (use --force to import)",
+                        "{} Skipping {} item: {} #{} (state: {}) (use --force to import)",
+                        ui::Symbol::Skip,
                         item.work_item_type,
                         item.title,
                         item.id.unwrap_or(0),
@@ -123,25 +348,58 @@ pub fn import(
             .collect()
     };
 
-    if dry_run {
-        println!("[DRY-RUN] Would import {} items:", filtered_items.len());
-        for item in &filtered_items {
-            println!(
-                "  - {} #{}: {}",
-                item.work_item_type,
-                item.id.unwrap_or(0),
-                item.title
-            );
+    // Work out every parent link up front so parents are created first
+    let mut parents = resolve_parents(&filtered_items, default_parent, create_missing_parents)?;
+    let mut order = creation_order(&filtered_items, &parents)?;
+
+    // Skipped items stay in the file's parent structure: a new item under a
+    // skipped existing one links to it by id instead
+    let skipped: Vec<bool> = filtered_items
+        .iter()
+        .map(|item| match mode.skip_reason(item) {
+            Some(reason) => {
+                println!(
+                    "{} Skipping {} item: {} #{} ({})",
+                    ui::Symbol::Warn,
+                    item.work_item_type,
+                    item.title,
+                    item.id.unwrap_or(0),
+                    reason
+                );
+                true
+            }
+            None => false,
+        })
+        .collect();
+    order.retain(|idx| !skipped[*idx]);
+    for parent in parents.iter_mut() {
+        if let Some(ParentRef::InFile(parent_idx)) = *parent
+            && skipped[parent_idx]
+            && let Some(id) = filtered_items[parent_idx].id.filter(|id| *id != 0)
+        {
+            *parent = Some(ParentRef::Existing(id));
         }
-        return Ok(());
+    }
+
+    if dry_run {
+        return preview_import(config, &filtered_items, &parents, &order);
     }
 
     // Import to DevOps
     let pat = config.get_devops_pat()?;
-    let client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    // (id, url) of each file item once it exists in DevOps, by index
+    let mut imported: HashMap<usize, (u32, String)> = HashMap::new();
+    let mut parent_urls: HashMap<u32, String> = HashMap::new();
 
-    for item in filtered_items {
-        if let Some(id) = item.id {
+    for idx in order {
+        let item = &filtered_items[idx];
+
+        if let Some(id) = item.id.filter(|id| *id != 0) {
             // Update existing work item
             println!("Updating {} #{}...", item.work_item_type, id);
 
@@ -163,8 +421,9 @@ pub fn import(
                 }));
             }
 
-            client.update_work_item(id, operations)?;
-            println!("✓ Updated #{}", id);
+            let updated = client.update_work_item(id, operations)?;
+            println!("{} Updated #{}", ui::Symbol::Ok, id);
+            imported.insert(idx, (id, updated.url));
         } else {
             // Create new work item
             println!("Creating new {} '{}'...", item.work_item_type, item.title);
@@ -187,10 +446,408 @@ pub fn import(
                 );
             }
 
-            let new_item = client.create_work_item(fields)?;
-            println!("✓ Created #{}", new_item.id);
+            let parent_url = match parents[idx] {
+                Some(ParentRef::InFile(parent_idx)) => {
+                    // creation_order guarantees the parent was imported already
+                    let (parent_id, url) = &imported[&parent_idx];
+                    if filtered_items[parent_idx].id.unwrap_or(0) == 0 {
+                        println!("  Linking to new parent #{}", parent_id);
+                    } else {
+                        println!("  Linking to parent #{}", parent_id);
+                    }
+                    Some(url.clone())
+                }
+                Some(ParentRef::Existing(parent_id)) => {
+                    let url = match parent_urls.entry(parent_id) {
+                        Entry::Occupied(entry) => entry.get().clone(),
+                        Entry::Vacant(entry) => {
+                            let parent = client
+                                .get_work_item(parent_id)
+                                .with_context(|| format!("Parent #{} not found", parent_id))?;
+                            entry.insert(parent.url).clone()
+                        }
+                    };
+                    println!("  Linking to parent #{}", parent_id);
+                    Some(url)
+                }
+                None => None,
+            };
+
+            let new_item = client.create_work_item_with_parent(fields, parent_url.as_deref())?;
+            println!("{} Created #{}", ui::Symbol::Ok, new_item.id);
+            imported.insert(idx, (new_item.id, new_item.url));
         }
     }
 
     Ok(())
 }
+
+/// Print what an import would do: changed fields (old -> new) for existing
+/// items, fetched from DevOps, and the fields a new item would be created with
+fn preview_import(
+    config: &Config,
+    items: &[ParsedWorkItem],
+    parents: &[Option<ParentRef>],
+    order: &[usize],
+) -> Result<()> {
+    // Only updates need the current state, so new-only files work without a PAT
+    let client = if items.iter().any(|item| item.id.unwrap_or(0) != 0) {
+        let pat = config.get_devops_pat()?;
+        let mut client =
+            DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+        if let Some(url) = &config.devops.api_url {
+            client = client.with_base_url(url);
+        }
+        Some(client)
+    } else {
+        None
+    };
+
+    println!("[DRY-RUN] Would import {} items:", order.len());
+    for &idx in order {
+        let item = &items[idx];
+        match (item.id.filter(|id| *id != 0), &client) {
+            (Some(id), Some(client)) => {
+                let current = match client.get_work_item(id) {
+                    Ok(current) => current,
+                    Err(e) => {
+                        println!(
+                            "  - {} #{}: {} (could not fetch: {})",
+                            item.work_item_type, id, item.title, e
+                        );
+                        continue;
+                    }
+                };
+                let changes = field_changes(item, &current);
+                if changes.is_empty() {
+                    println!(
+                        "  - {} #{}: {} (no changes)",
+                        item.work_item_type, id, item.title
+                    );
+                    continue;
+                }
+                println!("  - {} #{}: {}", item.work_item_type, id, item.title);
+                for change in changes {
+                    println!(
+                        "      {}: {} -> {}",
+                        change.field,
+                        change.old.as_deref().unwrap_or("(empty)"),
+                        change.new
+                    );
+                }
+            }
+            _ => {
+                println!("  - {} (new): {}", item.work_item_type, item.title);
+                let mut fields: Vec<_> = item.fields.iter().collect();
+                fields.sort();
+                for (key, value) in fields {
+                    println!("      {} = {}", key, preview_text(value));
+                }
+                if !item.description.is_empty() {
+                    println!(
+                        "      System.Description = {}",
+                        preview_text(&item.description)
+                    );
+                }
+                match parents[idx] {
+                    Some(ParentRef::Existing(parent_id)) => {
+                        println!("      Parent: #{}", parent_id)
+                    }
+                    Some(ParentRef::InFile(parent_idx)) => {
+                        println!("      Parent: '{}' (in file)", items[parent_idx].title)
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A field an import would change on an existing item
+#[derive(Debug, PartialEq)]
+struct FieldChange {
+    field: String,
+    old: Option<String>,
+    new: String,
+}
+
+/// Fields the import would write to `current` whose value actually differs, by name
+fn field_changes(item: &ParsedWorkItem, current: &WorkItem) -> Vec<FieldChange> {
+    let mut incoming: Vec<(&str, &str)> = item
+        .fields
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    if !item.description.is_empty() {
+        incoming.push(("System.Description", item.description.as_str()));
+    }
+    incoming.sort();
+
+    incoming
+        .into_iter()
+        .filter_map(|(field, new)| {
+            let old = current.fields.get(field);
+            if old.is_some_and(|old| same_field_value(field, old, new)) {
+                return None;
+            }
+            Some(FieldChange {
+                field: field.to_string(),
+                old: old.map(field_text).map(|t| preview_text(&t)),
+                new: preview_text(new),
+            })
+        })
+        .collect()
+}
+
+/// Compare a DevOps field value with its markdown text form
+fn same_field_value(field: &str, old: &serde_json::Value, new: &str) -> bool {
+    let new = new.trim();
+    match old {
+        serde_json::Value::Number(n) => new.parse::<f64>().ok() == n.as_f64(),
+        // Identities match by display name or account
+        serde_json::Value::Object(identity) => ["displayName", "uniqueName"]
+            .iter()
+            .filter_map(|key| identity.get(*key).and_then(|v| v.as_str()))
+            .any(|name| name.eq_ignore_ascii_case(new)),
+        // Export writes descriptions as text, so compare without markup or line breaks
+        serde_json::Value::String(s) if field == "System.Description" => {
+            preview_words(&strip_html_tags(s)) == preview_words(new)
+        }
+        _ => field_text(old).trim() == new,
+    }
+}
+
+fn field_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(identity) => identity
+            .get("displayName")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Single-line, shortened value for previews
+fn preview_text(value: &str) -> String {
+    truncate_chars(&preview_words(value), 60)
+}
+
+fn preview_words(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Where an imported item's parent lives
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParentRef {
+    /// A work item that already exists in DevOps
+    Existing(u32),
+    /// Another item in the same file, by index
+    InFile(usize),
+}
+
+/// Resolve each item's `**Parent:**` entry (or the `--parent` default).
+///
+/// `#0` means the enclosing item, i.e. the nearest earlier item with a
+/// shallower header. With `create_missing_parents` it may also be the nearest
+/// shallower item further down the file. A real id that also appears in the
+/// file points at that item, so it is imported first.
+fn resolve_parents(
+    items: &[ParsedWorkItem],
+    default_parent: Option<u32>,
+    create_missing_parents: bool,
+) -> Result<Vec<Option<ParentRef>>> {
+    let file_ids: HashMap<u32, usize> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| item.id.filter(|id| *id != 0).map(|id| (id, idx)))
+        .collect();
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let is_new = item.id.unwrap_or(0) == 0;
+            // Updates keep their existing links; only explicit entries order them
+            let parent_id = if is_new {
+                item.parent_id.or(default_parent)
+            } else {
+                item.parent_id
+            };
+
+            match parent_id {
+                Some(0) => {
+                    let enclosing = items[..idx]
+                        .iter()
+                        .rposition(|other| other.level < item.level);
+                    let later = || {
+                        items[idx + 1..]
+                            .iter()
+                            .position(|other| other.level < item.level)
+                            .map(|offset| idx + 1 + offset)
+                    };
+                    match enclosing.or_else(|| create_missing_parents.then(later).flatten()) {
+                        Some(parent_idx) => Ok(Some(ParentRef::InFile(parent_idx))),
+                        None if !is_new => Ok(None),
+                        None if create_missing_parents => anyhow::bail!(
+                            "'{}' has parent #0 but no higher-level item in the file",
+                            item.title
+                        ),
+                        None => anyhow::bail!(
+                            "'{}' has parent #0 but no item encloses it \
+                             (use --create-missing-parents to link a parent listed later)",
+                            item.title
+                        ),
+                    }
+                }
+                Some(id) => Ok(Some(match file_ids.get(&id) {
+                    Some(&parent_idx) => ParentRef::InFile(parent_idx),
+                    None => ParentRef::Existing(id),
+                })),
+                None => Ok(None),
+            }
+        })
+        .collect()
+}
+
+/// Order items so every in-file parent comes before its children, keeping
+/// file order where it already satisfies that. Fails on cyclic references.
+fn creation_order(items: &[ParsedWorkItem], parents: &[Option<ParentRef>]) -> Result<Vec<usize>> {
+    let mut placed = vec![false; items.len()];
+    let mut order = Vec::with_capacity(items.len());
+
+    for start in 0..items.len() {
+        // Walk up to the first ancestor that is already placed (or a root)
+        let mut chain: Vec<usize> = Vec::new();
+        let mut current = Some(start);
+        while let Some(idx) = current.filter(|idx| !placed[*idx]) {
+            if let Some(pos) = chain.iter().position(|c| *c == idx) {
+                let cycle = chain[pos..]
+                    .iter()
+                    .chain(std::iter::once(&idx))
+                    .map(|c| format!("'{}' (#{})", items[*c].title, items[*c].id.unwrap_or(0)))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                anyhow::bail!("Parent references form a cycle: {}", cycle);
+            }
+            chain.push(idx);
+            current = match parents[idx] {
+                Some(ParentRef::InFile(parent_idx)) => Some(parent_idx),
+                _ => None,
+            };
+        }
+
+        for idx in chain.into_iter().rev() {
+            placed[idx] = true;
+            order.push(idx);
+        }
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(id: u32, level: usize, title: &str, parent_id: Option<u32>) -> ParsedWorkItem {
+        ParsedWorkItem {
+            id: Some(id),
+            level,
+            work_item_type: "Feature".to_string(),
+            title: title.to_string(),
+            fields: HashMap::new(),
+            parent_id,
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_placeholder_parent_listed_later_requires_flag() {
+        let items = vec![
+            parsed(0, 3, "Story", Some(0)),
+            parsed(0, 2, "Feature", Some(0)),
+            parsed(0, 1, "Epic", None),
+        ];
+
+        assert!(resolve_parents(&items, None, false).is_err());
+
+        let parents = resolve_parents(&items, None, true).unwrap();
+        assert_eq!(
+            parents,
+            vec![Some(ParentRef::InFile(1)), Some(ParentRef::InFile(2)), None]
+        );
+        assert_eq!(creation_order(&items, &parents).unwrap(), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_creation_order_keeps_file_order_when_valid() {
+        let items = vec![
+            parsed(0, 1, "Epic", None),
+            parsed(0, 2, "Feature", Some(0)),
+            parsed(0, 3, "Story", Some(0)),
+            parsed(0, 2, "Other Feature", Some(77)),
+        ];
+
+        let parents = resolve_parents(&items, None, false).unwrap();
+        assert_eq!(parents[3], Some(ParentRef::Existing(77)));
+        assert_eq!(creation_order(&items, &parents).unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_field_changes_lists_only_changed_fields() {
+        let mut item = parsed(42, 4, "Fix login", None);
+        item.fields
+            .insert("System.State".to_string(), "Closed".to_string());
+        item.fields.insert(
+            "Microsoft.VSTS.Scheduling.Effort".to_string(),
+            "3".to_string(),
+        );
+        item.fields
+            .insert("System.AssignedTo".to_string(), "Alice".to_string());
+        item.description = "Users can\nlog in".to_string();
+
+        let current: WorkItem = serde_json::from_value(serde_json::json!({
+            "id": 42,
+            "rev": 7,
+            "fields": {
+                "System.State": "Active",
+                "Microsoft.VSTS.Scheduling.Effort": 3.0,
+                "System.AssignedTo": { "displayName": "Alice", "uniqueName": "alice@example.com" },
+                "System.Description": "<div>Users can log in</div>"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            field_changes(&item, &current),
+            vec![FieldChange {
+                field: "System.State".to_string(),
+                old: Some("Active".to_string()),
+                new: "Closed".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since("2026-01-05").unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()
+        );
+        assert!(parse_since("05/01/2026").is_err());
+        assert!(parse_since("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn test_creation_order_rejects_cycles() {
+        let items = vec![parsed(5, 2, "A", Some(6)), parsed(6, 2, "B", Some(5))];
+
+        let parents = resolve_parents(&items, None, false).unwrap();
+        let err = creation_order(&items, &parents).unwrap_err().to_string();
+        assert!(err.contains("cycle"), "{}", err);
+        assert!(err.contains("'A' (#5) -> 'B' (#6) -> 'A' (#5)"), "{}", err);
+    }
+}
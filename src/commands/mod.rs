@@ -6,3 +6,4 @@ pub mod devops;
 pub mod markdown;
 pub mod pace;
 pub mod task;
+pub mod whoami;
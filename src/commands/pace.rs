@@ -1,41 +1,146 @@
+use crate::OutputFormat;
 use crate::config::Config;
+use crate::config::WorkHoursConfig;
+use crate::devops::client::DevOpsClient;
 use crate::pace::client::PaceClient;
 use crate::pace::duration::format_duration;
+use crate::pace::models::Worklog;
+use crate::ui;
+use crate::utils::csv;
+use crate::utils::text::truncate_chars;
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// FR2.5: Manually log time to a work item
 pub fn log_time(
     config: &Config,
     work_item_id: u32,
-    hours: f32,
+    duration_secs: u32,
     comment: Option<String>,
+    timestamp: Option<&str>,
     dry_run: bool,
 ) -> Result<()> {
+    let timestamp = timestamp
+        .map(|value| parse_worklog_timestamp(value, Utc::now()))
+        .transpose()?;
+
     let pat = config
         .devops
         .pat
         .as_deref()
         .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
-    let pace_client = PaceClient::new(pat, &config.devops.organization);
-
-    let duration_secs = (hours * 3600.0) as u32;
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, pat);
 
     if dry_run {
         let formatted = format_duration(duration_secs);
         println!(
             "[DRY-RUN] Would log {:.2}h ({}) to Task {}",
-            hours, formatted, work_item_id
+            f64::from(duration_secs) / 3600.0,
+            formatted,
+            work_item_id
         );
         if let Some(ref c) = comment {
             println!("[DRY-RUN] Comment: {}", c);
         }
+        if let Some(ts) = timestamp {
+            println!("[DRY-RUN] Timestamp: {}", ts.to_rfc3339());
+        }
     } else {
-        let worklog = pace_client.create_worklog(work_item_id, duration_secs, comment)?;
+        let worklog =
+            pace_client.create_worklog(work_item_id, duration_secs, comment, timestamp)?;
         let formatted = format_duration(worklog.duration);
         println!(
-            "✓ Logged {} to Task {} (Worklog ID: {})",
-            formatted, work_item_id, worklog.id
+            "{} Logged {} to Task {} (Worklog ID: {})",
+            ui::Symbol::Ok,
+            formatted,
+            work_item_id,
+            worklog.id
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a `--timestamp` value for backdating a worklog. Accepts RFC 3339
+/// (`2026-01-05T09:00:00Z`) or a bare date, taken as midnight UTC.
+fn parse_worklog_timestamp(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let timestamp = match DateTime::parse_from_rfc3339(value) {
+        Ok(ts) => ts.with_timezone(&Utc),
+        Err(_) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|d| d.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc())
+            .with_context(|| {
+                format!(
+                    "Invalid --timestamp '{}'. Expected ISO 8601, e.g. 2026-01-05T09:00:00Z",
+                    value
+                )
+            })?,
+    };
+
+    if timestamp > now {
+        anyhow::bail!("--timestamp {} is in the future", timestamp.to_rfc3339());
+    }
+    Ok(timestamp)
+}
+
+/// Stop whatever 7Pace timer is running, even one started outside this tool.
+/// Local task state is left alone.
+pub fn stop_timer(config: &Config, reason: u8, format: OutputFormat) -> Result<()> {
+    let pat = config.get_devops_pat()?;
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
+
+    let Some(timer) = pace_client.get_current_timer()? else {
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::json!({ "status": "no_active_timer" }));
+        } else {
+            println!("No active 7Pace timer.");
+        }
+        return Ok(());
+    };
+
+    let stopped = pace_client
+        .stop_timer(reason)
+        .with_context(|| format!("Failed to stop 7Pace timer for Task {}", timer.work_item_id))?;
+
+    let Some(stopped) = stopped else {
+        if let OutputFormat::Json = format {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "work_item_id": timer.work_item_id,
+                    "worklog_id": null,
+                    "duration_seconds": null,
+                    "status": "stopped"
+                })
+            );
+        } else {
+            println!(
+                "{} Stopped 7Pace timer for Task {} (worklog details unavailable)",
+                ui::Symbol::Ok,
+                timer.work_item_id
+            );
+        }
+        return Ok(());
+    };
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "work_item_id": stopped.work_item_id,
+                "worklog_id": stopped.worklog_id,
+                "duration_seconds": stopped.duration,
+                "status": "stopped"
+            })
+        );
+    } else {
+        println!(
+            "{} Stopped 7Pace timer for Task {}: logged {} (Worklog ID: {})",
+            ui::Symbol::Ok,
+            stopped.work_item_id,
+            format_duration(stopped.duration),
+            stopped.worklog_id
         );
     }
 
@@ -43,51 +148,506 @@ pub fn log_time(
 }
 
 /// FR2.6: Fetch and display worklogs for reconciliation
-pub fn worklogs(config: &Config, days: u32) -> Result<()> {
+pub fn worklogs(
+    config: &Config,
+    days: u32,
+    from: Option<&str>,
+    to: Option<&str>,
+    group_by_item: bool,
+    reconcile: bool,
+    format: OutputFormat,
+) -> Result<()> {
     let pat = config
         .devops
         .pat
         .as_deref()
         .context("DevOps PAT not set. Run 'task config set devops.pat <PAT>'")?;
-    let pace_client = PaceClient::new(pat, &config.devops.organization);
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, pat);
 
-    let end = Utc::now();
-    let start = end - chrono::Duration::days(days as i64);
+    let (start, end) = worklog_window(days, from, to, Utc::now())?;
+    let window = match (from, to) {
+        (None, None) => format!("in the last {} days", days),
+        _ => format!(
+            "from {} to {}",
+            start.format("%Y-%m-%d"),
+            to.unwrap_or("now")
+        ),
+    };
 
     let logs = pace_client.get_worklogs(start, end)?;
 
+    if reconcile {
+        let days = reconcile_days(&logs, start, end, &config.work_hours)?;
+        return print_reconciliation(&days, &window, format);
+    }
+
+    if let OutputFormat::Csv = format {
+        println!("work_item_id,comment,duration_seconds,timestamp");
+        for log in &logs {
+            println!(
+                "{}",
+                csv::to_row(&[
+                    log.work_item_id.to_string(),
+                    log.comment.clone().unwrap_or_default(),
+                    log.duration.to_string(),
+                    log.timestamp.to_rfc3339(),
+                ])
+            );
+        }
+        return Ok(());
+    }
+
+    if let OutputFormat::Json | OutputFormat::Yaml = format {
+        let report = WorklogReport::new(&logs);
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print!("{}", serde_yaml::to_string(&report)?);
+        }
+        return Ok(());
+    }
+
     if logs.is_empty() {
-        println!("No worklogs found in the last {} days.", days);
+        println!("No worklogs found {}.", window);
         return Ok(());
     }
 
-    println!("Worklogs (last {} days):", days);
+    if group_by_item {
+        println!("Worklogs ({}), grouped by work item:", window);
+        print_grouped_worklogs(config, pat, &logs);
+    } else {
+        println!("Worklogs ({}):", window);
+        print_worklog_header();
+        for log in &logs {
+            print_worklog_row(log);
+        }
+    }
+
+    // Summary
+    let total_secs: u32 = logs.iter().map(|l| l.duration).sum();
+    let total_str = format_duration(total_secs);
+    println!("\nTotal: {} ({} entries)", total_str, logs.len());
+
+    Ok(())
+}
+
+/// One worklog in `worklogs --format json`
+#[derive(Debug, Serialize)]
+pub struct WorklogEntry {
+    pub work_item_id: u32,
+    pub duration_seconds: u32,
+    pub duration_human: String,
+    pub timestamp: DateTime<Utc>,
+    pub comment: Option<String>,
+}
+
+/// Totals across every entry in the report
+#[derive(Debug, Serialize)]
+pub struct WorklogSummary {
+    pub total_seconds: u32,
+    pub total_human: String,
+    pub entries: usize,
+}
+
+/// Machine-readable worklog listing, for timesheet importers
+#[derive(Debug, Serialize)]
+pub struct WorklogReport {
+    pub worklogs: Vec<WorklogEntry>,
+    pub summary: WorklogSummary,
+}
+
+impl WorklogReport {
+    pub fn new(logs: &[Worklog]) -> Self {
+        let total_seconds: u32 = logs.iter().map(|l| l.duration).sum();
+        Self {
+            worklogs: logs
+                .iter()
+                .map(|log| WorklogEntry {
+                    work_item_id: log.work_item_id,
+                    duration_seconds: log.duration,
+                    duration_human: format_duration(log.duration),
+                    timestamp: log.timestamp,
+                    comment: log.comment.clone(),
+                })
+                .collect(),
+            summary: WorklogSummary {
+                total_seconds,
+                total_human: format_duration(total_seconds),
+                entries: logs.len(),
+            },
+        }
+    }
+}
+
+fn print_worklog_header() {
     println!(
         "{:<8} {:<50} {:<12} {:<20}",
         "Task ID", "Comment", "Duration", "Date"
     );
     println!("{}", "-".repeat(92));
+}
 
-    for log in &logs {
-        let duration_str = format_duration(log.duration);
-        let comment_str = log.comment.as_deref().unwrap_or("(no comment)");
-        let comment_display = if comment_str.len() > 48 {
-            format!("{}...", &comment_str[0..45])
-        } else {
-            comment_str.to_string()
-        };
-        let date_str = log.timestamp.format("%Y-%m-%d %H:%M");
+fn print_worklog_row(log: &Worklog) {
+    let duration_str = format_duration(log.duration);
+    let comment_str = log.comment.as_deref().unwrap_or("(no comment)");
+    let comment_display = truncate_chars(comment_str, 48);
+    let date_str = log.timestamp.format("%Y-%m-%d %H:%M");
 
+    println!(
+        "{:<8} {:<50} {:<12} {:<20}",
+        log.work_item_id, comment_display, duration_str, date_str
+    );
+}
+
+/// Print worklogs per work item with a subtotal for each
+fn print_grouped_worklogs(config: &Config, pat: &str, logs: &[Worklog]) {
+    let mut client = DevOpsClient::new(pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    // One title lookup per work item; deleted items just show their id
+    for (id, group) in group_by_work_item(logs) {
+        let title = client
+            .get_work_item(id)
+            .ok()
+            .and_then(|wi| wi.get_title().map(str::to_string));
+        match title {
+            Some(title) => println!("\n#{} {}", id, title),
+            None => println!("\n#{}", id),
+        }
+
+        print_worklog_header();
+        for log in &group {
+            print_worklog_row(log);
+        }
+        let subtotal: u32 = group.iter().map(|l| l.duration).sum();
         println!(
-            "{:<8} {:<50} {:<12} {:<20}",
-            log.work_item_id, comment_display, duration_str, date_str
+            "Subtotal: {} ({} entries)",
+            format_duration(subtotal),
+            group.len()
         );
     }
+}
 
-    // Summary
-    let total_secs: u32 = logs.iter().map(|l| l.duration).sum();
-    let total_str = format_duration(total_secs);
-    println!("\nTotal: {} ({} entries)", total_str, logs.len());
+/// Group worklogs by work item id, ordered by id
+fn group_by_work_item(logs: &[Worklog]) -> BTreeMap<u32, Vec<&Worklog>> {
+    let mut groups: BTreeMap<u32, Vec<&Worklog>> = BTreeMap::new();
+    for log in logs {
+        groups.entry(log.work_item_id).or_default().push(log);
+    }
+    groups
+}
+
+/// Logged vs expected time for one day of the reconciliation report
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DayReconciliation {
+    pub date: NaiveDate,
+    pub logged_hours: f64,
+    pub expected_hours: f64,
+    pub delta_hours: f64,
+}
+
+/// Compare logged time per day against configured work hours.
+/// Days are bucketed in the work hours timezone; non-working days expect zero.
+fn reconcile_days(
+    logs: &[Worklog],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    work_hours: &WorkHoursConfig,
+) -> Result<Vec<DayReconciliation>> {
+    if work_hours.start.is_empty() || work_hours.end.is_empty() {
+        anyhow::bail!("Work hours not configured. Set work_hours.start and work_hours.end");
+    }
+    let parse = |s: &str| {
+        NaiveTime::parse_from_str(s, "%H:%M")
+            .with_context(|| format!("Invalid work hours time '{}'", s))
+    };
+    let daily_secs = (parse(&work_hours.end)? - parse(&work_hours.start)?).num_seconds();
+    let tz = work_hours.tz()?;
+
+    let mut logged: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for log in logs {
+        let date = log.timestamp.with_timezone(&tz).date_naive();
+        *logged.entry(date).or_default() += log.duration as u64;
+    }
+
+    let round = |hours: f64| (hours * 100.0).round() / 100.0;
+    let first = start.with_timezone(&tz).date_naive();
+    let last = (end - chrono::Duration::seconds(1))
+        .with_timezone(&tz)
+        .date_naive();
+
+    Ok(first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .map(|date| {
+            let logged_hours = logged.get(&date).copied().unwrap_or(0) as f64 / 3600.0;
+            let expected_hours = if work_hours.is_working_day(date) {
+                daily_secs as f64 / 3600.0
+            } else {
+                0.0
+            };
+            DayReconciliation {
+                date,
+                logged_hours: round(logged_hours),
+                expected_hours: round(expected_hours),
+                delta_hours: round(logged_hours - expected_hours),
+            }
+        })
+        .collect())
+}
 
+fn print_reconciliation(
+    days: &[DayReconciliation],
+    window: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(days)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(days)?),
+        OutputFormat::Csv => {
+            println!("date,logged_hours,expected_hours,delta_hours");
+            for day in days {
+                println!(
+                    "{},{},{},{}",
+                    day.date, day.logged_hours, day.expected_hours, day.delta_hours
+                );
+            }
+        }
+        OutputFormat::Text => {
+            println!("Reconciliation ({}):", window);
+            println!(
+                "{:<12} {:>10} {:>10} {:>10}",
+                "Date", "Logged", "Expected", "Delta"
+            );
+            println!("{}", "-".repeat(45));
+            for day in days {
+                println!(
+                    "{:<12} {:>9.2}h {:>9.2}h {:>+9.2}h",
+                    day.date, day.logged_hours, day.expected_hours, day.delta_hours
+                );
+            }
+            let logged: f64 = days.iter().map(|d| d.logged_hours).sum();
+            let expected: f64 = days.iter().map(|d| d.expected_hours).sum();
+            println!(
+                "\nTotal: {:.2}h logged of {:.2}h expected ({:+.2}h)",
+                logged,
+                expected,
+                logged - expected
+            );
+        }
+    }
     Ok(())
 }
+
+/// Resolve the worklog query window. `--from`/`--to` are inclusive ISO 8601 dates
+/// and override the `days` window ending now; a missing bound falls back to it.
+fn worklog_window(
+    days: u32,
+    from: Option<&str>,
+    to: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let parse = |flag: &str, value: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .with_context(|| format!("Invalid --{} date '{}'. Expected YYYY-MM-DD", flag, value))
+    };
+
+    let end = match to {
+        Some(to) => (parse("to", to)? + chrono::Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc(),
+        None => now,
+    };
+    let start = match from {
+        Some(from) => parse("from", from)?
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc(),
+        None => end - chrono::Duration::days(days as i64),
+    };
+
+    if start >= end {
+        anyhow::bail!(
+            "--from ({}) must not be after --to ({})",
+            from.unwrap_or("default"),
+            to.unwrap_or("today")
+        );
+    }
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_worklog_window_defaults_to_days() {
+        let (start, end) = worklog_window(7, None, None, now()).unwrap();
+        assert_eq!(end, now());
+        assert_eq!(start, now() - chrono::Duration::days(7));
+    }
+
+    #[test]
+    fn test_worklog_window_from_to_is_inclusive() {
+        let (start, end) =
+            worklog_window(7, Some("2026-01-01"), Some("2026-01-31"), now()).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_worklog_window_single_day() {
+        let (start, end) =
+            worklog_window(7, Some("2026-01-05"), Some("2026-01-05"), now()).unwrap();
+        assert_eq!(end - start, chrono::Duration::days(1));
+    }
+
+    #[test]
+    fn test_worklog_window_from_only_ends_now() {
+        let (start, end) = worklog_window(7, Some("2026-02-01"), None, now()).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, now());
+    }
+
+    #[test]
+    fn test_worklog_window_rejects_reversed_range() {
+        let err = worklog_window(7, Some("2026-02-01"), Some("2026-01-01"), now()).unwrap_err();
+        assert!(err.to_string().contains("must not be after"));
+    }
+
+    #[test]
+    fn test_worklog_window_rejects_invalid_date() {
+        let err = worklog_window(7, Some("01/02/2026"), None, now()).unwrap_err();
+        assert!(err.to_string().contains("Invalid --from date"));
+    }
+
+    fn worklog(id: u32, work_item_id: u32, duration: u32) -> Worklog {
+        Worklog {
+            id,
+            work_item_id,
+            user_id: "user".to_string(),
+            duration,
+            timestamp: now(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_work_item_orders_and_subtotals() {
+        let logs = vec![
+            worklog(1, 300, 600),
+            worklog(2, 100, 1800),
+            worklog(3, 300, 900),
+        ];
+
+        let groups = group_by_work_item(&logs);
+
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![100, 300]);
+        let subtotal: u32 = groups[&300].iter().map(|l| l.duration).sum();
+        assert_eq!(subtotal, 1500);
+        assert_eq!(groups[&100].len(), 1);
+    }
+
+    fn worklog_at(id: u32, duration: u32, timestamp: DateTime<Utc>) -> Worklog {
+        Worklog {
+            timestamp,
+            ..worklog(id, 100, duration)
+        }
+    }
+
+    #[test]
+    fn test_reconcile_days_computes_deltas() {
+        let work_hours = WorkHoursConfig {
+            start: "09:00".to_string(),
+            end: "17:00".to_string(),
+            timezone: "UTC".to_string(),
+            ..Default::default()
+        };
+        let logs = vec![
+            worklog_at(
+                1,
+                4 * 3600,
+                Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap(),
+            ),
+            worklog_at(
+                2,
+                5 * 3600,
+                Utc.with_ymd_and_hms(2026, 1, 5, 15, 0, 0).unwrap(),
+            ),
+            worklog_at(
+                3,
+                6 * 3600 + 1800,
+                Utc.with_ymd_and_hms(2026, 1, 6, 9, 0, 0).unwrap(),
+            ),
+        ];
+        let (start, end) =
+            worklog_window(0, Some("2026-01-05"), Some("2026-01-06"), now()).unwrap();
+
+        let days = reconcile_days(&logs, start, end, &work_hours).unwrap();
+
+        assert_eq!(
+            days,
+            vec![
+                DayReconciliation {
+                    date: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+                    logged_hours: 9.0,
+                    expected_hours: 8.0,
+                    delta_hours: 1.0,
+                },
+                DayReconciliation {
+                    date: NaiveDate::from_ymd_opt(2026, 1, 6).unwrap(),
+                    logged_hours: 6.5,
+                    expected_hours: 8.0,
+                    delta_hours: -1.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_days_expects_nothing_on_weekends() {
+        let work_hours = WorkHoursConfig {
+            start: "09:00".to_string(),
+            end: "17:00".to_string(),
+            skip_weekends: true,
+            ..Default::default()
+        };
+        // 2026-01-10 is a Saturday
+        let (start, end) =
+            worklog_window(0, Some("2026-01-10"), Some("2026-01-10"), now()).unwrap();
+
+        let days = reconcile_days(&[], start, end, &work_hours).unwrap();
+
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].expected_hours, 0.0);
+        assert_eq!(days[0].delta_hours, 0.0);
+    }
+
+    #[test]
+    fn test_parse_worklog_timestamp_accepts_rfc3339_and_date() {
+        assert_eq!(
+            parse_worklog_timestamp("2026-02-14T09:30:00+01:00", now()).unwrap(),
+            Utc.with_ymd_and_hms(2026, 2, 14, 8, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_worklog_timestamp("2026-02-14", now()).unwrap(),
+            Utc.with_ymd_and_hms(2026, 2, 14, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_worklog_timestamp_rejects_future_and_garbage() {
+        let err = parse_worklog_timestamp("2026-02-16T09:00:00Z", now()).unwrap_err();
+        assert!(err.to_string().contains("in the future"));
+        assert!(parse_worklog_timestamp("yesterday", now()).is_err());
+    }
+}
@@ -1,9 +1,13 @@
 use crate::OutputFormat;
 use crate::config::Config;
 use crate::devops::client::DevOpsClient;
-use crate::state::{CurrentTask, State, with_state_lock};
+use crate::pace::client::PaceClient;
+use crate::pace::duration::format_duration;
+use crate::state::{CurrentTask, State, with_state_lock_timeout};
+use crate::ui;
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::io::{self, IsTerminal};
 use std::path::PathBuf;
 
 pub fn state_paths(config: &Config) -> Result<(PathBuf, PathBuf)> {
@@ -16,13 +20,68 @@ pub fn state_paths(config: &Config) -> Result<(PathBuf, PathBuf)> {
     Ok((state_dir.join("state.lock"), state_dir.join("state.json")))
 }
 
-pub fn start(
+/// Options for `start`
+#[derive(Debug, Clone, Default)]
+pub struct StartOptions {
+    /// Comment recorded on the 7Pace timer
+    pub comment: Option<String>,
+    pub dry_run: bool,
+    /// Book a Focus Block in the calendar
+    pub schedule_focus: bool,
+    /// Focus Block length in minutes instead of `focus_blocks.duration_minutes`
+    pub focus_duration: Option<u32>,
+    /// Stop a stale running timer without asking
+    pub yes: bool,
+}
+
+/// The Focus Block `start --schedule-focus` books: the next free slot after
+/// `now`, lasting `duration_mins` or the configured block length
+pub fn focus_block_event(
     config: &Config,
     id: u32,
-    dry_run: bool,
-    schedule_focus: bool,
-    format: OutputFormat,
-) -> Result<()> {
+    title: &str,
+    events: &[crate::graph::models::CalendarEvent],
+    now: chrono::DateTime<Utc>,
+    duration_mins: Option<u32>,
+) -> Result<crate::graph::models::CalendarEvent> {
+    // Find next slot using smart scheduler
+    let duration = duration_mins.unwrap_or(config.focus_blocks.duration_minutes);
+    let (slot_start, slot_end) = crate::graph::scheduler::find_next_slot_capped(
+        events,
+        now,
+        duration,
+        config.focus_blocks.buffer_minutes,
+        &config.work_hours,
+        crate::graph::scheduler::DailyCap::from_config(&config.focus_blocks),
+    )?;
+
+    Ok(crate::graph::models::CalendarEvent {
+        id: None,
+        subject: format!("🎯 Focus: {} - {}", id, title),
+        start: crate::graph::models::DateTimeTimeZone::from_utc(slot_start, "UTC"),
+        end: crate::graph::models::DateTimeTimeZone::from_utc(slot_end, "UTC"),
+        body: None,
+        categories: config.focus_blocks.categories(),
+        extended_properties: Some(vec![crate::graph::models::ExtendedProperty::work_item_id(
+            id,
+        )]),
+        is_all_day: false,
+        recurrence: None,
+        show_as: Some(config.focus_blocks.show_as.as_graph_str().to_string()),
+    })
+}
+
+pub fn start(config: &Config, id: u32, options: &StartOptions, format: OutputFormat) -> Result<()> {
+    let StartOptions {
+        comment,
+        dry_run,
+        schedule_focus,
+        focus_duration,
+        yes,
+    } = options.clone();
+    if focus_duration == Some(0) {
+        anyhow::bail!("--focus-duration must be at least 1 minute");
+    }
     let (lock_path, state_path) = state_paths(config)?;
 
     // 1. Fetch work item from DevOps to validate
@@ -33,10 +92,7 @@ pub fn start(
         devops_client = devops_client.with_base_url(url);
     }
 
-    let mut pace_client = crate::pace::client::PaceClient::new(&pat, &config.devops.organization);
-    if let Some(url) = &config.devops.pace_api_url {
-        pace_client = pace_client.with_base_url(url);
-    }
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
 
     if let OutputFormat::Text = format {
         println!("Fetching work item {}...", id);
@@ -48,6 +104,41 @@ pub fn start(
     if let Some(current_timer) = pace_client.get_current_timer()?
         && current_timer.work_item_id != id
     {
+        // A timer older than a task's expiry was most likely forgotten, and
+        // stopping it logs all of that time
+        let elapsed = Utc::now() - current_timer.started_at;
+        if elapsed > chrono::Duration::hours(config.state.task_expiry_hours.into()) {
+            let age = format_duration(elapsed.num_seconds().clamp(0, u32::MAX as i64) as u32);
+            let warning = format!(
+                "{} The running timer for Task {} started {} ago (at {})",
+                ui::Symbol::Warn,
+                current_timer.work_item_id,
+                age,
+                current_timer.started_at.format("%Y-%m-%d %H:%M UTC")
+            );
+            if let OutputFormat::Json = format {
+                eprintln!("{}", warning);
+            } else {
+                println!("{}", warning);
+            }
+
+            if !dry_run && !yes {
+                // Without a terminal to answer the prompt, fail rather than
+                // exit 0 having started nothing
+                if matches!(format, OutputFormat::Json) || !io::stdin().is_terminal() {
+                    anyhow::bail!(
+                        "Not stopping the {}-old timer for Task {} without confirmation; pass --yes to stop it",
+                        age,
+                        current_timer.work_item_id
+                    );
+                }
+                if !ui::confirm("Stop it (logging that time) and start the new task?")? {
+                    println!("Cancelled.");
+                    return Ok(());
+                }
+            }
+        }
+
         if dry_run {
             println!(
                 "[DRY-RUN] Would stop existing timer for Task {}",
@@ -70,71 +161,55 @@ pub fn start(
         if !matches!(format, OutputFormat::Json) {
             println!("Starting timer for Task {} - {}...", id, title);
         }
-        let timer = pace_client.start_timer(id, None)?;
+        let timer = pace_client.start_timer(id, comment.clone())?;
         if !matches!(format, OutputFormat::Json) {
-            println!("✓ Timer started for Task {}", id);
+            println!("{} Timer started for Task {}", ui::Symbol::Ok, id);
         }
         Some(timer.id)
     };
 
     // 4. Schedule Focus Block if requested (FR3.7)
+    let mut focus_event_id = None;
     if schedule_focus {
         if dry_run {
             println!("[DRY-RUN] Would schedule Focus Block in calendar");
         } else {
-            println!("📅 Scheduling Focus Block...");
+            println!("{} Scheduling Focus Block...", ui::Symbol::Calendar);
 
             // Use async runtime for calendar operations
             let runtime = tokio::runtime::Runtime::new()?;
             let result = runtime.block_on(async {
-                let token_cache_path = home::home_dir()
-                    .context("Could not find home directory")?
-                    .join(".ao-no-out7ook")
-                    .join("tokens.json");
-
-                let auth = crate::graph::auth::GraphAuthenticator::new(
-                    config.graph.client_id.clone(),
-                    token_cache_path,
-                );
+                let auth = crate::graph::auth::GraphAuthenticator::from_config(config)?;
                 let client = crate::graph::client::GraphClient::new(auth);
 
                 // Get existing events for today
                 let now = chrono::Utc::now();
                 let end_of_day = now + chrono::Duration::hours(24);
-                let events = client.list_events(now, end_of_day).await?;
-
-                // Find next slot using smart scheduler
-                let duration = config.focus_blocks.duration_minutes;
-                let (slot_start, slot_end) = crate::graph::scheduler::find_next_slot(
-                    &events,
-                    now,
-                    duration,
-                    &config.work_hours,
-                )?;
-
-                // Create Focus Block event
-                let event = crate::graph::models::CalendarEvent {
-                    id: None,
-                    subject: format!("🎯 Focus: {} - {}", id, title),
-                    start: crate::graph::models::DateTimeTimeZone::from_utc(slot_start, "UTC"),
-                    end: crate::graph::models::DateTimeTimeZone::from_utc(slot_end, "UTC"),
-                    body: None,
-                    categories: vec!["Focus Block".to_string()],
-                    extended_properties: None, // TODO: Add work_item_id
-                };
+                // From a day back so blocks earlier today count towards max_per_day
+                let events = client
+                    .list_events(now - chrono::Duration::hours(24), end_of_day)
+                    .await?;
 
+                let event = focus_block_event(config, id, &title, &events, now, focus_duration)?;
                 client.create_event(event).await
             });
 
             match result {
                 Ok(created) => {
                     println!(
-                        "✓ Focus Block created: {} to {}",
-                        created.start.date_time, created.end.date_time
+                        "{} Focus Block created: {} to {}",
+                        ui::Symbol::Ok,
+                        created.start.date_time,
+                        created.end.date_time
                     );
+                    focus_event_id = created.id;
                 }
                 Err(e) => {
-                    println!("⚠ Warning: Could not create Focus Block: {}", e);
+                    println!(
+                        "{} Warning: Could not create Focus Block: {}",
+                        ui::Symbol::Warn,
+                        e
+                    );
                     println!("  Continuing with timer start...");
                 }
             }
@@ -142,128 +217,517 @@ pub fn start(
     }
 
     // 4. Update State
-    with_state_lock(&lock_path, &state_path, |state| {
-        // In dry-run, we just describe what we would do
-        if dry_run {
-            if let Some(current) = &state.current_task {
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            // In dry-run, we just describe what we would do
+            if dry_run {
+                if let Some(current) = &state.current_task {
+                    println!(
+                        "[DRY-RUN] Would stop previous task: {} - {}",
+                        current.id, current.title
+                    );
+                }
+                println!("[DRY-RUN] Would set current task to {} - {}", id, title);
+                return Ok(());
+            }
+
+            if let Some(current) = &state.current_task
+                && !matches!(format, OutputFormat::Json)
+            {
+                println!("Stopping previous task: {} - {}", current.id, current.title);
+            }
+
+            // FR3.3: Remember which event belongs to this work item
+            if let Some(event_id) = &focus_event_id {
+                state.upsert_calendar_mapping(id, event_id.clone());
+            }
+
+            let now = Utc::now();
+            state.finish_current_task(now);
+            state.current_task = Some(CurrentTask {
+                id,
+                title: title.clone(),
+                started_at: now,
+                expires_at: now + chrono::Duration::hours(config.state.task_expiry_hours.into()),
+                timer_id: timer_id.clone(),
+                paused_at: None,
+            });
+
+            if let OutputFormat::Json = format {
                 println!(
-                    "[DRY-RUN] Would stop previous task: {} - {}",
-                    current.id, current.title
+                    "{}",
+                    serde_json::json!({
+                        "id": id,
+                        "title": title,
+                        "started_at": now,
+                        "timer_id": timer_id,
+                        "comment": comment
+                    })
                 );
+            } else {
+                println!("{} Started task: {} - {}", ui::Symbol::Ok, id, title);
             }
-            println!("[DRY-RUN] Would set current task to {} - {}", id, title);
-            return Ok(());
+            Ok(())
+        },
+    )
+}
+
+pub fn stop(config: &Config, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let (lock_path, state_path) = state_paths(config)?;
+
+    // Snapshot the current task first so the 7Pace call can happen outside the lock closure
+    let current_task = with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| Ok(state.current_task.clone()),
+    )?;
+
+    let Some(current) = current_task else {
+        if let OutputFormat::Json = format {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "no_active_task"
+                })
+            );
+        } else {
+            println!("No active task to stop.");
         }
+        return Ok(());
+    };
 
-        if let Some(current) = &state.current_task {
-            if !matches!(format, OutputFormat::Json) {
-                println!("Stopping previous task: {} - {}", current.id, current.title);
-            }
+    if dry_run {
+        println!("[DRY-RUN] Would stop timer for Task {}", current.id);
+        return Ok(());
+    }
+
+    // Stop 7Pace timer if active. A failure here leaves local state untouched
+    // so the user can retry instead of ending up with a runaway timer.
+    if current.timer_id.is_some() {
+        if !matches!(format, OutputFormat::Json) {
+            println!("Stopping timer for Task {}...", current.id);
         }
 
-        let now = Utc::now();
-        state.current_task = Some(CurrentTask {
-            id,
-            title: title.clone(),
-            started_at: now,
-            expires_at: now + chrono::Duration::hours(config.state.task_expiry_hours.into()),
-            timer_id: timer_id.clone(),
-        });
+        let pat = config.get_devops_pat()?;
+        let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
+
+        pace_client
+            .stop_timer(0)
+            .with_context(|| format!("Failed to stop 7Pace timer for Task {}", current.id))?;
+    }
+
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            state.finish_current_task(Utc::now());
+            Ok(())
+        },
+    )?;
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": current.id,
+                "title": current.title,
+                "status": "stopped"
+            })
+        );
+    } else {
+        println!(
+            "{} Stopped task: {} - {}",
+            ui::Symbol::Ok,
+            current.id,
+            current.title
+        );
+    }
+
+    Ok(())
+}
 
+/// Stop the 7Pace timer but keep the task current so `resume` can pick it up again
+pub fn pause(config: &Config, dry_run: bool, format: OutputFormat) -> Result<()> {
+    let (lock_path, state_path) = state_paths(config)?;
+
+    let current_task = with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| Ok(state.current_task.clone()),
+    )?;
+
+    let Some(current) = current_task else {
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::json!({ "status": "no_active_task" }));
+        } else {
+            println!("No active task to pause.");
+        }
+        return Ok(());
+    };
+
+    if current.is_paused() {
         if let OutputFormat::Json = format {
             println!(
                 "{}",
                 serde_json::json!({
-                    "id": id,
-                    "title": title,
-                    "started_at": now,
-                    "timer_id": timer_id
+                    "id": current.id,
+                    "title": current.title,
+                    "status": "already_paused",
+                    "paused_at": current.paused_at
                 })
             );
         } else {
-            println!("✓ Started task: {} - {}", id, title);
+            println!("Task {} is already paused.", current.id);
         }
-        Ok(())
-    })
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("[DRY-RUN] Would pause timer for Task {}", current.id);
+        return Ok(());
+    }
+
+    // As with stop, a failed 7Pace call leaves the task running locally
+    if current.timer_id.is_some() {
+        let pat = config.get_devops_pat()?;
+        let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
+
+        pace_client
+            .stop_timer(0)
+            .with_context(|| format!("Failed to stop 7Pace timer for Task {}", current.id))?;
+    }
+
+    let paused_at = Utc::now();
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            if let Some(task) = state.current_task.as_mut() {
+                task.timer_id = None;
+                task.paused_at = Some(paused_at);
+            }
+            Ok(())
+        },
+    )?;
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": current.id,
+                "title": current.title,
+                "status": "paused",
+                "paused_at": paused_at
+            })
+        );
+    } else {
+        println!(
+            "{} Paused task: {} - {} (run 'ano7 resume' to continue)",
+            ui::Symbol::Ok,
+            current.id,
+            current.title
+        );
+    }
+
+    Ok(())
 }
 
-pub fn stop(config: &Config, dry_run: bool, format: OutputFormat) -> Result<()> {
+/// Start a fresh 7Pace timer for the paused current task
+pub fn resume(config: &Config, dry_run: bool, format: OutputFormat) -> Result<()> {
     let (lock_path, state_path) = state_paths(config)?;
 
-    with_state_lock(&lock_path, &state_path, |state| {
-        if let Some(current) = &state.current_task {
-            if dry_run {
-                println!("[DRY-RUN] Would stop timer for Task {}", current.id);
-            } else if current.timer_id.is_some() {
-                // Stop 7Pace timer if active
-                if !matches!(format, OutputFormat::Json) {
-                    println!("Stopping timer for Task {}...", current.id);
-                }
-                // Currently implementing stop using config might be complex in closure,
-                // for now we trust the CLI/User to manage this, or implement full stop logic later.
-                // The current implementation is just state maintenance essentially.
-                // NOTE: To properly stop timer we need PAT.
-                // But inside closure?
-                // We'll leave it as is per previous implementation which just cleared state locally
-                // and printed "Stopped task", deferring API stop?
-                // Wait, previous implementation (lines 44-48) printed "Stopped task".
-                // Did it call API?
-                // Looking at old code (lines 154-162):
-                //     } else if current.timer_id.is_some() {
-                //         // Stop 7Pace timer if active
-                //         // Note: We can't access config here easily...
-                //         println!("✓ Stopped task: {} - {}", current.id, current.title);
-                //     }
-                // So it did NOT call API. This is a known limitation/TODO.
-
-                if let OutputFormat::Json = format {
-                    println!(
-                        "{}",
-                        serde_json::json!({
-                            "id": current.id,
-                            "title": current.title,
-                            "status": "stopped"
-                        })
-                    );
-                } else {
-                    println!("✓ Stopped task: {} - {}", current.id, current.title);
-                }
-                state.current_task = None;
-            } else {
-                println!("✓ Stopped task: {} - {}", current.id, current.title);
-                state.current_task = None;
-            }
+    let current_task = with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| Ok(state.current_task.clone()),
+    )?;
+
+    let Some(current) = current_task else {
+        if let OutputFormat::Json = format {
+            println!("{}", serde_json::json!({ "status": "no_active_task" }));
         } else {
-            if let OutputFormat::Json = format {
-                println!(
-                    "{}",
-                    serde_json::json!({
-                        "status": "no_active_task"
-                    })
-                );
-            } else {
-                println!("No active task to stop.");
-            }
+            println!("No active task to resume.");
         }
-        Ok(())
-    })
+        return Ok(());
+    };
+
+    if !current.is_paused() {
+        if let OutputFormat::Json = format {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "id": current.id,
+                    "title": current.title,
+                    "status": "not_paused"
+                })
+            );
+        } else {
+            println!("Task {} is not paused.", current.id);
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("[DRY-RUN] Would resume timer for Task {}", current.id);
+        return Ok(());
+    }
+
+    let pat = config.get_devops_pat()?;
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
+    let timer = pace_client
+        .start_timer(current.id, None)
+        .with_context(|| format!("Failed to start 7Pace timer for Task {}", current.id))?;
+
+    with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            if let Some(task) = state.current_task.as_mut() {
+                task.timer_id = Some(timer.id.clone());
+                task.paused_at = None;
+            }
+            Ok(())
+        },
+    )?;
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": current.id,
+                "title": current.title,
+                "status": "resumed",
+                "timer_id": timer.id
+            })
+        );
+    } else {
+        println!(
+            "{} Resumed task: {} - {}",
+            ui::Symbol::Ok,
+            current.id,
+            current.title
+        );
+    }
+
+    Ok(())
 }
 
-pub fn current(config: &Config) -> Result<()> {
+/// Move from the current task to `new_id` in one state-lock transaction: a single
+/// 7Pace timer check, then stop the old timer and start the new one back to back.
+pub fn switch(config: &Config, new_id: u32) -> Result<()> {
+    let (lock_path, state_path) = state_paths(config)?;
+
+    let pat = config.get_devops_pat()?;
+    let mut devops_client =
+        DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        devops_client = devops_client.with_base_url(url);
+    }
+    let pace_client = PaceClient::from_config(&config.devops, &config.pace, &pat);
+
+    let work_item = devops_client.get_work_item(new_id)?;
+    let title = work_item.get_title().unwrap_or("Unknown Title").to_string();
+
+    // A failed start after the old timer stopped still commits the state change,
+    // so the error is carried out of the closure instead of aborting the save
+    let start_error = with_state_lock_timeout(
+        &lock_path,
+        &state_path,
+        config.state.lock_timeout(),
+        |state| {
+            if let Some(current) = &state.current_task
+                && current.id == new_id
+            {
+                println!("Already working on Task {} - {}", current.id, current.title);
+                return Ok(None);
+            }
+
+            let existing_timer_id = match pace_client.get_current_timer()? {
+                // Timer already tracks the new task (e.g. started in the 7Pace UI)
+                Some(timer) if timer.work_item_id == new_id => Some(timer.id),
+                Some(timer) => {
+                    pace_client.stop_timer(0).with_context(|| {
+                        format!("Failed to stop 7Pace timer for Task {}", timer.work_item_id)
+                    })?;
+                    None
+                }
+                None => None,
+            };
+
+            let now = Utc::now();
+            if let Some(previous) = state.finish_current_task(now) {
+                println!("Stopped task: {} - {}", previous.id, previous.title);
+            }
+
+            let timer_id = match existing_timer_id {
+                Some(id) => id,
+                None => match pace_client.start_timer(new_id, None) {
+                    Ok(timer) => timer.id,
+                    Err(e) => return Ok(Some(e)),
+                },
+            };
+
+            state.current_task = Some(CurrentTask {
+                id: new_id,
+                title: title.clone(),
+                started_at: now,
+                expires_at: now + chrono::Duration::hours(config.state.task_expiry_hours.into()),
+                timer_id: Some(timer_id),
+                paused_at: None,
+            });
+            println!(
+                "{} Switched to task: {} - {}",
+                ui::Symbol::Ok,
+                new_id,
+                title
+            );
+            Ok(None)
+        },
+    )?;
+
+    match start_error {
+        Some(e) => Err(e).with_context(|| {
+            format!(
+                "Previous task was stopped, but the timer for Task {} could not be started",
+                new_id
+            )
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn current(config: &Config, format: OutputFormat) -> Result<()> {
     let (_lock_path, state_path) = state_paths(config)?;
 
     // Read-only access doesn't strictly need exclusive lock
     let state = State::load(&state_path)?;
 
-    if let Some(current) = state.current_task {
+    if let OutputFormat::Json = format {
+        let output = match &state.current_task {
+            Some(current) => serde_json::json!({
+                "id": current.id,
+                "title": current.title,
+                "started_at": current.started_at,
+                "expires_at": current.expires_at,
+                "elapsed_minutes": (Utc::now() - current.started_at).num_minutes().max(0),
+                "expired": current.is_expired(Utc::now()),
+                "paused_at": current.paused_at
+            }),
+            None => serde_json::json!({
+                "status": "no_active_task"
+            }),
+        };
+        println!("{}", output);
+    } else {
+        print_current(&state);
+    }
+
+    Ok(())
+}
+
+/// Redraw the active task every `interval_secs` until interrupted (Ctrl-C).
+/// State is re-read each tick so switching tasks elsewhere shows up here.
+pub fn watch(config: &Config, interval_secs: u64) -> Result<()> {
+    let (_lock_path, state_path) = state_paths(config)?;
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+
+    loop {
+        // A half-written state file shouldn't end the watch; show the error and retry
+        let state = State::load(&state_path);
+
+        // Clear screen and move the cursor home
+        print!("\x1B[2J\x1B[H");
+        match state {
+            Ok(state) => print_current(&state),
+            Err(e) => println!("{} Could not read state: {:#}", ui::Symbol::Warn, e),
+        }
+        println!(
+            "\nRefreshing every {}s (Ctrl-C to exit)",
+            interval.as_secs()
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        std::thread::sleep(interval);
+    }
+}
+
+fn print_current(state: &State) {
+    if let Some(current) = &state.current_task {
+        let elapsed = (Utc::now() - current.started_at).num_minutes().max(0);
         println!("Active Task:");
         println!("  ID: {}", current.id);
         println!("  Title: {}", current.title);
         println!("  Started: {}", current.started_at);
+        println!("  Elapsed: {} min", elapsed);
         println!("  Expires: {}", current.expires_at);
+        if let Some(paused_at) = current.paused_at {
+            println!("  Paused: {} (timer stopped)", paused_at);
+        }
+        if current.is_expired(Utc::now()) {
+            println!(
+                "\n{} This task expired at {}. Run 'ano7 stop' or start a new task.",
+                ui::Symbol::Warn,
+                current.expires_at.format("%Y-%m-%d %H:%M")
+            );
+        }
     } else {
         println!("No active task.");
     }
+}
+
+/// Show tasks worked on within the last `days` days
+pub fn history(config: &Config, days: u32, format: OutputFormat) -> Result<()> {
+    let (_lock_path, state_path) = state_paths(config)?;
+    let state = State::load(&state_path)?;
+
+    let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+    let entries: Vec<_> = state
+        .history
+        .iter()
+        .filter(|e| e.stopped_at >= cutoff)
+        .collect();
+
+    if let OutputFormat::Json = format {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No task history in the last {} days.", days);
+        return Ok(());
+    }
+
+    println!("Task history (last {} days):", days);
+    println!(
+        "{:<8} {:<50} {:<18} {:<10}",
+        "Task ID", "Title", "Started", "Duration"
+    );
+    println!("{}", "-".repeat(88));
+
+    for entry in entries.iter().rev() {
+        let title = if entry.title.chars().count() > 48 {
+            format!("{}...", entry.title.chars().take(45).collect::<String>())
+        } else {
+            entry.title.clone()
+        };
+        let secs = (entry.stopped_at - entry.started_at).num_seconds().max(0) as u32;
+        println!(
+            "{:<8} {:<50} {:<18} {:<10}",
+            entry.id,
+            title,
+            entry.started_at.format("%Y-%m-%d %H:%M"),
+            crate::pace::duration::format_duration(secs)
+        );
+    }
 
     Ok(())
 }
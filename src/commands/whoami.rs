@@ -0,0 +1,99 @@
+use crate::OutputFormat;
+use crate::config::Config;
+use crate::devops::client::DevOpsClient;
+use crate::graph::auth::GraphAuthenticator;
+use crate::graph::client::GraphClient;
+use crate::ui;
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Account a set of credentials authenticates as
+#[derive(Debug, Serialize)]
+struct AccountIdentity {
+    display_name: Option<String>,
+    email: Option<String>,
+}
+
+/// Show which accounts the DevOps PAT and the Graph token belong to.
+///
+/// Each source is checked on its own, so a bad PAT still reports the
+/// calendar identity (and vice versa).
+pub fn whoami(config: &Config, format: OutputFormat) -> Result<()> {
+    let devops = devops_identity(config);
+    let graph = if config.graph.client_id.is_empty() {
+        None
+    } else {
+        Some(tokio::runtime::Runtime::new()?.block_on(graph_identity(config)))
+    };
+
+    if let OutputFormat::Json = format {
+        println!(
+            "{}",
+            serde_json::json!({
+                "devops": source_json(&devops),
+                "graph": graph.as_ref().map_or(Value::Null, source_json),
+            })
+        );
+        return Ok(());
+    }
+
+    print_source("DevOps", &devops);
+    match &graph {
+        Some(graph) => print_source("Graph", graph),
+        None => println!("{:<8} not configured (set graph.client_id)", "Graph:"),
+    }
+
+    Ok(())
+}
+
+fn devops_identity(config: &Config) -> Result<AccountIdentity> {
+    let pat = config.get_devops_pat()?;
+    let mut client = DevOpsClient::new(&pat, &config.devops.organization, &config.devops.project);
+    if let Some(url) = &config.devops.api_url {
+        client = client.with_base_url(url);
+    }
+
+    let user = client.get_connection_data()?.authenticated_user;
+    Ok(AccountIdentity {
+        display_name: user.display_name.clone(),
+        email: user
+            .property("Mail")
+            .or_else(|| user.property("Account"))
+            .map(str::to_string),
+    })
+}
+
+async fn graph_identity(config: &Config) -> Result<AccountIdentity> {
+    let auth = GraphAuthenticator::from_config(config)?;
+    if !auth.has_cached_tokens() {
+        anyhow::bail!("Not authenticated. Run 'task oauth login' first.");
+    }
+
+    let user = GraphClient::new(auth).get_me().await?;
+    Ok(AccountIdentity {
+        display_name: user.display_name,
+        email: user.mail.or(user.user_principal_name),
+    })
+}
+
+fn source_json(result: &Result<AccountIdentity>) -> Value {
+    match result {
+        Ok(identity) => serde_json::json!(identity),
+        Err(e) => serde_json::json!({ "error": format!("{:#}", e) }),
+    }
+}
+
+fn print_source(label: &str, result: &Result<AccountIdentity>) {
+    let label = format!("{}:", label);
+    match result {
+        Ok(identity) => {
+            let name = identity.display_name.as_deref().unwrap_or("(unknown)");
+            match &identity.email {
+                Some(email) => println!("{:<8} {} <{}>", label, name, email),
+                None => println!("{:<8} {}", label, name),
+            }
+        }
+        Err(e) => println!("{:<8} {} {:#}", label, ui::Symbol::Fail, e),
+    }
+}
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, Weekday};
+use chrono_tz::Tz;
 use config::{Config as ConfigBuilder, Environment, File, FileFormat};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct Config {
@@ -15,7 +19,12 @@ pub struct Config {
     #[serde(default)]
     pub focus_blocks: FocusBlocksConfig,
     #[serde(default)]
+    pub pace: PaceConfig,
+    #[serde(default)]
     pub state: StateConfig,
+    /// Account name of the authenticated DevOps user ("me"), resolved at most once per process
+    #[serde(skip)]
+    pub current_user: OnceLock<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -30,9 +39,29 @@ pub struct DevOpsConfig {
     pub api_url: Option<String>,
     /// Optional 7Pace API URL override for testing
     pub pace_api_url: Option<String>,
-    /// Whether to migrate/use system keyring for PAT (default: true)
+    /// 7Pace API host; the organization is appended (self-hosted instances differ)
+    #[serde(default = "default_pace_host")]
+    pub pace_host: String,
+    /// Whether to migrate/use system keyring for PAT and Graph tokens (default: true)
     #[serde(default = "default_use_keyring")]
     pub use_keyring: bool,
+    /// Allowed state transitions (from -> reachable states), enforced by `state` when set
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub transitions: HashMap<String, Vec<String>>,
+    /// Worker threads for fetching hierarchy subtrees; 1 fetches sequentially
+    #[serde(default = "default_hierarchy_threads")]
+    pub hierarchy_threads: usize,
+    /// Assignee filter `list` applies when neither --assigned-to nor --mine is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_assignee: Option<String>,
+}
+
+fn default_hierarchy_threads() -> usize {
+    1
+}
+
+fn default_pace_host() -> String {
+    "https://api.timehub.7pace.com".to_string()
 }
 
 fn default_use_keyring() -> bool {
@@ -57,36 +86,128 @@ impl Default for DevOpsConfig {
             skip_states: default_skip_states(),
             api_url: None,
             pace_api_url: None,
+            pace_host: default_pace_host(),
             use_keyring: true,
+            transitions: HashMap::new(),
+            hierarchy_threads: default_hierarchy_threads(),
+            default_assignee: None,
         }
     }
 }
 
+impl DevOpsConfig {
+    /// Reject `from -> to` when a transition map is configured for `from`
+    pub fn check_transition(&self, from: &str, to: &str) -> Result<()> {
+        if from.eq_ignore_ascii_case(to) {
+            return Ok(());
+        }
+        // Case-insensitive: the config loader lowercases table keys
+        let reachable = self
+            .transitions
+            .iter()
+            .find(|(state, _)| state.eq_ignore_ascii_case(from))
+            .map(|(_, reachable)| reachable);
+        if let Some(reachable) = reachable
+            && !reachable.iter().any(|s| s.eq_ignore_ascii_case(to))
+        {
+            anyhow::bail!(
+                "Transition {} -> {} is not allowed. Reachable from {}: {}",
+                from,
+                to,
+                from,
+                reachable.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// 7Pace API root: `pace_api_url` if set, else `{pace_host}/{organization}`
+    pub fn pace_base_url(&self) -> String {
+        match &self.pace_api_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!(
+                "{}/{}",
+                self.pace_host.trim_end_matches('/'),
+                self.organization
+            ),
+        }
+    }
+
+    /// Web UI link for a work item
+    pub fn work_item_url(&self, id: u32) -> String {
+        format!(
+            "https://dev.azure.com/{}/{}/_workitems/edit/{}",
+            self.organization.replace(' ', "%20"),
+            self.project.replace(' ', "%20"),
+            id
+        )
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GraphConfig {
     pub client_id: String,
     #[serde(default = "default_tenant_id")]
     pub tenant_id: String,
+    /// Delegated permissions requested at login; must include `offline_access`
+    #[serde(default = "default_graph_scopes")]
+    pub scopes: Vec<String>,
 }
 
 fn default_tenant_id() -> String {
     "common".to_string()
 }
 
+/// Lets tokens be refreshed without signing in again
+pub const OFFLINE_ACCESS_SCOPE: &str = "offline_access";
+
+pub fn default_graph_scopes() -> Vec<String> {
+    // User.Read lets `whoami` read the signed-in profile from /me
+    ["Calendars.ReadWrite", "User.Read", OFFLINE_ACCESS_SCOPE]
+        .map(String::from)
+        .to_vec()
+}
+
 impl Default for GraphConfig {
     fn default() -> Self {
         Self {
             client_id: String::new(),
             tenant_id: "common".to_string(),
+            scopes: default_graph_scopes(),
         }
     }
 }
 
+impl GraphConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.scopes.iter().any(|s| s.trim().is_empty()) {
+            anyhow::bail!("graph.scopes must not contain empty entries");
+        }
+        if !self
+            .scopes
+            .iter()
+            .any(|s| s.trim().eq_ignore_ascii_case(OFFLINE_ACCESS_SCOPE))
+        {
+            anyhow::bail!(
+                "graph.scopes must include '{}' so tokens can be refreshed",
+                OFFLINE_ACCESS_SCOPE
+            );
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct WorkHoursConfig {
     pub start: String,
     pub end: String,
     pub timezone: String,
+    /// Never schedule Focus Blocks on Saturday or Sunday
+    #[serde(default)]
+    pub skip_weekends: bool,
+    /// Dates (YYYY-MM-DD) to treat as non-working days
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -94,6 +215,42 @@ pub struct FocusBlocksConfig {
     pub duration_minutes: u32,
     pub interval_minutes: u32,
     pub teams_presence_sync: bool,
+    /// Breathing room kept free before and after existing meetings
+    #[serde(default)]
+    pub buffer_minutes: u32,
+    /// Outlook category applied to created Focus Blocks; empty applies none
+    #[serde(default = "default_focus_category")]
+    pub category: String,
+    /// Availability shown to others while a Focus Block is on the calendar
+    #[serde(default)]
+    pub show_as: ShowAs,
+    /// Most Focus Blocks to schedule on one day; unset means no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_day: Option<u32>,
+}
+
+fn default_focus_category() -> String {
+    "Focus Block".to_string()
+}
+
+/// Free/busy status for created events, mapped to Graph's `showAs`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowAs {
+    #[default]
+    Busy,
+    Free,
+    Tentative,
+}
+
+impl ShowAs {
+    pub fn as_graph_str(self) -> &'static str {
+        match self {
+            ShowAs::Busy => "busy",
+            ShowAs::Free => "free",
+            ShowAs::Tentative => "tentative",
+        }
+    }
 }
 
 impl Default for FocusBlocksConfig {
@@ -102,15 +259,55 @@ impl Default for FocusBlocksConfig {
             duration_minutes: 45,
             interval_minutes: 15,
             teams_presence_sync: true,
+            buffer_minutes: 0,
+            category: default_focus_category(),
+            show_as: ShowAs::default(),
+            max_per_day: None,
         }
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PaceConfig {
+    /// Milliseconds to wait between 7Pace requests in bulk operations (avoids 429s)
+    #[serde(default)]
+    pub request_delay_ms: u64,
+    /// Retries per entry when 7Pace rate limits or is unavailable in bulk operations
+    #[serde(default = "default_pace_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_pace_max_retries() -> u32 {
+    crate::pace::client::DEFAULT_MAX_RETRIES
+}
+
+impl Default for PaceConfig {
+    fn default() -> Self {
+        Self {
+            request_delay_ms: 0,
+            max_retries: default_pace_max_retries(),
+        }
+    }
+}
+
+impl PaceConfig {
+    pub fn request_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.request_delay_ms)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct StateConfig {
     pub task_expiry_hours: u32,
     /// Optional override for state directory (for testing)
     pub state_dir_override: Option<PathBuf>,
+    /// Seconds to wait for another process to release the state lock
+    #[serde(default = "default_lock_timeout_secs")]
+    pub lock_timeout_secs: u64,
+}
+
+fn default_lock_timeout_secs() -> u64 {
+    crate::state::DEFAULT_LOCK_TIMEOUT.as_secs()
 }
 
 impl Default for StateConfig {
@@ -118,13 +315,41 @@ impl Default for StateConfig {
         Self {
             task_expiry_hours: 24,
             state_dir_override: None,
+            lock_timeout_secs: default_lock_timeout_secs(),
         }
     }
 }
 
+impl StateConfig {
+    pub fn lock_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.lock_timeout_secs)
+    }
+}
+
 impl WorkHoursConfig {
+    /// Whether Focus Blocks may be scheduled on the given date
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        if self.skip_weekends && matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        !self.holidays.contains(&date)
+    }
+
+    /// IANA timezone the work hours are expressed in; empty means UTC
+    pub fn tz(&self) -> Result<Tz> {
+        if self.timezone.trim().is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid work hours timezone '{}'", self.timezone))
+    }
+
     /// Validate work hours configuration
     pub fn validate(&self) -> Result<()> {
+        self.tz()?;
+
         if self.start.is_empty() || self.end.is_empty() {
             return Ok(()); // Empty is fine (no validation)
         }
@@ -158,12 +383,28 @@ impl WorkHoursConfig {
 }
 
 impl FocusBlocksConfig {
+    /// Categories to set on a new Focus Block
+    pub fn categories(&self) -> Vec<String> {
+        let category = self.category.trim();
+        if category.is_empty() {
+            Vec::new()
+        } else {
+            vec![category.to_string()]
+        }
+    }
+
     /// Validate focus blocks configuration
     pub fn validate(&self) -> Result<()> {
         if self.duration_minutes == 0 {
             anyhow::bail!("Focus block duration must be greater than 0");
         }
 
+        if self.max_per_day == Some(0) {
+            anyhow::bail!(
+                "focus_blocks.max_per_day must be at least 1; leave it unset for no limit"
+            );
+        }
+
         // Warn if interval is not a common value
         let common_intervals = [15, 25, 30, 50, 60];
         if !common_intervals.contains(&self.interval_minutes) {
@@ -178,13 +419,25 @@ impl FocusBlocksConfig {
 }
 
 impl Config {
+    /// Cached account name for "me", running `resolve` only on first use
+    pub fn current_user_or_resolve(
+        &self,
+        resolve: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        if let Some(user) = self.current_user.get() {
+            return Ok(user.clone());
+        }
+        let user = resolve()?;
+        Ok(self.current_user.get_or_init(|| user).clone())
+    }
+
     /// Get DevOps PAT from keyring or config (with migration)
     pub fn get_devops_pat(&self) -> Result<String> {
         // Try keyring first if enabled
-        if self.devops.use_keyring {
-            if let Ok(pat) = crate::keyring::get_devops_pat() {
-                return Ok(pat);
-            }
+        if self.devops.use_keyring
+            && let Ok(pat) = crate::keyring::get_devops_pat()
+        {
+            return Ok(pat);
         }
 
         // Fall back to config file (legacy or testing)
@@ -199,6 +452,7 @@ impl Config {
     pub fn validate(&self) -> Result<()> {
         self.work_hours.validate()?;
         self.focus_blocks.validate()?;
+        self.graph.validate()?;
         Ok(())
     }
 
@@ -235,11 +489,16 @@ pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Config> {
         .context("Failed to parse config file")
 }
 
-pub fn load() -> Result<Config> {
-    let config_dir = home::home_dir()
+/// Location of the user config file (~/.ao-no-out7ook/config.toml)
+pub fn config_path() -> Result<PathBuf> {
+    Ok(home::home_dir()
         .context("Could not find home directory")?
-        .join(".ao-no-out7ook");
-    let config_path = config_dir.join("config.toml");
+        .join(".ao-no-out7ook")
+        .join("config.toml"))
+}
+
+pub fn load() -> Result<Config> {
+    let config_path = config_path()?;
 
     let mut config = load_from_path(&config_path)?;
 
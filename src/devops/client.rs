@@ -1,61 +1,173 @@
-use crate::devops::models::WorkItem;
+use crate::devops::models::{
+    Attachment, CommentList, ConnectionData, Identity, WorkItem, WorkItemComment, WorkItemType,
+};
+use crate::error::ApiError;
+use crate::pace::retry::{TransientError, retry_after, with_transient_retry};
 use anyhow::{Context, Result};
 use base64::prelude::*;
-use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Maximum number of ids accepted by the workitemsbatch endpoint
+const BATCH_SIZE: usize = 200;
+
+/// Default number of retries for transient failures (429/503/network)
+const DEFAULT_MAX_RETRIES: u32 = 3;
 
 pub struct DevOpsClient {
     client: Client,
-    base_url: String, // https://dev.azure.com/{org}
+    base_url: String,          // https://dev.azure.com/{org}
+    identity_base_url: String, // https://vssps.dev.azure.com/{org}
     project: String,
     pat: String,
+    max_retries: u32,
+    /// Type definitions rarely change, so keep them for the client's lifetime
+    type_cache: Mutex<HashMap<String, WorkItemType>>,
+}
+
+/// The useful part of an error body: the `message` of a DevOps error object
+/// (e.g. a WIQL syntax error), else the trimmed body text
+fn server_message(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("message")?.as_str().map(|m| m.trim().to_string()))
+        .filter(|m| !m.is_empty());
+    Some(message.unwrap_or_else(|| body.to_string()))
 }
 
 impl DevOpsClient {
     pub fn new(pat: &str, org: &str, project: &str) -> Self {
         let base_url = format!("https://dev.azure.com/{}", org);
+        let identity_base_url = format!("https://vssps.dev.azure.com/{}", org);
         Self {
             client: Client::new(),
             base_url,
+            identity_base_url,
             project: project.to_string(),
             pat: pat.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            type_cache: Mutex::new(HashMap::new()),
         }
     }
 
     /// Helper for testing to override base URL (e.g. wiremock)
     pub fn with_base_url(mut self, url: &str) -> Self {
         self.base_url = url.trim_end_matches('/').to_string();
+        self.identity_base_url = self.base_url.clone();
+        self
+    }
+
+    /// Override how many times transient failures are retried
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
         self
     }
 
+    /// Pass successful responses through; classify anything else as an [`ApiError`]
+    fn check_status(response: Response, what: &str) -> Result<Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().unwrap_or_default();
+        let message = match server_message(&body) {
+            Some(detail) => format!("{} ({}): {}", what, status, detail),
+            None => format!("{}: status {}", what, status),
+        };
+        Err(ApiError::from_status(status, message).into())
+    }
+
     fn auth_header(&self) -> String {
         let val = format!(":{}", self.pat);
         format!("Basic {}", BASE64_STANDARD.encode(val))
     }
 
-    pub fn get_work_item_type(
-        &self,
-        type_name: &str,
-    ) -> Result<crate::devops::models::WorkItemType> {
+    /// Send an idempotent request, retrying on 429/503 and network errors (FR2.8)
+    fn send_with_retry<F>(&self, build: F) -> Result<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        with_transient_retry(
+            || {
+                let response = build().send().map_err(|e| {
+                    if e.is_builder() {
+                        anyhow::Error::new(e)
+                    } else {
+                        TransientError {
+                            message: format!("network error: {}", e),
+                            retry_after: None,
+                            status: None,
+                        }
+                        .into()
+                    }
+                })?;
+
+                let status = response.status();
+                if status == StatusCode::TOO_MANY_REQUESTS
+                    || status == StatusCode::SERVICE_UNAVAILABLE
+                {
+                    return Err(TransientError {
+                        message: format!("DevOps API error: status {}", status),
+                        retry_after: retry_after(response.headers()),
+                        status: Some(status),
+                    }
+                    .into());
+                }
+
+                Ok(response)
+            },
+            self.max_retries,
+        )
+        .map_err(|e| match e.downcast::<TransientError>() {
+            Ok(transient) => ApiError::from(transient).into(),
+            Err(e) => e,
+        })
+    }
+
+    /// Send a request once (for non-idempotent writes), mapping transport failures
+    /// to `ApiError::Network`
+    fn send_once(&self, request: RequestBuilder) -> Result<Response> {
+        request
+            .send()
+            .map_err(|e| ApiError::Network(format!("network error: {}", e)).into())
+    }
+
+    pub fn get_work_item_type(&self, type_name: &str) -> Result<WorkItemType> {
+        if let Some(cached) = self.type_cache.lock().unwrap().get(type_name) {
+            return Ok(cached.clone());
+        }
+
         let url = format!(
             "{}/{}/_apis/wit/workitemtypes/{}?api-version=7.0",
             self.base_url, self.project, type_name
         );
 
         let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.auth_header())
-            .send()
+            .send_with_retry(|| {
+                self.client
+                    .get(&url)
+                    .header("Authorization", self.auth_header())
+            })
             .context("Failed to fetch work item type definition")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("WorkItemType API error: status {}", response.status());
-        }
+        let response = Self::check_status(response, "WorkItemType API error")?;
 
         let type_def = response
-            .json::<crate::devops::models::WorkItemType>()
+            .json::<WorkItemType>()
             .context("Failed to parse WorkItemType")?;
 
+        self.type_cache
+            .lock()
+            .unwrap()
+            .insert(type_name.to_string(), type_def.clone());
+
         Ok(type_def)
     }
 
@@ -67,15 +179,14 @@ impl DevOpsClient {
         );
 
         let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.auth_header())
-            .send()
+            .send_with_retry(|| {
+                self.client
+                    .get(&url)
+                    .header("Authorization", self.auth_header())
+            })
             .context("Failed to send request to DevOps REST API")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("DevOps API error: status {}", response.status());
-        }
+        let response = Self::check_status(response, "DevOps API error")?;
 
         let work_item = response
             .json::<WorkItem>()
@@ -84,34 +195,100 @@ impl DevOpsClient {
         Ok(work_item)
     }
 
+    /// Files attached to a work item
+    pub fn list_attachments(&self, id: u32) -> Result<Vec<Attachment>> {
+        Ok(self.get_work_item(id)?.get_attachments())
+    }
+
+    /// Download an attachment's raw content to `dest`, returning its size in bytes
+    pub fn download_attachment(&self, url: &str, dest: &Path) -> Result<u64> {
+        let response = self
+            .send_with_retry(|| {
+                self.client
+                    .get(url)
+                    .query(&[("api-version", "7.0")])
+                    .header("Authorization", self.auth_header())
+            })
+            .context("Failed to download attachment")?;
+
+        let response = Self::check_status(response, "Attachment download error")?;
+
+        let content = response
+            .bytes()
+            .context("Failed to read attachment content")?;
+        std::fs::write(dest, &content)
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+
+        Ok(content.len() as u64)
+    }
+
+    /// Names of the work item types defined in the project
+    pub fn list_work_item_types(&self) -> Result<Vec<String>> {
+        let url = format!(
+            "{}/{}/_apis/wit/workitemtypes?api-version=7.0",
+            self.base_url, self.project
+        );
+
+        let response = self
+            .send_with_retry(|| {
+                self.client
+                    .get(&url)
+                    .header("Authorization", self.auth_header())
+            })
+            .context("Failed to list work item types")?;
+
+        let response = Self::check_status(response, "WorkItemType API error")?;
+
+        // Response is { "count": N, "value": [ { "name": ..., ... } ] }
+        let json_val = response.json::<serde_json::Value>()?;
+        let names = json_val
+            .get("value")
+            .and_then(|v| v.as_array())
+            .context("Work item types response missing 'value' field")?
+            .iter()
+            .filter_map(|t| t.get("name").and_then(|n| n.as_str()))
+            .map(str::to_string)
+            .collect();
+
+        Ok(names)
+    }
+
+    /// Fetch several work items at once; ids that are deleted or not visible
+    /// to this PAT are left out of the result
     pub fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<WorkItem>> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
+        // workitemsbatch accepts at most 200 ids per call, so fetch in chunks
+        let mut items = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(BATCH_SIZE) {
+            items.extend(self.fetch_batch_chunk(chunk)?);
         }
+        Ok(items)
+    }
 
+    fn fetch_batch_chunk(&self, ids: &[u32]) -> Result<Vec<WorkItem>> {
         // Use POST /wit/workitemsbatch per Azure DevOps API spec
         let url = format!(
             "{}/{}/_apis/wit/workitemsbatch?api-version=7.0",
             self.base_url, self.project
         );
 
+        // Omit ids that are deleted or not visible instead of failing the whole batch
         let body = serde_json::json!({
             "ids": ids,
-            "$expand": "all"
+            "$expand": "all",
+            "errorPolicy": "omit"
         });
 
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", self.auth_header())
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
+            .send_with_retry(|| {
+                self.client
+                    .post(&url)
+                    .header("Authorization", self.auth_header())
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+            })
             .context("Failed to batch fetch work items")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("DevOps Batch API error: status {}", response.status());
-        }
+        let response = Self::check_status(response, "DevOps Batch API error")?;
 
         // Response is { "count": N, "value": [ ... ] }
         let json_val = response.json::<serde_json::Value>()?;
@@ -119,31 +296,43 @@ impl DevOpsClient {
             .get("value")
             .context("Batch response missing 'value' field")?;
 
-        let items: Vec<WorkItem> = serde_json::from_value(items_val.clone())
+        // Omitted ids come back as nulls
+        let items: Vec<Option<WorkItem>> = serde_json::from_value(items_val.clone())
             .context("Failed to deserialize batch work items")?;
 
-        Ok(items)
+        Ok(items.into_iter().flatten().collect())
     }
 
     pub fn execute_wiql(&self, query: &str) -> Result<crate::devops::models::WiqlResponse> {
-        let url = format!(
+        self.execute_wiql_with_precision(query, false)
+    }
+
+    /// Run a WIQL query; `time_precision` allows times (not just dates) in date comparisons
+    pub fn execute_wiql_with_precision(
+        &self,
+        query: &str,
+        time_precision: bool,
+    ) -> Result<crate::devops::models::WiqlResponse> {
+        let mut url = format!(
             "{}/{}/_apis/wit/wiql?api-version=7.0",
             self.base_url, self.project
         );
+        if time_precision {
+            url.push_str("&timePrecision=true");
+        }
 
         let body = serde_json::json!({ "query": query });
 
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", self.auth_header())
-            .json(&body)
-            .send()
+            .send_with_retry(|| {
+                self.client
+                    .post(&url)
+                    .header("Authorization", self.auth_header())
+                    .json(&body)
+            })
             .context("Failed to execute WIQL")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("WIQL API error: status {}", response.status());
-        }
+        let response = Self::check_status(response, "WIQL API error")?;
 
         let wiql_resp = response
             .json::<crate::devops::models::WiqlResponse>()
@@ -152,6 +341,170 @@ impl DevOpsClient {
         Ok(wiql_resp)
     }
 
+    /// Identity the PAT authenticates as (via connectionData)
+    pub fn get_connection_data(&self) -> Result<ConnectionData> {
+        let url = format!("{}/_apis/connectionData", self.base_url);
+
+        let response = self
+            .send_with_retry(|| {
+                self.client
+                    .get(&url)
+                    .query(&[("api-version", "7.0-preview")])
+                    .header("Authorization", self.auth_header())
+            })
+            .context("Failed to fetch connection data")?;
+
+        let response = Self::check_status(response, "Connection data API error")?;
+
+        response
+            .json::<ConnectionData>()
+            .context("Failed to parse connection data")
+    }
+
+    /// Account name of the user the PAT belongs to (via connectionData)
+    pub fn get_authenticated_user(&self) -> Result<String> {
+        self.get_connection_data()?
+            .authenticated_user
+            .unique_name()
+            .map(str::to_string)
+            .context("Authenticated user has no account name")
+    }
+
+    /// Resolve a display name (or email) to the unique name accepted by System.AssignedTo
+    pub fn resolve_identity(&self, query: &str) -> Result<String> {
+        let url = format!("{}/_apis/identities", self.identity_base_url);
+
+        let response = self
+            .send_with_retry(|| {
+                self.client
+                    .get(&url)
+                    .query(&[
+                        ("searchFilter", "General"),
+                        ("filterValue", query),
+                        ("queryMembership", "None"),
+                        ("api-version", "7.0"),
+                    ])
+                    .header("Authorization", self.auth_header())
+            })
+            .context("Failed to search identities")?;
+
+        let response = Self::check_status(response, "Identities API error")?;
+
+        let json_val = response.json::<serde_json::Value>()?;
+        let identities: Vec<Identity> = serde_json::from_value(
+            json_val
+                .get("value")
+                .cloned()
+                .context("Identities response missing 'value' field")?,
+        )
+        .context("Failed to deserialize identities")?;
+
+        match identities.as_slice() {
+            [] => anyhow::bail!("No DevOps user found matching '{}'", query),
+            [identity] => identity
+                .unique_name()
+                .map(str::to_string)
+                .with_context(|| format!("Identity for '{}' has no account name", query)),
+            many => {
+                let names: Vec<&str> = many
+                    .iter()
+                    .map(|i| i.unique_name().or(i.display_name.as_deref()).unwrap_or("?"))
+                    .collect();
+                anyhow::bail!(
+                    "'{}' matches {} users ({}). Use their email instead.",
+                    query,
+                    many.len(),
+                    names.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Post a discussion comment on a work item
+    pub fn add_comment(&self, id: u32, text: &str) -> Result<WorkItemComment> {
+        let url = format!(
+            "{}/{}/_apis/wit/workItems/{}/comments?api-version=7.0-preview.3",
+            self.base_url, self.project, id
+        );
+
+        let body = serde_json::json!({ "text": text });
+
+        let response = self
+            .send_once(
+                self.client
+                    .post(&url)
+                    .header("Authorization", self.auth_header())
+                    .json(&body),
+            )
+            .context("Failed to post work item comment")?;
+
+        let response = Self::check_status(response, "Comment API error")?;
+
+        let comment = response
+            .json::<WorkItemComment>()
+            .context("Failed to parse created comment")?;
+
+        Ok(comment)
+    }
+
+    /// All discussion comments on a work item, newest first
+    pub fn get_comments(&self, id: u32) -> Result<Vec<WorkItemComment>> {
+        let url = format!(
+            "{}/{}/_apis/wit/workItems/{}/comments?api-version=7.0-preview.3",
+            self.base_url, self.project, id
+        );
+
+        let mut comments = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let response = self
+                .send_with_retry(|| {
+                    let request = self
+                        .client
+                        .get(&url)
+                        .header("Authorization", self.auth_header());
+                    match &continuation {
+                        Some(token) => request.query(&[("continuationToken", token)]),
+                        None => request,
+                    }
+                })
+                .context("Failed to fetch work item comments")?;
+
+            let response = Self::check_status(response, "Comment API error")?;
+            let page = response
+                .json::<CommentList>()
+                .context("Failed to parse work item comments")?;
+
+            comments.extend(page.comments);
+            match page.continuation_token {
+                Some(token) if !token.is_empty() => continuation = Some(token),
+                _ => break,
+            }
+        }
+
+        comments.sort_by_key(|c| std::cmp::Reverse(c.created_date));
+        Ok(comments)
+    }
+
+    /// Delete a work item (Azure DevOps moves it to the project's recycle bin)
+    pub fn delete_work_item(&self, id: u32) -> Result<()> {
+        let url = format!(
+            "{}/{}/_apis/wit/workitems/{}?api-version=7.0",
+            self.base_url, self.project, id
+        );
+
+        let response = self
+            .send_once(
+                self.client
+                    .delete(&url)
+                    .header("Authorization", self.auth_header()),
+            )
+            .context("Failed to delete work item")?;
+
+        Self::check_status(response, &format!("Delete API error for work item {}", id))?;
+        Ok(())
+    }
+
     pub fn update_work_item(
         &self,
         id: u32,
@@ -164,6 +517,15 @@ impl DevOpsClient {
     pub fn create_work_item(
         &self,
         fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<WorkItem> {
+        self.create_work_item_with_parent(fields, None)
+    }
+
+    /// Create a work item, linking it to `parent_url` in the same request
+    pub fn create_work_item_with_parent(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+        parent_url: Option<&str>,
     ) -> Result<WorkItem> {
         // Extract work item type from fields
         let work_item_type = fields
@@ -185,21 +547,28 @@ impl DevOpsClient {
                 "value": value
             }));
         }
+        if let Some(parent_url) = parent_url {
+            operations.push(serde_json::json!({
+                "op": "add",
+                "path": "/relations/-",
+                "value": {
+                    "rel": "System.LinkTypes.Hierarchy-Reverse",
+                    "url": parent_url
+                }
+            }));
+        }
 
         let response = self
-            .client
-            .post(&url)
-            .basic_auth("", Some(&self.pat))
-            .header("Content-Type", "application/json-patch+json")
-            .json(&operations)
-            .send()
+            .send_once(
+                self.client
+                    .post(&url)
+                    .basic_auth("", Some(&self.pat))
+                    .header("Content-Type", "application/json-patch+json")
+                    .json(&operations),
+            )
             .context("Failed to send create work item request")?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().unwrap_or_default();
-            anyhow::bail!("Create work item failed ({}): {}", status, body);
-        }
+        let response = Self::check_status(response, "Create work item failed")?;
 
         response
             .json::<WorkItem>()
@@ -216,13 +585,15 @@ impl DevOpsClient {
         if let Some(expected) = expected_rev {
             let current = self.get_work_item(id)?;
             if current.rev != expected {
-                anyhow::bail!(
-                    "Conflict detected: Work item {} has been modified (expected rev {}, current rev {}). \
-                     Fetch latest and retry.",
-                    id,
-                    expected,
-                    current.rev
-                );
+                return Err(ApiError::Conflict {
+                    status: StatusCode::PRECONDITION_FAILED,
+                    message: format!(
+                        "Conflict detected: Work item {} has been modified (expected rev {}, current rev {}). \
+                         Fetch latest and retry.",
+                        id, expected, current.rev
+                    ),
+                }
+                .into());
             }
         }
 
@@ -232,18 +603,17 @@ impl DevOpsClient {
         );
 
         let response = self
-            .client
-            .patch(&url)
-            .header("Authorization", self.auth_header())
-            .header("Content-Type", "application/json-patch+json")
-            .json(&operations)
-            .send()
+            .send_once(
+                self.client
+                    .patch(&url)
+                    .header("Authorization", self.auth_header())
+                    .header("Content-Type", "application/json-patch+json")
+                    .json(&operations),
+            )
             .context("Failed to update work item")?;
 
-        if !response.status().is_success() {
-            let error_text = response.text().unwrap_or_default();
-            anyhow::bail!("Update API error: {}. details: {}", id, error_text);
-        }
+        let response =
+            Self::check_status(response, &format!("Update API error for work item {}", id))?;
 
         let work_item = response
             .json::<WorkItem>()
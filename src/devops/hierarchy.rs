@@ -1,6 +1,7 @@
 use crate::devops::client::DevOpsClient;
 use crate::devops::models::WorkItem;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use rayon::prelude::*;
 use std::fmt;
 use termtree::Tree;
 
@@ -18,53 +19,82 @@ impl fmt::Display for HierarchyNode {
     }
 }
 
+impl HierarchyNode {
+    /// Consume the tree into a flat list, parents before their children
+    pub fn flatten(self) -> Vec<WorkItem> {
+        let mut items = vec![self.item];
+        for child in self.children {
+            items.extend(child.flatten());
+        }
+        items
+    }
+
+    /// Number of descendants and their summed effort; missing effort counts as 0
+    pub fn effort_rollup(&self) -> (usize, f64) {
+        self.children
+            .iter()
+            .fold((0, 0.0), |(count, total), child| {
+                let (child_count, child_total) = child.effort_rollup();
+                (
+                    count + 1 + child_count,
+                    total + child.item.get_effort().unwrap_or(0.0) + child_total,
+                )
+            })
+    }
+}
+
 pub fn build_tree(client: &DevOpsClient, root_id: u32, depth: u8) -> Result<HierarchyNode> {
     let root = client.get_work_item(root_id)?;
+    build_tree_from_item(client, root, depth)
+}
 
+/// Build a tree below an already-fetched item (it must carry its relations)
+pub fn build_tree_from_item(
+    client: &DevOpsClient,
+    item: WorkItem,
+    depth: u8,
+) -> Result<HierarchyNode> {
     if depth == 0 {
         return Ok(HierarchyNode {
-            item: root,
+            item,
             children: Vec::new(),
         });
     }
 
     let mut children = Vec::new();
-    if let Some(relations) = &root.relations {
-        let child_ids: Vec<u32> = relations
-            .iter()
-            .filter(|r| r.rel == "System.LinkTypes.Hierarchy-Forward")
-            .filter_map(|r| {
-                // "url": "https://.../_apis/wit/workItems/123"
-                r.url.split('/').next_back().and_then(|s| s.parse().ok())
-            })
-            .collect();
-
-        if !child_ids.is_empty() {
-            // Optimization: Batch fetch immediate children
-            let child_items = client.get_work_items_batch(&child_ids)?;
-
-            for child_item in child_items {
-                // For each child, recurse?
-                // If we batch fetched, we have the item. But to get ITS children, we need its relations.
-                // The batch fetch usually returns relations if $expand=all is set (which we did).
+    for child_item in fetch_children(client, &item)? {
+        children.push(build_tree_from_item(client, child_item, depth - 1)?);
+    }
+    children.sort_by_key(|c| c.item.id);
 
-                // So we can convert WorkItem to HierarchyNode recursively?
-                // But wait, `build_tree` calls `get_work_item`.
-                // We should refactor to `build_tree_from_item`.
+    Ok(HierarchyNode { item, children })
+}
 
-                let node = build_tree_recursive(client, child_item, depth - 1)?;
-                children.push(node);
-            }
-        }
+/// Like [`build_tree_from_item`], but sibling subtrees are fetched concurrently
+/// on a pool of `threads` workers sharing `client`. Children come back sorted by
+/// id, so the tree is identical to the sequential one.
+pub fn build_tree_parallel(
+    client: &DevOpsClient,
+    item: WorkItem,
+    depth: u8,
+    threads: usize,
+) -> Result<HierarchyNode> {
+    if threads <= 1 {
+        return build_tree_from_item(client, item, depth);
     }
 
-    Ok(HierarchyNode {
-        item: root,
-        children,
-    })
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .context("Failed to start hierarchy fetch threads")?;
+    pool.install(|| build_subtree_parallel(client, item, depth))
 }
 
-fn build_tree_recursive(client: &DevOpsClient, item: WorkItem, depth: u8) -> Result<HierarchyNode> {
+fn build_subtree_parallel(
+    client: &DevOpsClient,
+    item: WorkItem,
+    depth: u8,
+) -> Result<HierarchyNode> {
     if depth == 0 {
         return Ok(HierarchyNode {
             item,
@@ -72,26 +102,35 @@ fn build_tree_recursive(client: &DevOpsClient, item: WorkItem, depth: u8) -> Res
         });
     }
 
-    let mut children = Vec::new();
-    if let Some(relations) = &item.relations {
-        let child_ids: Vec<u32> = relations
-            .iter()
-            .filter(|r| r.rel == "System.LinkTypes.Hierarchy-Forward")
-            .filter_map(|r| r.url.split('/').next_back().and_then(|s| s.parse().ok()))
-            .collect();
-
-        if !child_ids.is_empty() {
-            let child_items = client.get_work_items_batch(&child_ids)?;
-            for child_item in child_items {
-                let node = build_tree_recursive(client, child_item, depth - 1)?;
-                children.push(node);
-            }
-        }
-    }
+    let mut children = fetch_children(client, &item)?
+        .into_par_iter()
+        .map(|child_item| build_subtree_parallel(client, child_item, depth - 1))
+        .collect::<Result<Vec<_>>>()?;
+    children.sort_by_key(|c| c.item.id);
 
     Ok(HierarchyNode { item, children })
 }
 
+/// Batch fetch the immediate children of `item`; $expand=all gives us their relations too
+fn fetch_children(client: &DevOpsClient, item: &WorkItem) -> Result<Vec<WorkItem>> {
+    let Some(relations) = &item.relations else {
+        return Ok(Vec::new());
+    };
+    let child_ids: Vec<u32> = relations
+        .iter()
+        .filter(|r| r.rel == "System.LinkTypes.Hierarchy-Forward")
+        .filter_map(|r| {
+            // "url": "https://.../_apis/wit/workItems/123"
+            r.url.split('/').next_back().and_then(|s| s.parse().ok())
+        })
+        .collect();
+
+    if child_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    client.get_work_items_batch(&child_ids)
+}
+
 pub fn print_tree(node: &HierarchyNode) {
     let tree = build_termtree(node);
     println!("{}", tree);
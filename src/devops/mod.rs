@@ -1,3 +1,4 @@
 pub mod client;
 pub mod hierarchy;
 pub mod models;
+pub mod store;
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -54,6 +55,117 @@ pub struct WorkItemStateColor {
     pub category: String,
 }
 
+/// Discussion comment on a work item
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkItemComment {
+    pub id: u32,
+    #[serde(rename = "workItemId", default)]
+    pub work_item_id: u32,
+    /// HTML
+    pub text: String,
+    #[serde(rename = "createdBy", default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<CommentAuthor>,
+    #[serde(
+        rename = "createdDate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommentAuthor {
+    #[serde(rename = "displayName", default)]
+    pub display_name: Option<String>,
+    #[serde(rename = "uniqueName", default)]
+    pub unique_name: Option<String>,
+}
+
+impl WorkItemComment {
+    pub fn author(&self) -> Option<&str> {
+        let author = self.created_by.as_ref()?;
+        author
+            .display_name
+            .as_deref()
+            .or(author.unique_name.as_deref())
+    }
+}
+
+/// One page of `_apis/wit/workItems/{id}/comments`
+#[derive(Debug, Deserialize)]
+pub struct CommentList {
+    #[serde(default)]
+    pub comments: Vec<WorkItemComment>,
+    #[serde(rename = "continuationToken", default)]
+    pub continuation_token: Option<String>,
+}
+
+/// Identity returned by the identities search API
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Identity {
+    #[serde(default)]
+    pub descriptor: Option<String>,
+    #[serde(rename = "providerDisplayName", default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub properties: HashMap<String, Value>,
+}
+
+impl Identity {
+    /// String value of an identity property such as "Account" or "Mail"
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .get(key)
+            .and_then(|prop| prop.get("$value").and_then(|v| v.as_str()))
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Best value for System.AssignedTo: account (UPN), then mail, then descriptor
+    pub fn unique_name(&self) -> Option<&str> {
+        self.property("Account")
+            .or_else(|| self.property("Mail"))
+            .or(self.descriptor.as_deref())
+    }
+}
+
+/// Response of the connectionData API, describing who the PAT belongs to
+#[derive(Debug, Deserialize)]
+pub struct ConnectionData {
+    #[serde(rename = "authenticatedUser")]
+    pub authenticated_user: Identity,
+}
+
+/// Flattened view of a work item for structured output (e.g. `show --format yaml`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkItemSummary {
+    pub id: u32,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub work_item_type: String,
+    pub state: String,
+    pub assigned_to: Option<String>,
+    pub parent_id: Option<u32>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl From<&WorkItem> for WorkItemSummary {
+    fn from(item: &WorkItem) -> Self {
+        Self {
+            id: item.id,
+            title: item.get_title().unwrap_or_default().to_string(),
+            work_item_type: item.get_type().unwrap_or_default().to_string(),
+            state: item.get_state().unwrap_or_default().to_string(),
+            assigned_to: item.get_assigned_to().map(str::to_string),
+            parent_id: item.get_parent_id(),
+            tags: item.get_tags().unwrap_or_default(),
+            description: item
+                .get_description()
+                .map(crate::utils::markdown::strip_html_tags),
+        }
+    }
+}
+
 // Helper to access common fields easily
 impl WorkItem {
     pub fn get_title(&self) -> Option<&str> {
@@ -103,11 +215,56 @@ impl WorkItem {
             })
     }
 
+    /// Effort estimate (`Microsoft.VSTS.Scheduling.Effort`), if set
+    pub fn get_effort(&self) -> Option<f64> {
+        self.fields
+            .get("Microsoft.VSTS.Scheduling.Effort")
+            .and_then(|v| v.as_f64())
+    }
+
+    /// Story points (`Microsoft.VSTS.Scheduling.StoryPoints`), if set
+    pub fn get_story_points(&self) -> Option<f64> {
+        self.fields
+            .get("Microsoft.VSTS.Scheduling.StoryPoints")
+            .and_then(|v| v.as_f64())
+    }
+
     pub fn get_description(&self) -> Option<&str> {
         self.fields
             .get("System.Description")
             .and_then(|v| v.as_str())
     }
+
+    /// Files attached to the item (`AttachedFile` relations; needs `$expand=all`)
+    pub fn get_attachments(&self) -> Vec<Attachment> {
+        self.relations
+            .iter()
+            .flatten()
+            .filter(|r| r.rel == "AttachedFile")
+            .map(|r| {
+                let attribute = |key: &str| r.attributes.as_ref().and_then(|a| a.get(key));
+                Attachment {
+                    name: attribute("name")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string)
+                        .unwrap_or_else(|| {
+                            r.url.rsplit('/').next().unwrap_or_default().to_string()
+                        }),
+                    size: attribute("resourceSize").and_then(|v| v.as_u64()),
+                    url: r.url.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// File attached to a work item
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    /// Size in bytes, when DevOps reports it
+    pub size: Option<u64>,
+    pub url: String,
 }
 
 #[cfg(test)]
@@ -156,4 +313,56 @@ mod tests {
         assert_eq!(response.work_items.len(), 2);
         assert_eq!(response.work_items[0].id, 1);
     }
+
+    #[test]
+    fn test_work_item_summary_yaml_round_trip() {
+        let item = WorkItem {
+            id: 42,
+            rev: 1,
+            fields: HashMap::from([
+                ("System.Title".to_string(), json!("Login page")),
+                ("System.WorkItemType".to_string(), json!("Task")),
+                ("System.State".to_string(), json!("Active")),
+                (
+                    "System.AssignedTo".to_string(),
+                    json!({ "displayName": "Jane Smith" }),
+                ),
+                ("System.Tags".to_string(), json!("ui; auth")),
+                (
+                    "System.Description".to_string(),
+                    json!("<p>Build the <b>form</b></p>"),
+                ),
+            ]),
+            relations: Some(vec![WorkItemRelation {
+                rel: "System.LinkTypes.Hierarchy-Reverse".to_string(),
+                url: "https://dev.azure.com/org/_apis/wit/workItems/7".to_string(),
+                attributes: None,
+            }]),
+            url: String::new(),
+        };
+
+        let yaml = serde_yaml::to_string(&WorkItemSummary::from(&item)).unwrap();
+        let map: std::collections::BTreeMap<String, serde_yaml::Value> =
+            serde_yaml::from_str(&yaml).unwrap();
+
+        let keys: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "assigned_to",
+                "description",
+                "id",
+                "parent_id",
+                "state",
+                "tags",
+                "title",
+                "type"
+            ]
+        );
+        assert_eq!(map["parent_id"], serde_yaml::Value::from(7));
+        assert_eq!(
+            map["description"],
+            serde_yaml::Value::from("Build the form")
+        );
+    }
 }
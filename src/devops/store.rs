@@ -0,0 +1,252 @@
+use crate::devops::client::DevOpsClient;
+use crate::devops::models::{WiqlResponse, WorkItem, WorkItemComment, WorkItemType};
+use anyhow::Result;
+
+/// The work item operations commands need, so they can run against a fake
+/// store in unit tests instead of a mocked HTTP server
+pub trait WorkItemStore {
+    fn get_work_item(&self, id: u32) -> Result<WorkItem>;
+
+    fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<WorkItem>>;
+
+    /// Run a WIQL query; `time_precision` allows times (not just dates) in date comparisons
+    fn execute_wiql_with_precision(
+        &self,
+        query: &str,
+        time_precision: bool,
+    ) -> Result<WiqlResponse>;
+
+    fn execute_wiql(&self, query: &str) -> Result<WiqlResponse> {
+        self.execute_wiql_with_precision(query, false)
+    }
+
+    /// Apply JSON Patch operations, failing if the item is no longer at `expected_rev`
+    fn update_work_item_with_rev(
+        &self,
+        id: u32,
+        operations: Vec<serde_json::Value>,
+        expected_rev: Option<u32>,
+    ) -> Result<WorkItem>;
+
+    fn create_work_item(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<WorkItem>;
+
+    /// Create a work item, linking it to `parent_url` in the same request
+    fn create_work_item_with_parent(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+        parent_url: Option<&str>,
+    ) -> Result<WorkItem>;
+
+    fn get_work_item_type(&self, type_name: &str) -> Result<WorkItemType>;
+
+    fn list_work_item_types(&self) -> Result<Vec<String>>;
+
+    fn add_comment(&self, id: u32, text: &str) -> Result<WorkItemComment>;
+}
+
+impl WorkItemStore for DevOpsClient {
+    fn get_work_item(&self, id: u32) -> Result<WorkItem> {
+        DevOpsClient::get_work_item(self, id)
+    }
+
+    fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<WorkItem>> {
+        DevOpsClient::get_work_items_batch(self, ids)
+    }
+
+    fn execute_wiql_with_precision(
+        &self,
+        query: &str,
+        time_precision: bool,
+    ) -> Result<WiqlResponse> {
+        DevOpsClient::execute_wiql_with_precision(self, query, time_precision)
+    }
+
+    fn update_work_item_with_rev(
+        &self,
+        id: u32,
+        operations: Vec<serde_json::Value>,
+        expected_rev: Option<u32>,
+    ) -> Result<WorkItem> {
+        DevOpsClient::update_work_item_with_rev(self, id, operations, expected_rev)
+    }
+
+    fn create_work_item(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<WorkItem> {
+        DevOpsClient::create_work_item(self, fields)
+    }
+
+    fn create_work_item_with_parent(
+        &self,
+        fields: serde_json::Map<String, serde_json::Value>,
+        parent_url: Option<&str>,
+    ) -> Result<WorkItem> {
+        DevOpsClient::create_work_item_with_parent(self, fields, parent_url)
+    }
+
+    fn get_work_item_type(&self, type_name: &str) -> Result<WorkItemType> {
+        DevOpsClient::get_work_item_type(self, type_name)
+    }
+
+    fn list_work_item_types(&self) -> Result<Vec<String>> {
+        DevOpsClient::list_work_item_types(self)
+    }
+
+    fn add_comment(&self, id: u32, text: &str) -> Result<WorkItemComment> {
+        DevOpsClient::add_comment(self, id, text)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::WorkItemStore;
+    use crate::devops::models::{
+        WiqlResponse, WorkItem, WorkItemComment, WorkItemStateColor, WorkItemType,
+    };
+    use crate::error::ApiError;
+    use anyhow::{Context, Result};
+    use reqwest::StatusCode;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Work item id, patch operations and expected revision of one update
+    pub type Update = (u32, Vec<serde_json::Value>, Option<u32>);
+
+    /// Fields and parent URL of one created work item
+    pub type Create = (serde_json::Map<String, serde_json::Value>, Option<String>);
+
+    /// In-memory store recording the writes a command makes
+    #[derive(Default)]
+    pub struct MockStore {
+        pub items: HashMap<u32, WorkItem>,
+        /// Work item types the project defines
+        pub types: Vec<String>,
+        pub queries: RefCell<Vec<String>>,
+        pub updates: RefCell<Vec<Update>>,
+        pub creates: RefCell<Vec<Create>>,
+        pub comments: RefCell<Vec<(u32, String)>>,
+    }
+
+    impl MockStore {
+        pub fn with_task(id: u32, rev: u32, state: &str) -> Self {
+            let item: WorkItem = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "rev": rev,
+                "url": format!("https://dev.azure.com/org/_apis/wit/workItems/{}", id),
+                "fields": {
+                    "System.Title": "Fix login",
+                    "System.State": state,
+                    "System.WorkItemType": "Task"
+                }
+            }))
+            .unwrap();
+            Self {
+                items: HashMap::from([(id, item)]),
+                types: vec!["Task".to_string(), "Bug".to_string()],
+                ..Default::default()
+            }
+        }
+    }
+
+    impl WorkItemStore for MockStore {
+        fn get_work_item(&self, id: u32) -> Result<WorkItem> {
+            self.items
+                .get(&id)
+                .cloned()
+                .with_context(|| format!("Work item {} not found", id))
+        }
+
+        fn get_work_items_batch(&self, ids: &[u32]) -> Result<Vec<WorkItem>> {
+            ids.iter().map(|id| self.get_work_item(*id)).collect()
+        }
+
+        /// Every stored item matches, in id order
+        fn execute_wiql_with_precision(
+            &self,
+            query: &str,
+            _time_precision: bool,
+        ) -> Result<WiqlResponse> {
+            self.queries.borrow_mut().push(query.to_string());
+            let mut ids: Vec<u32> = self.items.keys().copied().collect();
+            ids.sort_unstable();
+            Ok(serde_json::from_value(serde_json::json!({
+                "queryType": "flat",
+                "workItems": ids
+                    .iter()
+                    .map(|id| serde_json::json!({ "id": id, "url": "" }))
+                    .collect::<Vec<_>>(),
+            }))?)
+        }
+
+        fn update_work_item_with_rev(
+            &self,
+            id: u32,
+            operations: Vec<serde_json::Value>,
+            expected_rev: Option<u32>,
+        ) -> Result<WorkItem> {
+            let item = self.get_work_item(id)?;
+            self.updates
+                .borrow_mut()
+                .push((id, operations, expected_rev));
+            Ok(item)
+        }
+
+        fn create_work_item(
+            &self,
+            fields: serde_json::Map<String, serde_json::Value>,
+        ) -> Result<WorkItem> {
+            self.create_work_item_with_parent(fields, None)
+        }
+
+        fn create_work_item_with_parent(
+            &self,
+            fields: serde_json::Map<String, serde_json::Value>,
+            parent_url: Option<&str>,
+        ) -> Result<WorkItem> {
+            let mut creates = self.creates.borrow_mut();
+            let id = 1000 + creates.len() as u32;
+            let item = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "rev": 1,
+                "fields": fields,
+            }))?;
+            creates.push((fields, parent_url.map(str::to_string)));
+            Ok(item)
+        }
+
+        /// Known types share a New/Active/Closed workflow; others are 404s
+        fn get_work_item_type(&self, type_name: &str) -> Result<WorkItemType> {
+            if !self.types.iter().any(|t| t.eq_ignore_ascii_case(type_name)) {
+                return Err(ApiError::from_status(
+                    StatusCode::NOT_FOUND,
+                    format!("Work item type '{}' not found", type_name),
+                )
+                .into());
+            }
+            let state = |name: &str| WorkItemStateColor {
+                name: name.to_string(),
+                color: "007acc".to_string(),
+                category: "InProgress".to_string(),
+            };
+            Ok(WorkItemType {
+                name: type_name.to_string(),
+                states: vec![state("New"), state("Active"), state("Closed")],
+            })
+        }
+
+        fn list_work_item_types(&self) -> Result<Vec<String>> {
+            Ok(self.types.clone())
+        }
+
+        fn add_comment(&self, id: u32, text: &str) -> Result<WorkItemComment> {
+            self.comments.borrow_mut().push((id, text.to_string()));
+            Ok(serde_json::from_value(
+                serde_json::json!({ "id": 1, "text": text }),
+            )?)
+        }
+    }
+}
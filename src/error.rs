@@ -1 +1,147 @@
+//! Typed API errors and reporting helpers for machine-readable output.
 
+use crate::pace::retry::TransientError;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+/// A failed call to a remote API, classified so callers can tell a missing
+/// work item from an expired PAT. Commands keep using `anyhow`; recover the
+/// kind with `err.downcast_ref::<ApiError>()`.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    /// 404: the resource doesn't exist or isn't visible to this PAT
+    #[error("{0}")]
+    NotFound(String),
+    /// 401/403: missing, expired or under-scoped credentials
+    #[error("{message}")]
+    Unauthorized { status: StatusCode, message: String },
+    /// 429: still throttled after retries
+    #[error("{0}")]
+    RateLimited(String),
+    /// 409/412, or a revision mismatch detected client-side (reported as 412)
+    #[error("{message}")]
+    Conflict { status: StatusCode, message: String },
+    /// The request never got a response
+    #[error("{0}")]
+    Network(String),
+    /// Any other non-success status
+    #[error("{message}")]
+    Http { status: StatusCode, message: String },
+}
+
+impl ApiError {
+    /// Classify a non-success HTTP status
+    pub fn from_status(status: StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status {
+            StatusCode::NOT_FOUND => Self::NotFound(message),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Self::Unauthorized { status, message }
+            }
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(message),
+            StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => {
+                Self::Conflict { status, message }
+            }
+            status => Self::Http { status, message },
+        }
+    }
+
+    /// HTTP status behind the error, if the server responded at all
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::NotFound(_) => Some(StatusCode::NOT_FOUND),
+            Self::RateLimited(_) => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Network(_) => None,
+            Self::Unauthorized { status, .. }
+            | Self::Conflict { status, .. }
+            | Self::Http { status, .. } => Some(*status),
+        }
+    }
+}
+
+/// A transient failure that exhausted its retries
+impl From<TransientError> for ApiError {
+    fn from(err: TransientError) -> Self {
+        match err.status {
+            Some(status) => Self::from_status(status, err.message),
+            None => Self::Network(err.message),
+        }
+    }
+}
+
+/// Render an error chain as `{"error": "...", "context": [...]}` for `--format json`.
+///
+/// `error` is the outermost message; `context` lists the underlying causes in order.
+pub fn error_json(err: &anyhow::Error) -> Value {
+    let context: Vec<String> = err.chain().skip(1).map(|cause| cause.to_string()).collect();
+    json!({
+        "error": err.to_string(),
+        "context": context,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_json_walks_chain() {
+        let err = Err::<(), _>(anyhow::anyhow!("connection refused"))
+            .context("Failed to fetch work item")
+            .unwrap_err();
+
+        let value = error_json(&err);
+
+        assert_eq!(value["error"], "Failed to fetch work item");
+        assert_eq!(value["context"], json!(["connection refused"]));
+    }
+
+    #[test]
+    fn test_api_error_from_status() {
+        assert!(matches!(
+            ApiError::from_status(StatusCode::NOT_FOUND, "gone"),
+            ApiError::NotFound(_)
+        ));
+        assert!(matches!(
+            ApiError::from_status(StatusCode::FORBIDDEN, "denied"),
+            ApiError::Unauthorized { .. }
+        ));
+        assert!(matches!(
+            ApiError::from_status(StatusCode::INTERNAL_SERVER_ERROR, "boom"),
+            ApiError::Http {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_api_error_keeps_actual_status() {
+        let forbidden = ApiError::from_status(StatusCode::FORBIDDEN, "denied");
+        assert_eq!(forbidden.status(), Some(StatusCode::FORBIDDEN));
+
+        let stale = ApiError::from_status(StatusCode::PRECONDITION_FAILED, "stale rev");
+        assert_eq!(stale.status(), Some(StatusCode::PRECONDITION_FAILED));
+
+        let unauthorized = ApiError::from_status(StatusCode::UNAUTHORIZED, "expired");
+        assert_eq!(unauthorized.status(), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_exhausted_transient_error_keeps_kind() {
+        let throttled = ApiError::from(TransientError {
+            message: "DevOps API error: status 429".to_string(),
+            retry_after: None,
+            status: Some(StatusCode::TOO_MANY_REQUESTS),
+        });
+        assert!(matches!(throttled, ApiError::RateLimited(_)));
+
+        let offline = ApiError::from(TransientError {
+            message: "network error: connection refused".to_string(),
+            retry_after: None,
+            status: None,
+        });
+        assert!(matches!(offline, ApiError::Network(_)));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::{Config, OFFLINE_ACCESS_SCOPE};
+use crate::ui;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use oauth2::{
@@ -5,13 +7,12 @@ use oauth2::{
     devicecode::StandardDeviceAuthorizationResponse,
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::time::Duration as TokioDuration;
 
-const MICROSOFT_AUTH_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0/authorize";
-const MICROSOFT_TOKEN_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0/token";
-const MICROSOFT_DEVICE_AUTH_URL: &str =
-    "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode";
+const MICROSOFT_LOGIN_URL: &str = "https://login.microsoftonline.com";
+/// Any work/school or personal account, when no tenant is configured
+const DEFAULT_TENANT: &str = "common";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenCache {
@@ -20,56 +21,228 @@ pub struct TokenCache {
     pub expires_at: DateTime<Utc>,
 }
 
+/// A device code login waiting for the user, kept on disk until it completes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingDeviceLogin {
+    /// Device authorization response, including the device code to poll with
+    pub details: StandardDeviceAuthorizationResponse,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingDeviceLogin {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .context("Failed to save pending login")
+    }
+
+    /// The saved login, or `None` if there isn't one
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path).context("Failed to read pending login")?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .context("Failed to parse pending login")
+    }
+}
+
 pub struct GraphAuthenticator {
     client_id: String,
+    /// Directory tenant (GUID or domain), or `common`/`organizations`/`consumers`
+    tenant_id: String,
+    /// Requested at login
+    scopes: Vec<String>,
+    /// Identity platform host; overridable for tests
+    login_url: String,
     token_cache_path: PathBuf,
+    use_keyring: bool,
+    /// Serializes refreshes so concurrent callers don't each burn the refresh token
+    refresh_lock: tokio::sync::Mutex<()>,
 }
 
 impl GraphAuthenticator {
-    pub fn new(client_id: String, token_cache_path: PathBuf) -> Self {
+    pub fn new(client_id: String, tenant_id: String, token_cache_path: PathBuf) -> Self {
         Self {
             client_id,
+            tenant_id,
+            scopes: crate::config::default_graph_scopes(),
+            login_url: MICROSOFT_LOGIN_URL.to_string(),
             token_cache_path,
+            use_keyring: true,
+            refresh_lock: tokio::sync::Mutex::new(()),
         }
     }
 
+    /// Build an authenticator from config using the standard token cache location
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let token_cache_path = home::home_dir()
+            .context("Could not find home directory")?
+            .join(".ao-no-out7ook")
+            .join("tokens.json");
+
+        Ok(Self::new(
+            config.graph.client_id.clone(),
+            config.graph.tenant_id.clone(),
+            token_cache_path,
+        )
+        .with_keyring(config.devops.use_keyring)
+        .with_scopes(config.graph.scopes.clone()))
+    }
+
+    /// Whether to store tokens in the system keyring (default: true)
+    pub fn with_keyring(mut self, use_keyring: bool) -> Self {
+        self.use_keyring = use_keyring;
+        self
+    }
+
+    /// Delegated permissions to request at login
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Use a different identity platform host (for testing)
+    pub fn with_login_url(mut self, url: &str) -> Self {
+        self.login_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Microsoft identity platform endpoint (`authorize`, `token`, `devicecode`)
+    /// for the configured tenant
+    fn endpoint_url(&self, endpoint: &str) -> Result<String> {
+        let tenant = match self.tenant_id.trim() {
+            "" => DEFAULT_TENANT,
+            tenant => tenant,
+        };
+        if !tenant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        {
+            anyhow::bail!(
+                "Invalid graph.tenant_id '{}'. Expected a tenant GUID, a domain or 'common'",
+                tenant
+            );
+        }
+        Ok(format!(
+            "{}/{}/oauth2/v2.0/{}",
+            self.login_url, tenant, endpoint
+        ))
+    }
+
+    /// Path of the legacy plaintext token cache
+    pub fn token_cache_path(&self) -> &PathBuf {
+        &self.token_cache_path
+    }
+
+    /// Whether tokens are cached in the keyring or the legacy file
+    pub fn has_cached_tokens(&self) -> bool {
+        (self.use_keyring && crate::keyring::get_graph_tokens().is_ok())
+            || self.token_cache_path.exists()
+    }
+
     /// Initiate OAuth2 device code flow - displays user code and verification URL
     pub async fn login(&self) -> Result<()> {
-        let client = BasicClient::new(
-            ClientId::new(self.client_id.clone()),
-            None,
-            AuthUrl::new(MICROSOFT_AUTH_URL.to_string())?,
-            Some(TokenUrl::new(MICROSOFT_TOKEN_URL.to_string())?),
-        )
-        .set_device_authorization_url(DeviceAuthorizationUrl::new(
-            MICROSOFT_DEVICE_AUTH_URL.to_string(),
-        )?);
+        let client = self.device_client()?;
+
+        if !self
+            .scopes
+            .iter()
+            .any(|s| s.trim().eq_ignore_ascii_case(OFFLINE_ACCESS_SCOPE))
+        {
+            anyhow::bail!(
+                "Graph scopes must include '{}' so tokens can be refreshed",
+                OFFLINE_ACCESS_SCOPE
+            );
+        }
 
         let details: StandardDeviceAuthorizationResponse = client
             .exchange_device_code()?
-            .add_scope(Scope::new("Calendars.ReadWrite".to_string()))
-            .add_scope(Scope::new("offline_access".to_string()))
+            .add_scopes(self.scopes.iter().map(|s| Scope::new(s.trim().to_string())))
             .request_async(oauth2::reqwest::async_http_client)
             .await
             .context("Failed to request device code")?;
 
-        println!("\n🔐 Microsoft Graph Authentication");
-        println!("════════════════════════════════════");
-        println!("1. Visit: {}", details.verification_uri().as_str());
-        println!("2. Enter code: {}", details.user_code().secret());
-        println!("════════════════════════════════════\n");
+        // Saved so `oauth login --resume` can finish if this process is interrupted
+        let pending = PendingDeviceLogin {
+            expires_at: Utc::now() + Duration::seconds(details.expires_in().as_secs() as i64),
+            details,
+        };
+        pending.save(&self.pending_login_path())?;
+
+        println!("\n{} Microsoft Graph Authentication", ui::Symbol::Lock);
+        println!("{}", ui::rule('═', 36));
+        println!("1. Visit: {}", pending.details.verification_uri().as_str());
+        println!("2. Enter code: {}", pending.details.user_code().secret());
+        println!("{}\n", ui::rule('═', 36));
         println!("Waiting for you to complete authentication...");
 
-        // Poll for token
+        self.complete_device_login(&client, &pending).await
+    }
+
+    /// Finish a device code login started by an interrupted `login`, without
+    /// showing a new code
+    pub async fn resume_login(&self) -> Result<()> {
+        let path = self.pending_login_path();
+        let pending = PendingDeviceLogin::load(&path)?
+            .context("No interrupted login to resume. Run 'task oauth login'")?;
+
+        if pending.is_expired(Utc::now()) {
+            std::fs::remove_file(&path).ok();
+            anyhow::bail!(
+                "The saved device code expired at {}. Run 'task oauth login' again",
+                pending.expires_at.to_rfc3339()
+            );
+        }
+
+        println!(
+            "Resuming login (code {} valid until {})...",
+            pending.details.user_code().secret(),
+            pending.expires_at.format("%H:%M:%S UTC")
+        );
+        self.complete_device_login(&self.device_client()?, &pending)
+            .await
+    }
+
+    fn device_client(&self) -> Result<BasicClient> {
+        Ok(BasicClient::new(
+            ClientId::new(self.client_id.clone()),
+            None,
+            AuthUrl::new(self.endpoint_url("authorize")?)?,
+            Some(TokenUrl::new(self.endpoint_url("token")?)?),
+        )
+        .set_device_authorization_url(DeviceAuthorizationUrl::new(
+            self.endpoint_url("devicecode")?,
+        )?))
+    }
+
+    /// Poll the token endpoint until the user finishes (the library backs off
+    /// on `slow_down`), then save tokens and drop the pending login file
+    async fn complete_device_login(
+        &self,
+        client: &BasicClient,
+        pending: &PendingDeviceLogin,
+    ) -> Result<()> {
+        let remaining = (pending.expires_at - Utc::now()).num_seconds().max(0) as u64;
         let token = client
-            .exchange_device_access_token(&details)
+            .exchange_device_access_token(&pending.details)
             .request_async(
                 oauth2::reqwest::async_http_client,
                 tokio::time::sleep,
-                Some(TokioDuration::from_secs(details.expires_in().as_secs())),
+                Some(TokioDuration::from_secs(remaining)),
             )
             .await
-            .context("Failed to exchange device code for token")?;
+            .context(
+                "Failed to exchange device code for token. \
+                 If you already entered the code, retry with 'task oauth login --resume'",
+            )?;
 
         // Save tokens
         let cache = TokenCache {
@@ -85,35 +258,70 @@ impl GraphAuthenticator {
         };
 
         self.save_token_cache(&cache)?;
-        println!("✓ Authentication successful! Tokens saved.");
+        let pending_path = self.pending_login_path();
+        if pending_path.exists() {
+            std::fs::remove_file(&pending_path).context("Failed to remove pending login file")?;
+        }
+        println!(
+            "{} Authentication successful! Tokens saved.",
+            ui::Symbol::Ok
+        );
 
         Ok(())
     }
 
+    /// Device code of a login in progress, next to the token cache
+    fn pending_login_path(&self) -> PathBuf {
+        self.token_cache_path.with_file_name("device_login.json")
+    }
+
     /// Get valid access token (refresh if expired)
     pub async fn get_access_token(&self) -> Result<String> {
-        let mut cache = self.load_token_cache()?;
+        let cache = self.load_token_cache()?;
 
         // Check if token is expired (with 5 min buffer)
-        if cache.expires_at < Utc::now() + Duration::minutes(5) {
-            if let Some(refresh_token) = &cache.refresh_token {
-                cache = self.refresh_access_token(refresh_token).await?;
-            } else {
-                anyhow::bail!(
-                    "Access token expired and no refresh token available. Run 'task oauth login'"
-                );
-            }
+        if !Self::is_expiring(&cache) {
+            return Ok(cache.access_token);
         }
 
-        Ok(cache.access_token)
+        let _guard = self.refresh_lock.lock().await;
+        // Another caller may have refreshed while we waited for the lock
+        let cache = self.load_token_cache()?;
+        if !Self::is_expiring(&cache) {
+            return Ok(cache.access_token);
+        }
+        Ok(self.refresh_cached(&cache).await?.access_token)
+    }
+
+    /// Refresh after the server rejected `stale_token` (401), unless someone else already did
+    pub async fn force_refresh(&self, stale_token: &str) -> Result<String> {
+        let _guard = self.refresh_lock.lock().await;
+        let cache = self.load_token_cache()?;
+        if cache.access_token != stale_token {
+            return Ok(cache.access_token);
+        }
+        Ok(self.refresh_cached(&cache).await?.access_token)
+    }
+
+    fn is_expiring(cache: &TokenCache) -> bool {
+        cache.expires_at < Utc::now() + Duration::minutes(5)
+    }
+
+    async fn refresh_cached(&self, cache: &TokenCache) -> Result<TokenCache> {
+        match &cache.refresh_token {
+            Some(refresh_token) => self.refresh_access_token(refresh_token).await,
+            None => anyhow::bail!(
+                "Access token expired and no refresh token available. Run 'task oauth login'"
+            ),
+        }
     }
 
     async fn refresh_access_token(&self, refresh_token: &str) -> Result<TokenCache> {
         let client = BasicClient::new(
             ClientId::new(self.client_id.clone()),
             None,
-            AuthUrl::new(MICROSOFT_AUTH_URL.to_string())?,
-            Some(TokenUrl::new(MICROSOFT_TOKEN_URL.to_string())?),
+            AuthUrl::new(self.endpoint_url("authorize")?)?,
+            Some(TokenUrl::new(self.endpoint_url("token")?)?),
         );
 
         let token = client
@@ -142,16 +350,40 @@ impl GraphAuthenticator {
     }
 
     fn load_token_cache(&self) -> Result<TokenCache> {
+        // Prefer keyring
+        if self.use_keyring
+            && let Ok(cache) = crate::keyring::get_graph_tokens()
+        {
+            return Ok(cache);
+        }
+
+        // Fall back to legacy plaintext file
         let content = std::fs::read_to_string(&self.token_cache_path).context(format!(
             "Failed to read token cache. Run 'task oauth login' first. Path: {:?}",
             self.token_cache_path
         ))?;
 
         let cache: TokenCache = serde_json::from_str(&content)?;
+
+        // Migrate plaintext tokens to keyring, keeping the file if the keyring can't hold them
+        if self.use_keyring && self.store_in_keyring(&cache) {
+            std::fs::remove_file(&self.token_cache_path)
+                .context("Failed to remove migrated token cache file")?;
+            eprintln!("Migrated Graph tokens to secure storage.");
+        }
+
         Ok(cache)
     }
 
     fn save_token_cache(&self, cache: &TokenCache) -> Result<()> {
+        if self.use_keyring && self.store_in_keyring(cache) {
+            // Don't leave a stale plaintext copy behind
+            if self.token_cache_path.exists() {
+                std::fs::remove_file(&self.token_cache_path)?;
+            }
+            return Ok(());
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = self.token_cache_path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -161,6 +393,14 @@ impl GraphAuthenticator {
         std::fs::write(&self.token_cache_path, content)?;
         Ok(())
     }
+
+    /// Store tokens in keyring and confirm they can be read back
+    fn store_in_keyring(&self, cache: &TokenCache) -> bool {
+        crate::keyring::store_graph_tokens(cache).is_ok()
+            && crate::keyring::get_graph_tokens()
+                .map(|stored| stored.access_token == cache.access_token)
+                .unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
@@ -179,11 +419,87 @@ mod tests {
             expires_at: Utc::now() + Duration::hours(1),
         };
 
-        let auth = GraphAuthenticator::new("test_client".to_string(), cache_path.clone());
+        let auth = GraphAuthenticator::new(
+            "test_client".to_string(),
+            "common".to_string(),
+            cache_path.clone(),
+        )
+        .with_keyring(false);
         auth.save_token_cache(&cache).unwrap();
 
         let loaded = auth.load_token_cache().unwrap();
         assert_eq!(loaded.access_token, "test_access");
         assert_eq!(loaded.refresh_token, Some("test_refresh".to_string()));
     }
+
+    #[test]
+    fn test_pending_login_round_trip() {
+        let dir = tempdir().unwrap();
+        let auth = GraphAuthenticator::new(
+            "test_client".to_string(),
+            "common".to_string(),
+            dir.path().join("tokens.json"),
+        )
+        .with_keyring(false);
+        let path = auth.pending_login_path();
+        assert!(PendingDeviceLogin::load(&path).unwrap().is_none());
+
+        let details: StandardDeviceAuthorizationResponse =
+            serde_json::from_value(serde_json::json!({
+                "device_code": "device-123",
+                "user_code": "ABCD-EFGH",
+                "verification_uri": "https://microsoft.com/devicelogin",
+                "expires_in": 900,
+                "interval": 5
+            }))
+            .unwrap();
+        let expires_at = Utc::now() + Duration::seconds(900);
+        PendingDeviceLogin {
+            details,
+            expires_at,
+        }
+        .save(&path)
+        .unwrap();
+
+        let loaded = PendingDeviceLogin::load(&path).unwrap().unwrap();
+        assert_eq!(loaded.details.device_code().secret(), "device-123");
+        assert_eq!(loaded.details.user_code().secret(), "ABCD-EFGH");
+        assert_eq!(loaded.expires_at, expires_at);
+        assert!(!loaded.is_expired(Utc::now()));
+        assert!(loaded.is_expired(expires_at));
+    }
+
+    #[test]
+    fn test_endpoint_urls_use_tenant() {
+        let auth = GraphAuthenticator::new(
+            "test_client".to_string(),
+            "contoso.onmicrosoft.com".to_string(),
+            PathBuf::from("tokens.json"),
+        );
+        assert_eq!(
+            auth.endpoint_url("token").unwrap(),
+            "https://login.microsoftonline.com/contoso.onmicrosoft.com/oauth2/v2.0/token"
+        );
+        assert_eq!(
+            auth.endpoint_url("devicecode").unwrap(),
+            "https://login.microsoftonline.com/contoso.onmicrosoft.com/oauth2/v2.0/devicecode"
+        );
+
+        let auth = GraphAuthenticator::new(
+            "test_client".to_string(),
+            String::new(),
+            PathBuf::from("tokens.json"),
+        );
+        assert_eq!(
+            auth.endpoint_url("authorize").unwrap(),
+            "https://login.microsoftonline.com/common/oauth2/v2.0/authorize"
+        );
+
+        let auth = GraphAuthenticator::new(
+            "test_client".to_string(),
+            "evil.com/x?".to_string(),
+            PathBuf::from("tokens.json"),
+        );
+        assert!(auth.endpoint_url("token").is_err());
+    }
 }
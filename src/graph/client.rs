@@ -1,13 +1,20 @@
 use crate::graph::auth::GraphAuthenticator;
-use crate::graph::models::{CalendarEvent, EventsResponse};
+use crate::graph::models::{CalendarEvent, EventsResponse, GraphUser, WORK_ITEM_ID_PROPERTY};
+use crate::pace::retry::{backoff_delay, retry_after};
+use crate::ui;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use std::sync::Arc;
 
+const GRAPH_BASE_URL: &str = "https://graph.microsoft.com/v1.0";
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
 pub struct GraphClient {
     client: Client,
     auth: Arc<GraphAuthenticator>,
+    base_url: String,
+    max_retries: u32,
 }
 
 impl GraphClient {
@@ -15,59 +22,142 @@ impl GraphClient {
         Self {
             client: Client::new(),
             auth: Arc::new(auth),
+            base_url: GRAPH_BASE_URL.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
-    async fn auth_header(&self) -> Result<String> {
-        let token = self.auth.get_access_token().await?;
-        Ok(format!("Bearer {}", token))
+    /// Override the Graph API root (e.g. for tests)
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Maximum retries for throttled (429) or unavailable (503) responses
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Send a request built with the given Authorization header.
+    ///
+    /// 429/503 and network errors back off and retry (honoring Retry-After); a 401
+    /// forces one token refresh, in case the cached token was revoked or rotated.
+    async fn send<F>(&self, build: F) -> Result<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let mut token = self.auth.get_access_token().await?;
+        let mut refreshed = false;
+        let mut attempt = 0;
+
+        loop {
+            let result = build(&format!("Bearer {}", token)).send().await;
+            let delay = match &result {
+                Ok(response) if response.status() == StatusCode::UNAUTHORIZED && !refreshed => {
+                    refreshed = true;
+                    token = self.auth.force_refresh(&token).await?;
+                    continue;
+                }
+                Ok(response)
+                    if matches!(
+                        response.status(),
+                        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+                    ) =>
+                {
+                    retry_after(response.headers()).unwrap_or_else(|| backoff_delay(attempt))
+                }
+                Err(e) if !e.is_builder() => backoff_delay(attempt),
+                _ => return Ok(result?),
+            };
+
+            if attempt >= self.max_retries {
+                return Ok(result?);
+            }
+            eprintln!(
+                "{} Graph API call failed (attempt {}/{}). Retrying in {}ms...",
+                ui::Symbol::Warn,
+                attempt + 1,
+                self.max_retries,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
     }
 
-    /// FR3.1: List calendar events in time range
+    /// FR3.1: List calendar events in time range, following `@odata.nextLink` pages
     pub async fn list_events(
         &self,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     ) -> Result<Vec<CalendarEvent>> {
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/calendar/events?\
+        self.list_events_limited(start, end, None).await
+    }
+
+    /// Like [`Self::list_events`], but stops paging once `limit` events are collected;
+    /// events come in start order, so these are the earliest
+    pub async fn list_events_limited(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> Result<Vec<CalendarEvent>> {
+        let mut url = format!(
+            "{}/me/calendar/events?\
              $filter=start/dateTime ge '{}' and end/dateTime le '{}'&\
-             $select=id,subject,start,end,categories,singleValueExtendedProperties",
+             $select=id,subject,start,end,categories,isAllDay&\
+             $orderby=start/dateTime&\
+             $expand=singleValueExtendedProperties($filter=id eq '{}')",
+            self.base_url,
             start.to_rfc3339(),
-            end.to_rfc3339()
+            end.to_rfc3339(),
+            WORK_ITEM_ID_PROPERTY
         );
+        let mut events = Vec::new();
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.auth_header().await?)
-            .send()
-            .await
-            .context("Failed to list calendar events")?;
+        loop {
+            let response = self
+                .send(|auth| self.client.get(&url).header("Authorization", auth))
+                .await
+                .context("Failed to list calendar events")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Graph API error: status {}", response.status());
-        }
+            if !response.status().is_success() {
+                anyhow::bail!("Graph API error: status {}", response.status());
+            }
 
-        let events_response: EventsResponse = response
-            .json()
-            .await
-            .context("Failed to parse events response")?;
+            let page: EventsResponse = response
+                .json()
+                .await
+                .context("Failed to parse events response")?;
+            events.extend(page.value);
+
+            if let Some(limit) = limit
+                && events.len() >= limit
+            {
+                events.truncate(limit);
+                return Ok(events);
+            }
 
-        Ok(events_response.value)
+            match page.next_link {
+                Some(next) => url = next,
+                None => return Ok(events),
+            }
+        }
     }
 
     /// FR3.2: Create calendar event (Focus Block)
     pub async fn create_event(&self, event: CalendarEvent) -> Result<CalendarEvent> {
-        let url = "https://graph.microsoft.com/v1.0/me/calendar/events";
+        let url = format!("{}/me/calendar/events", self.base_url);
 
         let response = self
-            .client
-            .post(url)
-            .header("Authorization", self.auth_header().await?)
-            .header("Content-Type", "application/json")
-            .json(&event)
-            .send()
+            .send(|auth| {
+                self.client
+                    .post(&url)
+                    .header("Authorization", auth)
+                    .header("Content-Type", "application/json")
+                    .json(&event)
+            })
             .await
             .context("Failed to create calendar event")?;
 
@@ -89,21 +179,62 @@ impl GraphClient {
         Ok(created)
     }
 
+    /// Fetch a single calendar event; a missing event is an error
+    pub async fn get_event(&self, event_id: &str) -> Result<CalendarEvent> {
+        let url = format!("{}/me/events/{}", self.base_url, event_id);
+
+        let response = self
+            .send(|auth| self.client.get(&url).header("Authorization", auth))
+            .await
+            .context("Failed to fetch calendar event")?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            anyhow::bail!("Calendar event '{}' not found", event_id);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Graph API get event error: status {}", response.status());
+        }
+
+        let event: CalendarEvent = response
+            .json()
+            .await
+            .context("Failed to parse calendar event")?;
+
+        Ok(event)
+    }
+
+    /// Whether an event is still on the calendar; only a 404 counts as gone
+    pub async fn event_exists(&self, event_id: &str) -> Result<bool> {
+        let url = format!("{}/me/events/{}?$select=id", self.base_url, event_id);
+
+        let response = self
+            .send(|auth| self.client.get(&url).header("Authorization", auth))
+            .await
+            .context("Failed to fetch calendar event")?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => anyhow::bail!("Graph API get event error: status {}", status),
+        }
+    }
+
     /// FR3.4: Update calendar event
     pub async fn update_event(
         &self,
         event_id: &str,
         event: CalendarEvent,
     ) -> Result<CalendarEvent> {
-        let url = format!("https://graph.microsoft.com/v1.0/me/events/{}", event_id);
+        let url = format!("{}/me/events/{}", self.base_url, event_id);
 
         let response = self
-            .client
-            .patch(&url)
-            .header("Authorization", self.auth_header().await?)
-            .header("Content-Type", "application/json")
-            .json(&event)
-            .send()
+            .send(|auth| {
+                self.client
+                    .patch(&url)
+                    .header("Authorization", auth)
+                    .header("Content-Type", "application/json")
+                    .json(&event)
+            })
             .await
             .context("Failed to update calendar event")?;
 
@@ -121,13 +252,10 @@ impl GraphClient {
 
     /// Delete calendar event
     pub async fn delete_event(&self, event_id: &str) -> Result<()> {
-        let url = format!("https://graph.microsoft.com/v1.0/me/events/{}", event_id);
+        let url = format!("{}/me/events/{}", self.base_url, event_id);
 
         let response = self
-            .client
-            .delete(&url)
-            .header("Authorization", self.auth_header().await?)
-            .send()
+            .send(|auth| self.client.delete(&url).header("Authorization", auth))
             .await
             .context("Failed to delete calendar event")?;
 
@@ -137,4 +265,26 @@ impl GraphClient {
 
         Ok(())
     }
+
+    /// Profile of the signed-in user
+    pub async fn get_me(&self) -> Result<GraphUser> {
+        let url = format!(
+            "{}/me?$select=displayName,mail,userPrincipalName",
+            self.base_url
+        );
+
+        let response = self
+            .send(|auth| self.client.get(&url).header("Authorization", auth))
+            .await
+            .context("Failed to fetch signed-in user")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Graph API /me error: status {}", response.status());
+        }
+
+        response
+            .json::<GraphUser>()
+            .await
+            .context("Failed to parse Graph user")
+    }
 }
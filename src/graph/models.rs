@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 
 /// Microsoft Graph calendar event
@@ -6,18 +6,32 @@ use serde::{Deserialize, Serialize};
 pub struct CalendarEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    // Empty subject/categories are omitted so partial PATCH updates leave them untouched
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub subject: String,
     pub start: DateTimeTimeZone,
     pub end: DateTimeTimeZone,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<ItemBody>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<String>,
     #[serde(
         rename = "singleValueExtendedProperties",
         skip_serializing_if = "Option::is_none"
     )]
     pub extended_properties: Option<Vec<ExtendedProperty>>,
+    /// All-day events start and end at midnight in the event's time zone
+    #[serde(
+        rename = "isAllDay",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub is_all_day: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<PatternedRecurrence>,
+    /// Free/busy status ("busy", "free", "tentative", ...)
+    #[serde(rename = "showAs", default, skip_serializing_if = "Option::is_none")]
+    pub show_as: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +58,102 @@ pub struct ItemBody {
     pub content: String,
 }
 
+/// Graph `patternedRecurrence`: how often an event repeats and for how long
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternedRecurrence {
+    pub pattern: RecurrencePattern,
+    pub range: RecurrenceRange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurrencePattern {
+    /// "daily", "weekly", ...
+    #[serde(rename = "type")]
+    pub pattern_type: String,
+    pub interval: u32,
+    /// Lowercase day names, required for weekly patterns
+    #[serde(rename = "daysOfWeek", default, skip_serializing_if = "Vec::is_empty")]
+    pub days_of_week: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurrenceRange {
+    /// "numbered", "endDate" or "noEnd"
+    #[serde(rename = "type")]
+    pub range_type: String,
+    /// First occurrence (YYYY-MM-DD)
+    #[serde(rename = "startDate")]
+    pub start_date: String,
+    #[serde(rename = "endDate", skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(
+        rename = "numberOfOccurrences",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub number_of_occurrences: Option<u32>,
+}
+
+/// When a recurring event stops repeating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceEnd {
+    Never,
+    /// After this many occurrences
+    Count(u32),
+    /// After the last occurrence on or before this date
+    Until(NaiveDate),
+}
+
+impl PatternedRecurrence {
+    /// Repeat every day from `start`
+    pub fn daily(start: NaiveDate, end: RecurrenceEnd) -> Self {
+        Self::new("daily", Vec::new(), start, end)
+    }
+
+    /// Repeat every week on `start`'s weekday
+    pub fn weekly(start: NaiveDate, end: RecurrenceEnd) -> Self {
+        Self::new("weekly", vec![weekday_name(start.weekday())], start, end)
+    }
+
+    fn new(
+        pattern_type: &str,
+        days_of_week: Vec<String>,
+        start: NaiveDate,
+        end: RecurrenceEnd,
+    ) -> Self {
+        let (range_type, end_date, number_of_occurrences) = match end {
+            RecurrenceEnd::Never => ("noEnd", None, None),
+            RecurrenceEnd::Count(n) => ("numbered", None, Some(n)),
+            RecurrenceEnd::Until(date) => ("endDate", Some(date.to_string()), None),
+        };
+        Self {
+            pattern: RecurrencePattern {
+                pattern_type: pattern_type.to_string(),
+                interval: 1,
+                days_of_week,
+            },
+            range: RecurrenceRange {
+                range_type: range_type.to_string(),
+                start_date: start.to_string(),
+                end_date,
+                number_of_occurrences,
+            },
+        }
+    }
+}
+
+fn weekday_name(day: Weekday) -> String {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+    .to_string()
+}
+
 /// Extended property for storing work_item_id in calendar event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtendedProperty {
@@ -51,10 +161,66 @@ pub struct ExtendedProperty {
     pub value: String,
 }
 
+/// Single-value extended property id used to tag Focus Blocks with their work item
+pub const WORK_ITEM_ID_PROPERTY: &str =
+    "String {66f5a359-4659-4830-9070-00047ec6ac6e} Name WorkItemId";
+
+impl ExtendedProperty {
+    pub fn work_item_id(id: u32) -> Self {
+        Self {
+            id: WORK_ITEM_ID_PROPERTY.to_string(),
+            value: id.to_string(),
+        }
+    }
+}
+
+impl CalendarEvent {
+    /// Work item id stored in the event's extended properties, if any
+    pub fn work_item_id(&self) -> Option<u32> {
+        self.extended_properties
+            .as_ref()?
+            .iter()
+            .find(|p| p.id.eq_ignore_ascii_case(WORK_ITEM_ID_PROPERTY))
+            .and_then(|p| p.value.parse().ok())
+    }
+
+    /// Days covered by an all-day event, end exclusive. Graph may send these
+    /// as bare dates, so only the date part of `dateTime` is read.
+    pub fn all_day_dates(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let date_only = |dt: &DateTimeTimeZone| dt.date_time.len() == 10;
+        if !self.is_all_day && !date_only(&self.start) && !date_only(&self.end) {
+            return None;
+        }
+        let date = |dt: &DateTimeTimeZone| {
+            dt.date_time
+                .get(..10)
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        };
+        let start = date(&self.start)?;
+        // A zero-length all-day event still takes its day
+        let end = date(&self.end)?.max(start.succ_opt()?);
+        Some((start, end))
+    }
+}
+
 /// Response from Graph API list events
 #[derive(Debug, Deserialize)]
 pub struct EventsResponse {
     pub value: Vec<CalendarEvent>,
+    /// Link to the next page; absent on the last one
+    #[serde(rename = "@odata.nextLink", default)]
+    pub next_link: Option<String>,
+}
+
+/// Signed-in user returned by Graph `/me`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphUser {
+    #[serde(rename = "displayName", default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub mail: Option<String>,
+    #[serde(rename = "userPrincipalName", default)]
+    pub user_principal_name: Option<String>,
 }
 
 #[cfg(test)]
@@ -98,10 +264,111 @@ mod tests {
             body: None,
             categories: vec![],
             extended_properties: None,
+            is_all_day: false,
+            recurrence: None,
+            show_as: None,
         };
 
         let json = serde_json::to_value(&event).unwrap();
         assert_eq!(json["subject"], "Test Event");
         assert!(json.get("id").is_none()); // Should be skipped
+        assert!(json.get("showAs").is_none());
+    }
+
+    #[test]
+    fn test_serialize_weekly_recurrence_with_count() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 8).unwrap(); // Thursday
+        let event = CalendarEvent {
+            id: None,
+            subject: "Deep Work".to_string(),
+            start: DateTimeTimeZone {
+                date_time: "2026-01-08T09:00:00".to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            end: DateTimeTimeZone {
+                date_time: "2026-01-08T11:00:00".to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            body: None,
+            categories: vec![],
+            extended_properties: None,
+            is_all_day: false,
+            recurrence: Some(PatternedRecurrence::weekly(start, RecurrenceEnd::Count(3))),
+            show_as: None,
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json["recurrence"],
+            serde_json::json!({
+                "pattern": {
+                    "type": "weekly",
+                    "interval": 1,
+                    "daysOfWeek": ["thursday"]
+                },
+                "range": {
+                    "type": "numbered",
+                    "startDate": "2026-01-08",
+                    "numberOfOccurrences": 3
+                }
+            })
+        );
+        assert!(json.get("isAllDay").is_none());
+    }
+
+    #[test]
+    fn test_serialize_daily_recurrence_until_date() {
+        let recurrence = PatternedRecurrence::daily(
+            NaiveDate::from_ymd_opt(2026, 1, 8).unwrap(),
+            RecurrenceEnd::Until(NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()),
+        );
+
+        let json = serde_json::to_value(&recurrence).unwrap();
+        assert_eq!(json["pattern"]["type"], "daily");
+        assert!(json["pattern"].get("daysOfWeek").is_none());
+        assert_eq!(json["range"]["type"], "endDate");
+        assert_eq!(json["range"]["endDate"], "2026-01-31");
+    }
+
+    #[test]
+    fn test_serialize_work_item_extended_property() {
+        let event = CalendarEvent {
+            id: None,
+            subject: "Focus".to_string(),
+            start: DateTimeTimeZone {
+                date_time: "2026-01-08T09:00:00".to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            end: DateTimeTimeZone {
+                date_time: "2026-01-08T10:00:00".to_string(),
+                time_zone: "UTC".to_string(),
+            },
+            body: None,
+            categories: vec![],
+            extended_properties: Some(vec![ExtendedProperty::work_item_id(123)]),
+            is_all_day: false,
+            recurrence: None,
+            show_as: None,
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        let props = json["singleValueExtendedProperties"].as_array().unwrap();
+        assert_eq!(props.len(), 1);
+        assert_eq!(props[0]["id"], WORK_ITEM_ID_PROPERTY);
+        assert_eq!(props[0]["value"], "123");
+        assert_eq!(event.work_item_id(), Some(123));
+    }
+
+    #[test]
+    fn test_work_item_id_missing() {
+        let json = serde_json::json!({
+            "id": "evt-1",
+            "subject": "Standup",
+            "start": { "dateTime": "2026-01-08T09:00:00", "timeZone": "UTC" },
+            "end": { "dateTime": "2026-01-08T09:15:00", "timeZone": "UTC" }
+        });
+
+        let event: CalendarEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(event.work_item_id(), None);
     }
 }
@@ -1,8 +1,11 @@
-use crate::config::WorkHoursConfig;
+use crate::config::{FocusBlocksConfig, WorkHoursConfig};
 use crate::graph::models::{CalendarEvent, DateTimeTimeZone};
 use anyhow::{Context, Result};
 #[allow(unused_imports)] // Datelike used in tests
-use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveTime, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
 
 /// Round to next 15-minute interval (:00, :15, :30, :45)
 pub fn round_to_next_interval(time: DateTime<Utc>) -> DateTime<Utc> {
@@ -36,7 +39,7 @@ pub fn round_to_next_interval(time: DateTime<Utc>) -> DateTime<Utc> {
 }
 
 /// Parse DateTime from event's DateTimeTimeZone
-fn parse_event_time(dt: &DateTimeTimeZone) -> Result<DateTime<Utc>> {
+pub(crate) fn parse_event_time(dt: &DateTimeTimeZone) -> Result<DateTime<Utc>> {
     let datetime_str = &dt.date_time;
 
     // Try parsing as RFC3339 first
@@ -56,22 +59,90 @@ fn parse_event_time(dt: &DateTimeTimeZone) -> Result<DateTime<Utc>> {
     anyhow::bail!("Failed to parse datetime: {}", datetime_str)
 }
 
+/// Start and end of an event in UTC. All-day events run from midnight to
+/// midnight in their own time zone (UTC if it isn't an IANA name).
+fn event_span(event: &CalendarEvent) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    if let Some((first, last)) = event.all_day_dates() {
+        let tz: Tz = event.start.time_zone.parse().unwrap_or(Tz::UTC);
+        return Some((
+            local_to_utc(&tz, first, NaiveTime::MIN),
+            local_to_utc(&tz, last, NaiveTime::MIN),
+        ));
+    }
+    Some((
+        parse_event_time(&event.start).ok()?,
+        parse_event_time(&event.end).ok()?,
+    ))
+}
+
+/// Round `time` up to the next `interval_mins` boundary within its hour
+/// (e.g. :00, :25, :50 for 25), or the top of the next hour. Never earlier
+/// than `time`; a time already on a boundary is returned as-is.
+pub fn round_up_to_interval(time: DateTime<Utc>, interval_mins: u32) -> DateTime<Utc> {
+    let hour = time
+        .with_minute(0)
+        .unwrap()
+        .with_second(0)
+        .unwrap()
+        .with_nanosecond(0)
+        .unwrap();
+    let step = Duration::minutes(interval_mins.clamp(1, 60) as i64);
+    let mut slot = hour;
+    while slot < time {
+        slot += step;
+    }
+    slot.min(hour + Duration::hours(1))
+}
+
+/// Start of a block that immediately follows `event`: its end, rounded up to
+/// the next `interval_mins` boundary unless it already falls on one
+pub fn start_after(event: &CalendarEvent, interval_mins: u32) -> Result<DateTime<Utc>> {
+    let (_, end) = event_span(event).with_context(|| {
+        format!(
+            "Event '{}' has an unparseable end time: {}",
+            event.subject, event.end.date_time
+        )
+    })?;
+    Ok(round_up_to_interval(end, interval_mins))
+}
+
+/// Events that overlap `[start, end)`; touching at an edge is not a conflict
+pub fn overlapping_events(
+    events: &[CalendarEvent],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<&CalendarEvent> {
+    events
+        .iter()
+        .filter(|e| {
+            event_span(e)
+                .is_some_and(|(event_start, event_end)| event_start < end && event_end > start)
+        })
+        .collect()
+}
+
 /// Find gaps between events (free time slots)
+///
+/// Each event is padded by `buffer_mins` on both sides, so gaps adjacent to a
+/// meeting shrink by the buffer while the search window edges are left as-is.
 pub fn find_gaps(
     events: &[CalendarEvent],
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
+    buffer_mins: u32,
 ) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
     if events.is_empty() {
         return Ok(vec![(start_time, end_time)]);
     }
 
+    let buffer = Duration::minutes(buffer_mins as i64);
+
     // Parse and sort events by start time, filtering out events that end before our search window
     let mut sorted_events: Vec<(DateTime<Utc>, DateTime<Utc>)> = events
         .iter()
         .filter_map(|e| {
-            let event_start = parse_event_time(&e.start).ok()?;
-            let event_end = parse_event_time(&e.end).ok()?;
+            let (event_start, event_end) = event_span(e)?;
+            let (event_start, event_end) = (event_start - buffer, event_end + buffer);
 
             // Skip events that end before or at our start_time (they're in the past)
             if event_end <= start_time {
@@ -109,48 +180,130 @@ pub fn find_gaps(
     Ok(gaps)
 }
 
+/// Resolve a local wall-clock time in `tz` to UTC
+///
+/// Ambiguous times (clocks falling back) take the earlier instant; times skipped
+/// by a DST jump move forward to the first valid local time.
+pub(crate) fn local_to_utc(tz: &Tz, day: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    let mut local = day.and_time(time);
+    // DST gaps are at most a couple of hours; step forward until we land on a real time
+    for _ in 0..(4 * 4) {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => {
+                return dt.with_timezone(&Utc);
+            }
+            LocalResult::None => local += Duration::minutes(15),
+        }
+    }
+    Utc.from_utc_datetime(&day.and_time(time))
+}
+
+/// Limit on Focus Blocks per day, recognised by their Outlook category
+#[derive(Debug, Clone, Copy)]
+pub struct DailyCap<'a> {
+    pub max: u32,
+    /// Focus Block category; when empty, events linked to a work item count instead
+    pub category: &'a str,
+}
+
+impl<'a> DailyCap<'a> {
+    /// The cap configured in `focus_blocks`, if any
+    pub fn from_config(focus_blocks: &'a FocusBlocksConfig) -> Option<Self> {
+        focus_blocks.max_per_day.map(|max| Self {
+            max,
+            category: focus_blocks.category.trim(),
+        })
+    }
+
+    fn counts(&self, event: &CalendarEvent) -> bool {
+        if self.category.is_empty() {
+            event.work_item_id().is_some()
+        } else {
+            event
+                .categories
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(self.category))
+        }
+    }
+}
+
 /// FR3.7: Find next available slot for Focus Block
+///
+/// Work hours are interpreted in `work_hours.timezone`; the returned slot is in UTC.
 pub fn find_next_slot(
     events: &[CalendarEvent],
     now: DateTime<Utc>,
     duration_mins: u32,
+    buffer_mins: u32,
     work_hours: &WorkHoursConfig,
+) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    find_next_slot_capped(events, now, duration_mins, buffer_mins, work_hours, None)
+}
+
+/// Like [`find_next_slot`], skipping days that already hold `cap.max` Focus Blocks
+pub fn find_next_slot_capped(
+    events: &[CalendarEvent],
+    now: DateTime<Utc>,
+    duration_mins: u32,
+    buffer_mins: u32,
+    work_hours: &WorkHoursConfig,
+    cap: Option<DailyCap>,
 ) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
     // Parse work hours
     let work_start = NaiveTime::parse_from_str(&work_hours.start, "%H:%M")
         .context("Invalid work hours start time format")?;
     let work_end = NaiveTime::parse_from_str(&work_hours.end, "%H:%M")
         .context("Invalid work hours end time format")?;
+    let tz = work_hours.tz()?;
 
     // Round current time to next interval
     let search_start = round_to_next_interval(now);
 
-    // Try today first
-    let mut search_day = search_start.date_naive();
+    // Try today first (today in the user's timezone, not UTC)
+    let today = now.with_timezone(&tz).date_naive();
+    let mut search_day = search_start.with_timezone(&tz).date_naive();
 
-    // Try up to 7 days in the future
-    for _ in 0..7 {
-        let day_start = search_day
-            .and_time(work_start)
-            .and_local_timezone(Utc)
-            .single()
-            .unwrap_or_else(|| Utc.from_utc_datetime(&search_day.and_time(work_start)));
+    // Try up to 7 working days in the future; weekends/holidays don't count
+    let mut working_days = 0;
+    while working_days < 7 {
+        if !work_hours.is_working_day(search_day) {
+            search_day = search_day.succ_opt().context("Date overflow")?;
+            continue;
+        }
+        working_days += 1;
+
+        // An all-day event (leave, holiday) blocks the whole day wherever its
+        // midnight falls relative to the work hours time zone
+        let blocked = events.iter().any(|e| {
+            e.all_day_dates()
+                .is_some_and(|(first, last)| first <= search_day && search_day < last)
+        });
+        let full = cap.is_some_and(|cap| {
+            let scheduled = events
+                .iter()
+                .filter(|e| cap.counts(e))
+                .filter_map(event_span)
+                .filter(|(start, _)| start.with_timezone(&tz).date_naive() == search_day)
+                .count();
+            scheduled >= cap.max as usize
+        });
+        if blocked || full {
+            search_day = search_day.succ_opt().context("Date overflow")?;
+            continue;
+        }
 
-        let day_end = search_day
-            .and_time(work_end)
-            .and_local_timezone(Utc)
-            .single()
-            .unwrap_or_else(|| Utc.from_utc_datetime(&search_day.and_time(work_end)));
+        let day_start = local_to_utc(&tz, search_day, work_start);
+        let day_end = local_to_utc(&tz, search_day, work_end);
 
         // For today, start from current time (rounded)
-        let actual_start = if search_day == now.date_naive() {
+        let actual_start = if search_day == today {
             search_start.max(day_start)
         } else {
             day_start
         };
 
         // Find gaps in this day
-        let gaps = find_gaps(events, actual_start, day_end)?;
+        let gaps = find_gaps(events, actual_start, day_end, buffer_mins)?;
 
         // Find first gap that fits duration
         for (gap_start, gap_end) in gaps {
@@ -176,19 +329,19 @@ pub fn find_next_slot(
         search_day = search_day.succ_opt().context("Date overflow")?;
     }
 
-    anyhow::bail!("Could not find available slot in next 7 days")
+    anyhow::bail!("Could not find available slot in next 7 working days")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
 
     fn default_work_hours() -> WorkHoursConfig {
         WorkHoursConfig {
             start: "08:30".to_string(),
             end: "17:00".to_string(),
             timezone: "UTC".to_string(),
+            ..Default::default()
         }
     }
 
@@ -227,9 +380,64 @@ mod tests {
             body: None,
             categories: vec![],
             extended_properties: None,
+            is_all_day: false,
+            recurrence: None,
+            show_as: None,
         }
     }
 
+    #[test]
+    fn test_start_after_keeps_aligned_end() {
+        let event = mock_event_utc(2026, 1, 8, 9, 0, 10, 0);
+        let start = start_after(&event, 15).unwrap();
+        assert_eq!(start.hour(), 10);
+        assert_eq!(start.minute(), 0);
+    }
+
+    #[test]
+    fn test_start_after_rounds_up_to_configured_interval() {
+        let mut anchor = mock_event_utc(2026, 1, 8, 10, 0, 10, 30);
+        anchor.end.date_time = "2026-01-08T10:30:30".to_string();
+        // Seconds past a boundary still round up, never back into the anchor
+        let start = start_after(&anchor, 15).unwrap();
+        assert_eq!(start.to_rfc3339(), "2026-01-08T10:45:00+00:00");
+        assert!(
+            overlapping_events(&[anchor.clone()], start, start + Duration::minutes(30)).is_empty()
+        );
+
+        anchor.end.date_time = "2026-01-08T10:30:00".to_string();
+        assert_eq!(
+            start_after(&anchor, 15).unwrap().to_rfc3339(),
+            "2026-01-08T10:30:00+00:00"
+        );
+
+        anchor.end.date_time = "2026-01-08T10:05:00".to_string();
+        assert_eq!(
+            start_after(&anchor, 30).unwrap().to_rfc3339(),
+            "2026-01-08T10:30:00+00:00"
+        );
+        anchor.end.date_time = "2026-01-08T10:51:00".to_string();
+        assert_eq!(
+            start_after(&anchor, 25).unwrap().to_rfc3339(),
+            "2026-01-08T11:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_events_ignores_touching_edges() {
+        let before = mock_event_utc(2026, 1, 8, 9, 0, 10, 0);
+        let during = mock_event_utc(2026, 1, 8, 10, 30, 11, 0);
+        let after = mock_event_utc(2026, 1, 8, 10, 45, 11, 30);
+        let events = vec![before, during, after];
+
+        let start = Utc.with_ymd_and_hms(2026, 1, 8, 10, 0, 0).unwrap();
+        let end = start + Duration::minutes(45);
+        let conflicts = overlapping_events(&events, start, end);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].start.date_time, events[1].start.date_time);
+    }
+
     #[test]
     fn test_round_to_next_interval() {
         let time = Utc.with_ymd_and_hms(2026, 1, 8, 9, 7, 0).unwrap();
@@ -253,7 +461,7 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2026, 1, 8, 9, 7, 0).unwrap();
         let work_hours = default_work_hours();
 
-        let (start, end) = find_next_slot(&events, now, 45, &work_hours).unwrap();
+        let (start, end) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
 
         assert_eq!(start.hour(), 9);
         assert_eq!(start.minute(), 15);
@@ -272,7 +480,7 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2026, 1, 8, 9, 30, 0).unwrap();
         let work_hours = default_work_hours();
 
-        let (start, end) = find_next_slot(&events, now, 45, &work_hours).unwrap();
+        let (start, end) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
 
         // Should find gap at 10:00-10:45
         assert_eq!(start.hour(), 10);
@@ -291,7 +499,7 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2026, 1, 8, 8, 30, 0).unwrap();
         let work_hours = default_work_hours();
 
-        let (start, _) = find_next_slot(&events, now, 45, &work_hours).unwrap();
+        let (start, _) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
 
         // Should skip 30-min gap and use time after 12pm
         assert!(start.hour() >= 12);
@@ -304,7 +512,7 @@ mod tests {
         let now = Utc.with_ymd_and_hms(2026, 1, 8, 16, 30, 0).unwrap();
         let work_hours = default_work_hours();
 
-        let (start, _) = find_next_slot(&events, now, 45, &work_hours).unwrap();
+        let (start, _) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
 
         // Should be next day at 8:30am
         assert_eq!(start.day(), 9); // Next day
@@ -318,13 +526,102 @@ mod tests {
         let start = Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap();
         let end = Utc.with_ymd_and_hms(2026, 1, 8, 17, 0, 0).unwrap();
 
-        let gaps = find_gaps(&events, start, end).unwrap();
+        let gaps = find_gaps(&events, start, end, 0).unwrap();
 
         assert_eq!(gaps.len(), 1);
         assert_eq!(gaps[0].0, start);
         assert_eq!(gaps[0].1, end);
     }
 
+    fn all_day_event(start: &str, end: &str, time_zone: &str) -> CalendarEvent {
+        CalendarEvent {
+            is_all_day: true,
+            start: DateTimeTimeZone {
+                date_time: start.to_string(),
+                time_zone: time_zone.to_string(),
+            },
+            end: DateTimeTimeZone {
+                date_time: end.to_string(),
+                time_zone: time_zone.to_string(),
+            },
+            ..mock_event_utc(2026, 1, 8, 0, 0, 0, 0)
+        }
+    }
+
+    #[test]
+    fn test_find_gaps_all_day_event_blocks_window() {
+        // Date-only values don't parse as times and used to be ignored
+        let events = vec![all_day_event("2026-01-08", "2026-01-09", "UTC")];
+        let start = Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 1, 8, 17, 0, 0).unwrap();
+
+        assert!(find_gaps(&events, start, end, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_next_slot_skips_all_day_event_days() {
+        // Two-day leave recorded in UTC; work hours are in Tokyo, where UTC
+        // midnight falls mid-morning, yet both local days stay blocked
+        let events = vec![all_day_event(
+            "2026-01-08T00:00:00.0000000",
+            "2026-01-10T00:00:00.0000000",
+            "UTC",
+        )];
+        let work_hours = WorkHoursConfig {
+            timezone: "Asia/Tokyo".to_string(),
+            ..default_work_hours()
+        };
+        // 08:00 Jan 8 in Tokyo
+        let now = Utc.with_ymd_and_hms(2026, 1, 7, 23, 0, 0).unwrap();
+
+        let (start, _) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
+
+        let tz: Tz = "Asia/Tokyo".parse().unwrap();
+        let local = start.with_timezone(&tz);
+        assert_eq!(
+            local.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap()
+        );
+        // The event itself still runs until UTC midnight, 09:00 in Tokyo
+        assert_eq!((local.hour(), local.minute()), (9, 0));
+    }
+
+    fn focus_block(day: u32, start_hour: u32) -> CalendarEvent {
+        CalendarEvent {
+            categories: vec!["Focus Block".to_string()],
+            ..mock_event_utc(2026, 1, day, start_hour, 0, start_hour, 45)
+        }
+    }
+
+    #[test]
+    fn test_find_next_slot_rolls_over_when_day_is_at_cap() {
+        let events = vec![focus_block(8, 9), focus_block(8, 11)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 8, 0, 0).unwrap();
+        let focus_blocks = FocusBlocksConfig {
+            max_per_day: Some(2),
+            ..FocusBlocksConfig::default()
+        };
+        let cap = DailyCap::from_config(&focus_blocks);
+
+        let (start, _) =
+            find_next_slot_capped(&events, now, 45, 0, &default_work_hours(), cap).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 9, 8, 30, 0).unwrap());
+
+        // Below the cap the free time on the same day is used
+        let (start, _) =
+            find_next_slot_capped(&events[..1], now, 45, 0, &default_work_hours(), cap).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 8, 9, 45, 0).unwrap());
+
+        // Other meetings don't count towards the cap
+        let meetings = vec![
+            mock_event_utc(2026, 1, 8, 9, 0, 9, 45),
+            mock_event_utc(2026, 1, 8, 11, 0, 11, 45),
+        ];
+        let (start, _) =
+            find_next_slot_capped(&meetings, now, 45, 0, &default_work_hours(), cap).unwrap();
+        assert_eq!(start.day(), 8);
+    }
+
     #[test]
     fn test_find_gaps_multiple() {
         let events = vec![
@@ -335,7 +632,7 @@ mod tests {
         let start = Utc.with_ymd_and_hms(2026, 1, 8, 8, 30, 0).unwrap();
         let end = Utc.with_ymd_and_hms(2026, 1, 8, 17, 0, 0).unwrap();
 
-        let gaps = find_gaps(&events, start, end).unwrap();
+        let gaps = find_gaps(&events, start, end, 0).unwrap();
 
         // Should have 4 gaps: before first, between 1-2, between 2-3, after last
         assert_eq!(gaps.len(), 4);
@@ -344,4 +641,174 @@ mod tests {
         assert_eq!(gaps[2].0.hour(), 12); // Between second and third
         assert_eq!(gaps[3].0.hour(), 15); // After last event
     }
+
+    #[test]
+    fn test_buffer_fits_exact_gap() {
+        // Search starts at 9:00; meeting at 9:50 leaves a 50-min gap, 5-min buffer leaves 45
+        let events = vec![mock_event_utc(2026, 1, 8, 9, 50, 12, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 8, 50, 0).unwrap();
+        let work_hours = default_work_hours();
+
+        let (start, end) = find_next_slot(&events, now, 45, 5, &work_hours).unwrap();
+
+        assert_eq!(start.hour(), 9);
+        assert_eq!(start.minute(), 0);
+        assert_eq!(end.hour(), 9);
+        assert_eq!(end.minute(), 45);
+    }
+
+    #[test]
+    fn test_buffer_rejects_short_gap() {
+        // Meeting at 9:48 leaves a 48-min gap; with a 5-min buffer only 43 remain
+        let events = vec![mock_event_utc(2026, 1, 8, 9, 48, 12, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 8, 50, 0).unwrap();
+        let work_hours = default_work_hours();
+
+        let (start, _) = find_next_slot(&events, now, 45, 5, &work_hours).unwrap();
+
+        // Pushed past the meeting and its trailing buffer (12:05 -> 12:15)
+        assert_eq!(start.hour(), 12);
+        assert_eq!(start.minute(), 15);
+    }
+
+    #[test]
+    fn test_zero_buffer_keeps_adjacent_slot() {
+        let events = vec![mock_event_utc(2026, 1, 8, 9, 48, 12, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 8, 50, 0).unwrap();
+        let work_hours = default_work_hours();
+
+        let (start, end) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
+
+        assert_eq!(start.hour(), 9);
+        assert_eq!(start.minute(), 0);
+        assert_eq!(end.minute(), 45);
+    }
+
+    #[test]
+    fn test_find_gaps_buffer_shrinks_both_sides_of_meeting() {
+        let events = vec![mock_event_utc(2026, 1, 8, 10, 0, 11, 0)];
+        let start = Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 1, 8, 12, 0, 0).unwrap();
+
+        let gaps = find_gaps(&events, start, end, 10).unwrap();
+
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(
+            gaps[0],
+            (start, Utc.with_ymd_and_hms(2026, 1, 8, 9, 50, 0).unwrap())
+        );
+        assert_eq!(
+            gaps[1],
+            (Utc.with_ymd_and_hms(2026, 1, 8, 11, 10, 0).unwrap(), end)
+        );
+    }
+
+    #[test]
+    fn test_skip_weekends_friday_to_monday() {
+        // Friday 2026-01-09 is fully booked for the rest of the afternoon
+        let events = vec![mock_event_utc(2026, 1, 9, 8, 30, 17, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 9, 15, 30, 0).unwrap();
+        let work_hours = WorkHoursConfig {
+            skip_weekends: true,
+            ..default_work_hours()
+        };
+
+        let (start, _) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
+
+        assert_eq!(start.weekday(), chrono::Weekday::Mon);
+        assert_eq!(start.day(), 12);
+        assert_eq!(start.hour(), 8);
+        assert_eq!(start.minute(), 30);
+    }
+
+    #[test]
+    fn test_weekends_allowed_by_default() {
+        let events = vec![mock_event_utc(2026, 1, 9, 8, 30, 17, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 9, 15, 30, 0).unwrap();
+
+        let (start, _) = find_next_slot(&events, now, 45, 0, &default_work_hours()).unwrap();
+
+        assert_eq!(start.weekday(), chrono::Weekday::Sat);
+    }
+
+    #[test]
+    fn test_skip_holidays() {
+        let events = vec![mock_event_utc(2026, 1, 9, 8, 30, 17, 0)];
+        let now = Utc.with_ymd_and_hms(2026, 1, 9, 15, 30, 0).unwrap();
+        let work_hours = WorkHoursConfig {
+            skip_weekends: true,
+            holidays: vec![NaiveDate::from_ymd_opt(2026, 1, 12).unwrap()],
+            ..default_work_hours()
+        };
+
+        let (start, _) = find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
+
+        // Monday is a holiday, so Tuesday morning
+        assert_eq!(start.weekday(), chrono::Weekday::Tue);
+        assert_eq!(start.day(), 13);
+    }
+
+    #[test]
+    fn test_work_hours_in_non_utc_timezone() {
+        // 08:30 in Los Angeles (PST, UTC-8) is 16:30 UTC
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 15, 0, 0).unwrap();
+        let work_hours = WorkHoursConfig {
+            timezone: "America/Los_Angeles".to_string(),
+            ..default_work_hours()
+        };
+
+        let (start, end) = find_next_slot(&[], now, 45, 0, &work_hours).unwrap();
+
+        // A naive UTC computation would return 15:00 UTC (07:00 local, before work)
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 8, 16, 30, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 1, 8, 17, 15, 0).unwrap());
+    }
+
+    #[test]
+    fn test_local_day_differs_from_utc_day() {
+        // 2026-01-09 02:00 UTC is still 2026-01-08 18:00 in Los Angeles, after hours,
+        // so the next slot is Jan 9 08:30 local = 16:30 UTC
+        let now = Utc.with_ymd_and_hms(2026, 1, 9, 2, 0, 0).unwrap();
+        let work_hours = WorkHoursConfig {
+            timezone: "America/Los_Angeles".to_string(),
+            ..default_work_hours()
+        };
+
+        let (start, _) = find_next_slot(&[], now, 45, 0, &work_hours).unwrap();
+
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 9, 16, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_local_to_utc_handles_dst_transitions() {
+        let tz: Tz = "America/Los_Angeles".parse().unwrap();
+
+        // Spring forward: 02:30 on 2026-03-08 doesn't exist, moves to 03:00 PDT
+        let day = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        let time = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(
+            local_to_utc(&tz, day, time),
+            Utc.with_ymd_and_hms(2026, 3, 8, 10, 0, 0).unwrap()
+        );
+
+        // Fall back: 01:30 on 2026-11-01 happens twice, take the earlier (PDT)
+        let day = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+        assert_eq!(
+            local_to_utc(&tz, day, time),
+            Utc.with_ymd_and_hms(2026, 11, 1, 8, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_timezone_errors() {
+        let work_hours = WorkHoursConfig {
+            timezone: "Mars/Olympus".to_string(),
+            ..default_work_hours()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap();
+
+        let err = find_next_slot(&[], now, 45, 0, &work_hours).unwrap_err();
+        assert!(err.to_string().contains("Mars/Olympus"));
+    }
 }
@@ -1,7 +1,9 @@
+use crate::graph::auth::TokenCache;
 use anyhow::{Context, Result};
 use keyring::Entry;
 
 const SERVICE_DEVOPS: &str = "ao-no-out7ook-devops";
+const SERVICE_GRAPH: &str = "ao-no-out7ook-graph";
 
 /// Store a credential in the system keyring
 pub fn store_credential(service: &str, username: &str, password: &str) -> Result<()> {
@@ -49,6 +51,23 @@ pub fn delete_devops_pat() -> Result<()> {
     delete_credential(SERVICE_DEVOPS, "default")
 }
 
+/// Store Microsoft Graph OAuth tokens in keyring (serialized as JSON)
+pub fn store_graph_tokens(cache: &TokenCache) -> Result<()> {
+    let json = serde_json::to_string(cache).context("Failed to serialize Graph tokens")?;
+    store_credential(SERVICE_GRAPH, "default", &json)
+}
+
+/// Retrieve Microsoft Graph OAuth tokens from keyring
+pub fn get_graph_tokens() -> Result<TokenCache> {
+    let json = get_credential(SERVICE_GRAPH, "default")?;
+    serde_json::from_str(&json).context("Failed to parse Graph tokens from keyring")
+}
+
+/// Delete Microsoft Graph OAuth tokens from keyring
+pub fn delete_graph_tokens() -> Result<()> {
+    delete_credential(SERVICE_GRAPH, "default")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +89,22 @@ mod tests {
         // Cleanup
         delete_credential(test_service, test_username).unwrap();
     }
+
+    #[test]
+    #[ignore] // Requires actual keyring backend
+    fn test_graph_tokens_round_trip() {
+        let cache = TokenCache {
+            access_token: "test_access".to_string(),
+            refresh_token: Some("test_refresh".to_string()),
+            expires_at: chrono::Utc::now(),
+        };
+
+        store_graph_tokens(&cache).unwrap();
+
+        let retrieved = get_graph_tokens().unwrap();
+        assert_eq!(retrieved.access_token, "test_access");
+        assert_eq!(retrieved.refresh_token, Some("test_refresh".to_string()));
+
+        delete_graph_tokens().unwrap();
+    }
 }
@@ -7,6 +7,7 @@ pub mod keyring;
 pub mod pace;
 pub mod platform;
 pub mod state;
+pub mod ui;
 pub mod utils;
 
 use clap::ValueEnum;
@@ -17,4 +18,6 @@ pub enum OutputFormat {
     #[default]
     Text,
     Json,
+    Csv,
+    Yaml,
 }
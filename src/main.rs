@@ -2,6 +2,7 @@ use anyhow::Result;
 use ao_no_out7ook::OutputFormat;
 use ao_no_out7ook::commands;
 use ao_no_out7ook::config;
+use ao_no_out7ook::state::SavedQuery;
 use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
@@ -11,6 +12,12 @@ use clap::{Args, Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// ASCII-only output, without emoji (also enabled by NO_COLOR)
+    #[arg(long, global = true)]
+    plain: bool,
+    /// Microsoft Entra tenant for Graph sign-in, overriding graph.tenant_id
+    #[arg(long, global = true, value_name = "TENANT")]
+    tenant: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -22,6 +29,8 @@ enum Commands {
     Start {
         #[arg(help = "DevOps Work Item ID (e.g., 12345)")]
         id: u32,
+        #[arg(long, help = "Comment recorded on the 7Pace timer")]
+        comment: Option<String>,
         #[arg(
             long,
             help = "Preview actions without starting timer or creating calendar event"
@@ -32,6 +41,19 @@ enum Commands {
             help = "Auto-schedule a Focus Block in the calendar for immediate work"
         )]
         schedule_focus: bool,
+        #[arg(
+            long,
+            value_name = "MINS",
+            requires = "schedule_focus",
+            help = "Focus Block length in minutes, overriding focus_blocks.duration_minutes"
+        )]
+        focus_duration: Option<u32>,
+        #[arg(
+            long,
+            short = 'y',
+            help = "Stop a running timer older than state.task_expiry_hours without asking"
+        )]
+        yes: bool,
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
@@ -42,18 +64,62 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
+    /// Pause current task, stopping its timer but keeping it current
+    Pause {
+        #[arg(long, help = "Preview without stopping timer")]
+        dry_run: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Resume the paused task with a new timer
+    Resume {
+        #[arg(long, help = "Preview without starting timer")]
+        dry_run: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
     /// Switch to a new task
     Switch {
         #[arg(help = "New Work Item ID")]
         id: u32,
     },
     /// Show current task status
-    Current,
+    Current {
+        #[arg(long, help = "Keep running and refresh the elapsed time")]
+        watch: bool,
+        #[arg(
+            long,
+            default_value = "30",
+            requires = "watch",
+            help = "Seconds between refreshes in --watch mode"
+        )]
+        interval: u64,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text, conflicts_with = "watch")]
+        format: OutputFormat,
+    },
+    /// Show recently worked tasks
+    History {
+        #[arg(long, default_value = "7", help = "Number of days to show")]
+        days: u32,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
     /// Check in after Focus Block (Continue/Blocked/Complete)
     ///
     /// Interactive command to update task status after a focus session.
     /// Agents: Use 'task state' or 'task stop' for non-interactive updates instead.
     Checkin {
+        #[arg(
+            long,
+            value_enum,
+            help = "Act without prompting (for agents and scripts)"
+        )]
+        choice: Option<commands::checkin::CheckinChoice>,
+        #[arg(
+            long,
+            help = "Reason or summary added as a work item comment when blocked or complete"
+        )]
+        note: Option<String>,
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
@@ -64,14 +130,81 @@ enum Commands {
     List {
         #[arg(long, help = "Filter by state (e.g. Active)")]
         state: Option<String>,
-        #[arg(long, help = "Filter by assignee (email or 'me')")]
+        #[arg(
+            long = "exclude-state",
+            value_name = "STATE",
+            help = "Leave out items in this state (repeatable, e.g. --exclude-state Done --exclude-state Removed)"
+        )]
+        exclude_states: Vec<String>,
+        #[arg(
+            long,
+            help = "Filter by assignee (email or 'me'; overrides --mine and devops.default_assignee)"
+        )]
         assigned_to: Option<String>,
+        #[arg(long, help = "Only items assigned to me (same as --assigned-to me)")]
+        mine: bool,
         #[arg(long, help = "Search by title text")]
         search: Option<String>,
         #[arg(long, help = "Filter by tag")]
         tags: Option<String>,
+        #[arg(long, help = "Filter by iteration path (e.g. 'Project\\Sprint 12')")]
+        iteration: Option<String>,
+        #[arg(long, help = "Filter by area path")]
+        area: Option<String>,
+        #[arg(long, help = "Include items in skip_states (e.g. Closed, Completed)")]
+        include_closed: bool,
+        #[arg(
+            long,
+            help = "Only items created on or after this date (YYYY-MM-DD or RFC 3339 timestamp)"
+        )]
+        created_after: Option<String>,
+        #[arg(
+            long,
+            help = "Only items changed on or after this date (YYYY-MM-DD or RFC 3339 timestamp)"
+        )]
+        changed_after: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            help = "Sort order (default: priority, then most recently changed)"
+        )]
+        sort: Option<commands::devops::ListSort>,
         #[arg(long, help = "Limit results", default_value = "50")]
         limit: u32,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Fields to show instead of the default columns (e.g. System.Title,System.AssignedTo)"
+        )]
+        select: Vec<String>,
+        #[arg(
+            long,
+            conflicts_with = "select",
+            help = "Wrap --format json output as {items, summary} with counts and effort totals"
+        )]
+        summary: bool,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Save this filter set (and sort) under NAME for list --run"
+        )]
+        save: Option<String>,
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = [
+                "state", "exclude_states", "assigned_to", "mine", "search", "tags", "iteration", "area",
+                "include_closed", "created_after", "changed_after", "save"
+            ],
+            help = "Re-run the filters saved under NAME"
+        )]
+        run: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["save", "run"],
+            help = "List saved queries and exit"
+        )]
+        queries: bool,
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
@@ -80,6 +213,33 @@ enum Commands {
     Show {
         #[arg(help = "Work Item ID")]
         id: u32,
+        #[arg(long, help = "Open the work item in the DevOps web UI")]
+        open: bool,
+        #[arg(
+            long,
+            default_value = "1",
+            value_parser = clap::value_parser!(u8).range(0..=commands::devops::MAX_SHOW_DEPTH as i64),
+            help = "Levels of children to show in the hierarchy"
+        )]
+        depth: u8,
+        #[arg(
+            long,
+            conflicts_with_all = ["open", "depth", "format"],
+            help = "Print the full work item JSON (all fields and relations) as returned by DevOps"
+        )]
+        raw: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["raw", "format"],
+            help = "Sum the effort of all items in the hierarchy (down to --depth)"
+        )]
+        effort_rollup: bool,
+        #[arg(
+            long,
+            conflicts_with = "raw",
+            help = "Include the discussion comments, newest first"
+        )]
+        comments: bool,
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
@@ -90,6 +250,62 @@ enum Commands {
         id: u32,
         #[arg(help = "New state (target)")]
         new_state: Option<String>,
+        #[arg(
+            long,
+            requires = "new_state",
+            help = "Comment to post explaining the transition"
+        )]
+        comment: Option<String>,
+        #[arg(long, help = "Preview changes without applying")]
+        dry_run: bool,
+    },
+
+    /// Remove a work item (moves it to the Removed state unless --permanent)
+    Delete {
+        #[arg(help = "Work Item ID")]
+        id: u32,
+        #[arg(long, help = "Delete the item instead of moving it to Removed")]
+        permanent: bool,
+        #[arg(
+            long,
+            short = 'y',
+            help = "Skip the confirmation prompt (required without a terminal)"
+        )]
+        yes: bool,
+        #[arg(long, help = "Preview changes without applying")]
+        dry_run: bool,
+    },
+
+    /// List a work item's attachments, optionally downloading them
+    Attachments {
+        #[arg(help = "Work Item ID")]
+        id: u32,
+        #[arg(long, help = "Download every attachment into this directory")]
+        download_dir: Option<std::path::PathBuf>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Add a comment to a work item
+    Comment {
+        #[arg(help = "Work Item ID")]
+        id: u32,
+        #[arg(long, help = "Comment text")]
+        text: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Link two work items (related, predecessor, successor, or parent)
+    Link {
+        #[arg(help = "Work item to add the link on")]
+        from: u32,
+        #[arg(help = "Work item to link to")]
+        to: u32,
+        #[arg(long = "type", value_enum, default_value_t = commands::devops::LinkType::Related)]
+        link_type: commands::devops::LinkType,
+        #[arg(long, help = "Remove the link instead of adding it")]
+        remove: bool,
         #[arg(long, help = "Preview changes without applying")]
         dry_run: bool,
     },
@@ -101,16 +317,60 @@ enum Commands {
     Update {
         #[arg(help = "Work Item ID")]
         id: u32,
-        #[arg(long, help = "Assign to user (email or 'me')")]
+        #[arg(long, help = "Rename the work item")]
+        title: Option<String>,
+        #[arg(long, help = "Assign to user (email, display name, or 'me')")]
         assigned_to: Option<String>,
         #[arg(long, help = "Set priority (1-4)")]
         priority: Option<u32>,
         #[arg(long, help = "Set tags (comma-separated)")]
         tags: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "tags",
+            help = "Add tags, keeping existing ones (comma-separated)"
+        )]
+        tags_add: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "tags",
+            help = "Remove tags, keeping the rest (comma-separated)"
+        )]
+        tags_remove: Option<String>,
+        #[arg(long, help = "Set effort (hours)")]
+        effort: Option<f32>,
+        #[arg(long, help = "Set remaining work (hours)")]
+        remaining_work: Option<f32>,
+        #[arg(long, help = "Move to an iteration path (e.g. 'Project\\Sprint 12')")]
+        iteration: Option<String>,
+        #[arg(long, help = "Move to an area path")]
+        area: Option<String>,
         #[arg(long, help = "Preview changes without applying")]
         dry_run: bool,
     },
 
+    /// Create a single work item
+    Create {
+        #[arg(long = "type", help = "Work item type (e.g. Task, Bug, 'User Story')")]
+        work_item_type: String,
+        #[arg(long, help = "Title")]
+        title: String,
+        #[arg(long, help = "Description (HTML or plain text)")]
+        description: Option<String>,
+        #[arg(long, help = "Parent work item ID")]
+        parent: Option<u32>,
+        #[arg(long, help = "Assign to user (email, display name, or 'me')")]
+        assigned_to: Option<String>,
+        #[arg(long, help = "Priority (1-4)")]
+        priority: Option<u32>,
+        #[arg(long, help = "Tags (comma-separated)")]
+        tags: Option<String>,
+        #[arg(long, help = "Preview without creating")]
+        dry_run: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
     /// Export work items to Markdown (Phase 4)
     ///
     /// Exports one or more work items to a hierarchical Markdown format.
@@ -123,12 +383,42 @@ enum Commands {
             value_delimiter = ','
         )]
         ids: Vec<u32>,
+        #[arg(
+            long,
+            conflicts_with = "ids",
+            help = "Export items matching a WIQL condition (e.g. \"[System.State] = 'Active'\")"
+        )]
+        query: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "ids",
+            help = "Only export items changed on or after this date (YYYY-MM-DD)"
+        )]
+        since: Option<String>,
         #[arg(long, help = "Export entire hierarchy (parents and children)")]
         hierarchy: bool,
-        #[arg(short, long, help = "Output file path")]
-        output: std::path::PathBuf,
+        #[arg(
+            short,
+            long,
+            required_unless_present = "json",
+            help = "Output file path"
+        )]
+        output: Option<std::path::PathBuf>,
         #[arg(long, help = "Preview export without writing file")]
         dry_run: bool,
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "output",
+            help = "Handlebars template rendered per item (e.g. {{id}}, {{title}}, {{#each tags}})"
+        )]
+        template: Option<std::path::PathBuf>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Also write the work items as a JSON array (as returned by DevOps)"
+        )]
+        json: Option<std::path::PathBuf>,
     },
 
     /// Import work items from Markdown (Phase 4)
@@ -148,16 +438,43 @@ enum Commands {
             help = "Force import of completed/closed items (overrides skip_states config)"
         )]
         force: bool,
+        #[arg(long, help = "Parent ID for new items without a **Parent:** entry")]
+        parent: Option<u32>,
+        #[arg(
+            long,
+            help = "Let **Parent:** #0 link to a higher-level item listed later in the file, creating it first"
+        )]
+        create_missing_parents: bool,
+        #[arg(
+            long,
+            conflicts_with = "create_only",
+            help = "Only update items that have an id; skip new ones"
+        )]
+        update_only: bool,
+        #[arg(long, help = "Only create items without an id; skip existing ones")]
+        create_only: bool,
     },
 
     /// Manually log time to a work item
     LogTime {
         #[arg(help = "Work Item ID")]
         id: u32,
-        #[arg(long, help = "Hours to log (decimal, e.g. 1.5)")]
-        hours: f32,
+        #[arg(
+            long,
+            required_unless_present = "duration",
+            conflicts_with = "duration",
+            help = "Hours to log (decimal, e.g. 1.5)"
+        )]
+        hours: Option<f32>,
+        #[arg(long, help = "Time to log, e.g. 90m, 1h30m or 1.5h")]
+        duration: Option<String>,
         #[arg(long, help = "Optional comment")]
         comment: Option<String>,
+        #[arg(
+            long,
+            help = "Backdate the worklog (ISO 8601, e.g. 2026-01-05T09:00:00Z)"
+        )]
+        timestamp: Option<String>,
         #[arg(long, help = "Preview without logging")]
         dry_run: bool,
     },
@@ -166,14 +483,49 @@ enum Commands {
     Worklogs {
         #[arg(long, default_value = "7", help = "Number of days to show")]
         days: u32,
+        #[arg(long, help = "Start date, inclusive (YYYY-MM-DD); overrides --days")]
+        from: Option<String>,
+        #[arg(long, help = "End date, inclusive (YYYY-MM-DD); overrides --days")]
+        to: Option<String>,
+        #[arg(long, help = "Group entries by work item with subtotals")]
+        group_by_item: bool,
+        #[arg(
+            long,
+            conflicts_with = "group_by_item",
+            help = "Compare logged hours per day against configured work hours"
+        )]
+        reconcile: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
 
+    /// 7Pace timer operations, independent of the current task
+    Pace(PaceArgs),
+
     /// OAuth authentication for Microsoft Graph
     Oauth(OauthArgs),
 
     /// Calendar operations
     Calendar(CalendarArgs),
 
+    /// Drop calendar mappings whose events were deleted in Outlook
+    Sync {
+        #[arg(
+            long,
+            default_value = "30",
+            help = "Days before and after today to list in one call; other mapped events are looked up one by one"
+        )]
+        days: u32,
+        #[arg(long, help = "Report stale mappings without removing them")]
+        dry_run: bool,
+    },
+
+    /// Show which accounts the DevOps PAT and Graph token belong to
+    Whoami {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
     /// Documentation and AI Workflows
     ///
     /// Outputs built-in guides and standard operating procedures (SOPs) for AI agents.
@@ -185,16 +537,45 @@ enum Commands {
 
     /// Export current task context for AI Agents
     Context {
-        #[arg(long, default_value = "llm", help = "Format (currently only 'llm')")]
+        #[arg(long, default_value = "llm", help = "Format: 'llm' (text) or 'json'")]
         format: String,
     },
 
     /// Decompose a User Story into tasks via JSON input
     Decompose {
-        #[arg(long, help = "Input JSON file path")]
-        input: std::path::PathBuf,
+        #[arg(
+            long,
+            required_unless_present = "print_schema",
+            help = "Input JSON file path (use - to read from stdin)"
+        )]
+        input: Option<std::path::PathBuf>,
+        #[arg(long, help = "Parent ID (overrides parent_id in the JSON)")]
+        parent: Option<u32>,
         #[arg(long, help = "Preview changes without creating items")]
         dry_run: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["input", "parent", "dry_run"],
+            help = "Print the JSON Schema for the input file and exit"
+        )]
+        print_schema: bool,
+    },
+}
+
+#[derive(Args)]
+struct PaceArgs {
+    #[command(subcommand)]
+    action: PaceAction,
+}
+
+#[derive(Subcommand)]
+enum PaceAction {
+    /// Stop the running 7Pace timer, even if it was started elsewhere
+    Stop {
+        #[arg(long, default_value = "0", help = "7Pace stop reason code")]
+        reason: u8,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
 }
 
@@ -207,7 +588,13 @@ struct OauthArgs {
 #[derive(Subcommand)]
 enum OauthAction {
     /// Authenticate with Microsoft Graph (device code flow)
-    Login,
+    Login {
+        #[arg(
+            long,
+            help = "Finish an interrupted login with its saved device code instead of showing a new one"
+        )]
+        resume: bool,
+    },
     /// Show current authentication status
     Status {
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
@@ -229,6 +616,8 @@ enum CalendarAction {
         days: u32,
         #[arg(long, help = "Filter by work item ID")]
         work_item: Option<u32>,
+        #[arg(long, help = "Show at most this many events")]
+        limit: Option<usize>,
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
     },
@@ -236,19 +625,72 @@ enum CalendarAction {
     Schedule {
         #[arg(help = "Work Item ID")]
         id: u32,
-        #[arg(long, help = "Start time (ISO 8601, e.g., 2026-01-08T14:00:00)")]
+        #[arg(
+            long,
+            help = "Start time (ISO 8601; without an offset, in the work hours time zone; a date with --all-day)"
+        )]
         start: Option<String>,
+        #[arg(
+            long,
+            value_name = "EVENT_ID",
+            conflicts_with = "start",
+            help = "Start when this event ends (rounded up to the next scheduling interval)"
+        )]
+        after: Option<String>,
         #[arg(long, default_value = "45", help = "Duration in minutes")]
         duration: u32,
         #[arg(long, help = "Custom title (defaults to work item title)")]
         title: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["duration", "after"],
+            help = "Block out the whole day (in the work hours time zone)"
+        )]
+        all_day: bool,
+        #[arg(long, value_enum, help = "Repeat the block")]
+        recurrence: Option<commands::calendar::Recurrence>,
+        #[arg(
+            long,
+            requires = "recurrence",
+            conflicts_with = "until",
+            help = "Number of occurrences"
+        )]
+        count: Option<u32>,
+        #[arg(
+            long,
+            requires = "recurrence",
+            help = "Last date to repeat on (YYYY-MM-DD)"
+        )]
+        until: Option<String>,
         #[arg(long, help = "Preview event without creating")]
         dry_run: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Move an existing Focus Block to a new time
+    #[command(alias = "update")]
+    Reschedule {
+        #[arg(help = "Event ID")]
+        event_id: String,
+        #[arg(
+            long,
+            help = "New start time (ISO 8601; without an offset, in the work hours time zone)"
+        )]
+        start: String,
+        #[arg(
+            long,
+            help = "Duration in minutes (defaults to focus_blocks.duration_minutes)"
+        )]
+        duration: Option<u32>,
+        #[arg(long, help = "Preview new times without updating")]
+        dry_run: bool,
     },
     /// Delete calendar event
     Delete {
         #[arg(help = "Event ID")]
         event_id: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
 }
 
@@ -273,123 +715,412 @@ enum ConfigAction {
     Get {
         #[arg(help = "Config key")]
         key: String,
+        #[arg(
+            long,
+            help = "Print secrets such as devops.pat in full instead of masked"
+        )]
+        reveal: bool,
     },
 }
 
+impl Commands {
+    /// Output format requested by the subcommand, if it takes one
+    fn output_format(&self) -> OutputFormat {
+        match self {
+            Commands::Start { format, .. }
+            | Commands::Stop { format, .. }
+            | Commands::Pause { format, .. }
+            | Commands::Resume { format, .. }
+            | Commands::History { format, .. }
+            | Commands::Checkin { format, .. }
+            | Commands::List { format, .. }
+            | Commands::Show { format, .. }
+            | Commands::Comment { format, .. }
+            | Commands::Attachments { format, .. }
+            | Commands::Worklogs { format, .. }
+            | Commands::Current { format, .. }
+            | Commands::Whoami { format }
+            | Commands::Create { format, .. } => *format,
+            Commands::Pace(PaceArgs {
+                action: PaceAction::Stop { format, .. },
+            }) => *format,
+            Commands::Oauth(OauthArgs {
+                action: OauthAction::Status { format },
+            }) => *format,
+            Commands::Calendar(CalendarArgs {
+                action:
+                    CalendarAction::List { format, .. }
+                    | CalendarAction::Schedule { format, .. }
+                    | CalendarAction::Delete { format, .. },
+            }) => *format,
+            Commands::Context { format } if format == "json" => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        }
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    ao_no_out7ook::ui::init(cli.plain);
+    let format = cli.command.output_format();
 
+    match run(cli) {
+        // Agents parse stdout, so report failures there as JSON too
+        Err(e) if matches!(format, OutputFormat::Json) => {
+            println!("{}", ao_no_out7ook::error::error_json(&e));
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
     // Ensure state dir exists
-    let config = config::load().unwrap_or_else(|_| {
+    let mut config = config::load().unwrap_or_else(|_| {
         // Initial load might fail if file missing, that's okay for now
         // In real app, we'd prompt setup
-        println!("Warning: No config found. Run 'task config set ...'");
+        eprintln!("Warning: No config found. Run 'task config set ...'");
         config::Config::default()
     });
+    if let Some(tenant) = &cli.tenant {
+        config.graph.tenant_id = tenant.clone();
+    }
 
     match &cli.command {
         Commands::Start {
             id,
+            comment,
             dry_run,
             schedule_focus,
+            focus_duration,
+            yes,
             format,
         } => {
-            commands::task::start(&config, *id, *dry_run, *schedule_focus, *format)?;
+            commands::task::start(
+                &config,
+                *id,
+                &commands::task::StartOptions {
+                    comment: comment.clone(),
+                    dry_run: *dry_run,
+                    schedule_focus: *schedule_focus,
+                    focus_duration: *focus_duration,
+                    yes: *yes,
+                },
+                *format,
+            )?;
         }
         Commands::Stop { dry_run, format } => {
             commands::task::stop(&config, *dry_run, *format)?;
         }
+        Commands::Pause { dry_run, format } => {
+            commands::task::pause(&config, *dry_run, *format)?;
+        }
+        Commands::Resume { dry_run, format } => {
+            commands::task::resume(&config, *dry_run, *format)?;
+        }
         Commands::Switch { id } => {
-            commands::task::stop(&config, false, OutputFormat::Text)?;
             // Switch doesn't auto-schedule Focus Block
-            commands::task::start(&config, *id, false, false, OutputFormat::Text)?;
+            commands::task::switch(&config, *id)?;
         }
-        Commands::Current => {
-            commands::task::current(&config)?;
+        Commands::Current {
+            watch,
+            interval,
+            format,
+        } => {
+            if *watch {
+                commands::task::watch(&config, *interval)?;
+            } else {
+                commands::task::current(&config, *format)?;
+            }
         }
-        Commands::Checkin { format } => {
-            commands::checkin::checkin(&config, *format)?;
+        Commands::History { days, format } => {
+            commands::task::history(&config, *days, *format)?;
+        }
+        Commands::Checkin {
+            choice,
+            note,
+            format,
+        } => {
+            commands::checkin::checkin(&config, *choice, note.clone(), *format)?;
         }
         Commands::Config(args) => match &args.action {
             ConfigAction::List => commands::config::list(&config)?,
             ConfigAction::Set { key, value } => commands::config::set(key, value)?,
-            ConfigAction::Get { key } => commands::config::get(key, &config)?,
+            ConfigAction::Get { key, reveal } => commands::config::get(key, &config, *reveal)?,
         },
         Commands::List {
             state,
+            exclude_states,
             assigned_to,
+            mine,
             search,
             tags,
+            iteration,
+            area,
+            include_closed,
+            created_after,
+            changed_after,
+            sort,
             limit,
+            select,
+            summary,
+            save,
+            run,
+            queries,
             format,
         } => {
-            commands::devops::list(
+            if *queries {
+                return commands::devops::list_saved_queries(&config, *format);
+            }
+
+            let query = match run {
+                Some(name) => commands::devops::saved_query(&config, name)?,
+                None => SavedQuery {
+                    state: state.clone(),
+                    exclude_states: exclude_states.clone(),
+                    // --mine is saved as "me" so the config default still applies on replay
+                    assigned_to: assigned_to
+                        .clone()
+                        .or_else(|| mine.then(|| "me".to_string())),
+                    search: search.clone(),
+                    tags: tags.clone(),
+                    iteration: iteration.clone(),
+                    area: area.clone(),
+                    include_closed: *include_closed,
+                    created_after: created_after.clone(),
+                    changed_after: changed_after.clone(),
+                    sort: sort.map(commands::devops::ListSort::name),
+                },
+            };
+            let mut filters = commands::devops::ListFilters::from_saved(&query)?;
+            // An explicit --assigned-to wins over --mine, which wins over the config default
+            filters.assigned_to = filters
+                .assigned_to
+                .or_else(|| config.devops.default_assignee.clone());
+            let sort = match (sort, &query.sort) {
+                (Some(sort), _) => Some(*sort),
+                (None, Some(name)) => Some(commands::devops::ListSort::from_name(name)?),
+                (None, None) => None,
+            };
+            if let Some(name) = save {
+                commands::devops::save_query(&config, name, query)?;
+                if let OutputFormat::Text = format {
+                    println!("Saved query '{}'.", name);
+                }
+            }
+            commands::devops::list_with_sort(
                 &config,
-                state.clone(),
-                assigned_to.clone(),
-                search.clone(),
-                tags.clone(),
+                filters,
+                sort,
                 Some(*limit),
+                select,
+                *summary,
                 *format,
             )?;
         }
-        Commands::Show { id, format } => {
-            commands::devops::show(&config, *id, *format)?;
+        Commands::Show {
+            id,
+            open,
+            depth,
+            raw,
+            effort_rollup,
+            comments,
+            format,
+        } => {
+            let options = commands::devops::ShowOptions {
+                open: *open,
+                depth: *depth,
+                raw: *raw,
+                effort_rollup: *effort_rollup,
+                comments: *comments,
+            };
+            commands::devops::show(&config, *id, &options, *format)?;
         }
         Commands::State {
             id,
             new_state,
+            comment,
+            dry_run,
+        } => {
+            commands::devops::state(&config, *id, new_state.clone(), comment.clone(), *dry_run)?;
+        }
+        Commands::Delete {
+            id,
+            permanent,
+            yes,
+            dry_run,
+        } => {
+            commands::devops::delete(&config, *id, *permanent, *yes, *dry_run)?;
+        }
+        Commands::Attachments {
+            id,
+            download_dir,
+            format,
+        } => {
+            commands::devops::attachments(&config, *id, download_dir.as_deref(), *format)?;
+        }
+        Commands::Comment { id, text, format } => {
+            commands::devops::comment(&config, *id, text, *format)?;
+        }
+        Commands::Link {
+            from,
+            to,
+            link_type,
+            remove,
             dry_run,
         } => {
-            commands::devops::state(&config, *id, new_state.clone(), *dry_run)?;
+            commands::devops::link(&config, *from, *to, *link_type, *remove, *dry_run)?;
         }
         Commands::Update {
             id,
+            title,
             assigned_to,
             priority,
             tags,
+            tags_add,
+            tags_remove,
+            effort,
+            remaining_work,
+            iteration,
+            area,
             dry_run,
         } => {
-            commands::devops::update(
-                &config,
-                *id,
-                assigned_to.clone(),
-                *priority,
-                tags.clone(),
-                *dry_run,
-            )?;
+            let changes = commands::devops::WorkItemUpdate {
+                title: title.clone(),
+                assigned_to: assigned_to.clone(),
+                priority: *priority,
+                tags: tags.clone(),
+                tags_add: tags_add.clone(),
+                tags_remove: tags_remove.clone(),
+                effort: *effort,
+                remaining_work: *remaining_work,
+                iteration: iteration.clone(),
+                area: area.clone(),
+            };
+            commands::devops::update(&config, *id, changes, *dry_run)?;
+        }
+        Commands::Create {
+            work_item_type,
+            title,
+            description,
+            parent,
+            assigned_to,
+            priority,
+            tags,
+            dry_run,
+            format,
+        } => {
+            let item = commands::devops::NewWorkItem {
+                work_item_type: work_item_type.clone(),
+                title: title.clone(),
+                description: description.clone(),
+                parent: *parent,
+                assigned_to: assigned_to.clone(),
+                priority: *priority,
+                tags: tags.clone(),
+            };
+            commands::devops::create(&config, item, *dry_run, *format)?;
         }
         Commands::Export {
             ids,
+            query,
+            since,
             hierarchy,
             output,
             dry_run,
+            template,
+            json,
         } => {
-            commands::markdown::export(&config, ids.clone(), *hierarchy, output, *dry_run)?;
+            commands::markdown::export_with_options(
+                &config,
+                ids.clone(),
+                query.clone(),
+                since.as_deref(),
+                &commands::markdown::ExportOptions {
+                    hierarchy: *hierarchy,
+                    output: output.as_deref(),
+                    dry_run: *dry_run,
+                    template: template.as_deref(),
+                    json: json.as_deref(),
+                },
+            )?;
         }
         Commands::Import {
             file,
             dry_run,
             validate,
             force,
+            parent,
+            create_missing_parents,
+            update_only,
+            create_only,
         } => {
-            commands::markdown::import(&config, file, *dry_run, *validate, *force)?;
+            let mode = if *update_only {
+                commands::markdown::ImportMode::UpdateOnly
+            } else if *create_only {
+                commands::markdown::ImportMode::CreateOnly
+            } else {
+                commands::markdown::ImportMode::All
+            };
+            let options = commands::markdown::ImportOptions {
+                dry_run: *dry_run,
+                validate_only: *validate,
+                force: *force,
+                default_parent: *parent,
+                create_missing_parents: *create_missing_parents,
+                mode,
+            };
+            commands::markdown::import_with_options(&config, file, &options)?;
         }
         Commands::LogTime {
             id,
             hours,
+            duration,
             comment,
+            timestamp,
             dry_run,
         } => {
-            commands::pace::log_time(&config, *id, *hours, comment.clone(), *dry_run)?;
+            let duration_secs = match (hours, duration) {
+                (_, Some(duration)) => ao_no_out7ook::pace::duration::parse_duration(duration)?,
+                (Some(hours), None) => (hours * 3600.0) as u32,
+                (None, None) => anyhow::bail!("Specify --hours or --duration"),
+            };
+            commands::pace::log_time(
+                &config,
+                *id,
+                duration_secs,
+                comment.clone(),
+                timestamp.as_deref(),
+                *dry_run,
+            )?;
         }
-        Commands::Worklogs { days } => {
-            commands::pace::worklogs(&config, *days)?;
+        Commands::Worklogs {
+            days,
+            from,
+            to,
+            group_by_item,
+            reconcile,
+            format,
+        } => {
+            commands::pace::worklogs(
+                &config,
+                *days,
+                from.as_deref(),
+                to.as_deref(),
+                *group_by_item,
+                *reconcile,
+                *format,
+            )?;
         }
+        Commands::Pace(pace_args) => match &pace_args.action {
+            PaceAction::Stop { reason, format } => {
+                commands::pace::stop_timer(&config, *reason, *format)?;
+            }
+        },
         Commands::Oauth(oauth_args) => match &oauth_args.action {
-            OauthAction::Login => {
+            OauthAction::Login { resume } => {
                 tokio::runtime::Runtime::new()?
-                    .block_on(commands::calendar::oauth_login(&config))?;
+                    .block_on(commands::calendar::oauth_login(&config, *resume))?;
             }
             OauthAction::Status { format } => {
                 tokio::runtime::Runtime::new()?
@@ -400,35 +1131,71 @@ fn main() -> Result<()> {
             CalendarAction::List {
                 days,
                 work_item,
+                limit,
                 format,
             } => {
                 tokio::runtime::Runtime::new()?.block_on(commands::calendar::calendar_list(
-                    &config, *days, *work_item, *format,
+                    &config, *days, *work_item, *limit, *format,
                 ))?;
             }
             CalendarAction::Schedule {
                 id,
                 start,
+                after,
                 duration,
                 title,
+                all_day,
+                recurrence,
+                count,
+                until,
                 dry_run,
+                format,
             } => {
+                let options = commands::calendar::ScheduleOptions {
+                    start_time: start.clone(),
+                    duration_mins: *duration,
+                    title: title.clone(),
+                    all_day: *all_day,
+                    recurrence: *recurrence,
+                    count: *count,
+                    until: until.clone(),
+                    after: after.clone(),
+                };
                 tokio::runtime::Runtime::new()?.block_on(commands::calendar::calendar_schedule(
-                    &config,
-                    *id,
-                    start.clone(),
-                    *duration,
-                    title.clone(),
-                    *dry_run,
+                    &config, *id, options, *dry_run, *format,
                 ))?;
             }
-            CalendarAction::Delete { event_id } => {
+            CalendarAction::Reschedule {
+                event_id,
+                start,
+                duration,
+                dry_run,
+            } => {
+                tokio::runtime::Runtime::new()?.block_on(
+                    commands::calendar::calendar_reschedule(
+                        &config,
+                        event_id.clone(),
+                        start.clone(),
+                        *duration,
+                        *dry_run,
+                    ),
+                )?;
+            }
+            CalendarAction::Delete { event_id, format } => {
                 tokio::runtime::Runtime::new()?.block_on(commands::calendar::calendar_delete(
                     &config,
                     event_id.clone(),
+                    *format,
                 ))?;
             }
         },
+        Commands::Sync { days, dry_run } => {
+            tokio::runtime::Runtime::new()?
+                .block_on(commands::calendar::calendar_sync(&config, *days, *dry_run))?;
+        }
+        Commands::Whoami { format } => {
+            commands::whoami::whoami(&config, *format)?;
+        }
         Commands::Doc { topic } => match topic.as_deref() {
             Some("story-breakdown") => {
                 println!("{}", include_str!("../.agent/workflows/breakdown_story.md"));
@@ -446,8 +1213,20 @@ fn main() -> Result<()> {
         Commands::Context { format } => {
             commands::agent::agent_context(&config, format)?;
         }
-        Commands::Decompose { input, dry_run } => {
-            commands::agent::agent_decompose(&config, input.clone(), *dry_run)?;
+        Commands::Decompose {
+            input,
+            parent,
+            dry_run,
+            print_schema,
+        } => {
+            if *print_schema {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&commands::agent::decompose_schema())?
+                );
+            } else if let Some(input) = input {
+                commands::agent::agent_decompose(&config, input.clone(), *parent, *dry_run)?;
+            }
         }
     }
 
@@ -1,10 +1,17 @@
+use crate::config::{DevOpsConfig, PaceConfig};
 use crate::pace::models::{
     CreateWorklogRequest, StartTimerRequest, StopTimerResponse, Timer, Worklog,
 };
+use crate::pace::retry::{TransientError, retry_after, with_transient_retry};
+use crate::ui;
 use anyhow::{Context, Result};
 use base64::prelude::*;
 use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
+use std::time::Duration;
+
+/// Default number of retries per entry in bulk operations
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
 
 pub struct PaceClient {
     client: Client,
@@ -12,6 +19,9 @@ pub struct PaceClient {
     #[allow(dead_code)] // May be used in future API calls
     organization: String,
     pat: String,
+    max_retries: u32,
+    /// Pause between consecutive requests in bulk operations, to stay under rate limits
+    request_delay: Duration,
 }
 
 impl PaceClient {
@@ -22,15 +32,38 @@ impl PaceClient {
             base_url,
             organization: organization.to_string(),
             pat: pat.to_string(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            request_delay: Duration::ZERO,
         }
     }
 
+    /// Build a client for the configured 7Pace host (or `pace_api_url` override),
+    /// with the `[pace]` request delay and retry count
+    pub fn from_config(config: &DevOpsConfig, pace: &PaceConfig, pat: &str) -> Self {
+        Self::new(pat, &config.organization)
+            .with_base_url(&config.pace_base_url())
+            .with_max_retries(pace.max_retries)
+            .with_request_delay(pace.request_delay())
+    }
+
     /// Helper for testing to override base URL
     pub fn with_base_url(mut self, url: &str) -> Self {
         self.base_url = url.trim_end_matches('/').to_string();
         self
     }
 
+    /// Override how many times each bulk entry is retried
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Wait this long between requests in bulk operations
+    pub fn with_request_delay(mut self, delay: Duration) -> Self {
+        self.request_delay = delay;
+        self
+    }
+
     fn auth_header(&self) -> String {
         let val = format!(":{}", self.pat);
         format!("Basic {}", BASE64_STANDARD.encode(val))
@@ -66,7 +99,10 @@ impl PaceClient {
     }
 
     /// FR2.2: Stop active timer
-    pub fn stop_timer(&self, reason: u8) -> Result<StopTimerResponse> {
+    ///
+    /// `None` when 7Pace acknowledges the stop with an empty or `null` body,
+    /// which leaves the worklog details unknown.
+    pub fn stop_timer(&self, reason: u8) -> Result<Option<StopTimerResponse>> {
         let url = format!(
             "{}/_apis/api/tracking/client/stopTracking/{}",
             self.base_url, reason
@@ -83,9 +119,21 @@ impl PaceClient {
             anyhow::bail!("7Pace stop timer API error: status {}", response.status());
         }
 
-        let stop_response = response
-            .json::<StopTimerResponse>()
-            .context("Failed to parse StopTimerResponse")?;
+        let body = response
+            .text()
+            .context("Failed to read stop timer response")?;
+        let stop_response = if body.trim().is_empty() {
+            None
+        } else {
+            serde_json::from_str::<Option<StopTimerResponse>>(&body)
+                .context("Failed to parse StopTimerResponse")?
+        };
+        if stop_response.is_none() {
+            eprintln!(
+                "{} 7Pace stopped the timer but returned no worklog details",
+                ui::Symbol::Warn
+            );
+        }
 
         Ok(stop_response)
     }
@@ -116,19 +164,20 @@ impl PaceClient {
         Ok(timer_opt)
     }
 
-    /// FR2.5: Create manual worklog entry
+    /// FR2.5: Create manual worklog entry. `timestamp` defaults to now.
     pub fn create_worklog(
         &self,
         work_item_id: u32,
         duration_secs: u32,
         comment: Option<String>,
+        timestamp: Option<DateTime<Utc>>,
     ) -> Result<Worklog> {
         let url = format!("{}/_apis/worklogs", self.base_url);
 
         let request_body = CreateWorklogRequest {
             work_item_id,
             duration: duration_secs,
-            timestamp: Utc::now(),
+            timestamp: timestamp.unwrap_or_else(Utc::now),
             comment,
         };
 
@@ -141,11 +190,18 @@ impl PaceClient {
             .send()
             .context("Failed to create worklog")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "7Pace create worklog API error: status {}",
-                response.status()
-            );
+        let status = response.status();
+        // Only statuses that mean nothing was written are safe to retry
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(TransientError {
+                message: format!("7Pace create worklog API error: status {}", status),
+                retry_after: retry_after(response.headers()),
+                status: Some(status),
+            }
+            .into());
+        }
+        if !status.is_success() {
+            anyhow::bail!("7Pace create worklog API error: status {}", status);
         }
 
         let worklog = response
@@ -155,6 +211,28 @@ impl PaceClient {
         Ok(worklog)
     }
 
+    /// Create several manual worklogs (work item id, duration in seconds, comment),
+    /// pausing `request_delay` between them. Each entry is retried on its own after a
+    /// 429 or 5xx and a failure doesn't stop the rest; results are returned in input order.
+    pub fn create_worklogs(&self, entries: &[(u32, u32, Option<String>)]) -> Vec<Result<Worklog>> {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(i, (work_item_id, duration_secs, comment))| {
+                if i > 0 && !self.request_delay.is_zero() {
+                    std::thread::sleep(self.request_delay);
+                }
+                // A POST isn't idempotent: a timed-out request may already have
+                // logged the time, so only retry responses that say it didn't
+                with_transient_retry(
+                    || self.create_worklog(*work_item_id, *duration_secs, comment.clone(), None),
+                    self.max_retries,
+                )
+                .with_context(|| format!("Failed to log time on Task {}", work_item_id))
+            })
+            .collect()
+    }
+
     /// FR2.6: Fetch worklogs for reconciliation
     pub fn get_worklogs(
         &self,
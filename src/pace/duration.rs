@@ -1,3 +1,5 @@
+use anyhow::Result;
+
 /// Duration computation from multiple sources (FR2.4)
 pub enum DurationSource {
     Timer { duration_secs: u32 },
@@ -21,6 +23,49 @@ pub fn format_duration(secs: u32) -> String {
     }
 }
 
+/// Parse a human duration such as `90m`, `1h30m`, `1h 30m` or `1.5h` into
+/// seconds. Units are `h`, `m` and `s`; every number needs one.
+pub fn parse_duration(input: &str) -> Result<u32> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid duration '{}'. Use forms like 90m, 1h30m or 1.5h",
+            input
+        )
+    };
+
+    let mut total = 0.0_f64;
+    let mut number = String::new();
+    let mut seen_unit = false;
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let unit_secs = match c.to_ascii_lowercase() {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => return Err(invalid()),
+        };
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        total += value * unit_secs;
+        number.clear();
+        seen_unit = true;
+    }
+    if !number.is_empty() || !seen_unit {
+        return Err(invalid());
+    }
+
+    let secs = total.round();
+    if secs < 1.0 {
+        anyhow::bail!("Duration '{}' must be longer than zero", input);
+    }
+    if secs > f64::from(u32::MAX) {
+        anyhow::bail!("Duration '{}' is too long", input);
+    }
+    Ok(secs as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,6 +80,33 @@ mod tests {
         assert_eq!(format_duration(300), "5m");
     }
 
+    #[test]
+    fn test_parse_duration_forms() {
+        assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+        assert_eq!(parse_duration("45m").unwrap(), 2700);
+        assert_eq!(parse_duration("90m").unwrap(), 5400);
+        assert_eq!(parse_duration("1.5h").unwrap(), 5400);
+        // format_duration output parses back
+        assert_eq!(parse_duration("1h 1m").unwrap(), 3660);
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_garbage() {
+        assert!(parse_duration("banana").is_err());
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("0m").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_zero_and_overflow_messages() {
+        let zero = parse_duration("0m").unwrap_err().to_string();
+        assert!(zero.contains("longer than zero"), "{}", zero);
+
+        let huge = parse_duration("9999999h").unwrap_err().to_string();
+        assert!(huge.contains("too long"), "{}", huge);
+    }
+
     #[test]
     fn test_compute_duration_timer() {
         let source = DurationSource::Timer {
@@ -1,30 +1,91 @@
+use crate::ui;
 use anyhow::Result;
 use std::thread::sleep;
 use std::time::Duration;
 
+/// Transient failure (rate limiting, unavailable service, network) that is safe to retry
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct TransientError {
+    pub message: String,
+    /// Server-requested delay (e.g. from a Retry-After header)
+    pub retry_after: Option<Duration>,
+    /// Status that triggered the retry; `None` for network errors
+    pub status: Option<reqwest::StatusCode>,
+}
+
+/// Default delay before retry number `attempt` (0-based): 100ms, 200ms, 400ms, 800ms...
+pub fn backoff_delay(attempt: u32) -> Duration {
+    Duration::from_millis(2_u64.pow(attempt) * 100)
+}
+
+/// Longest server-requested wait we honor, so a bogus Retry-After can't hang the CLI
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Parse a Retry-After header given in seconds, capped at [`MAX_RETRY_AFTER`]
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|secs| Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
 /// Retry an operation with exponential backoff (FR2.8)
 pub fn with_retry<F, T>(operation: F, max_retries: u32) -> Result<T>
 where
     F: Fn() -> Result<T>,
+{
+    with_retry_if(operation, max_retries, |_| true)
+}
+
+/// Retry only errors marked as `TransientError`; anything else fails immediately
+pub fn with_transient_retry<F, T>(operation: F, max_retries: u32) -> Result<T>
+where
+    F: Fn() -> Result<T>,
+{
+    with_retry_if(operation, max_retries, |e| {
+        e.downcast_ref::<TransientError>().is_some()
+    })
+}
+
+/// Retry errors accepted by `is_retryable`, honoring `TransientError::retry_after`
+pub fn with_retry_if<F, T, P>(operation: F, max_retries: u32, is_retryable: P) -> Result<T>
+where
+    F: Fn() -> Result<T>,
+    P: Fn(&anyhow::Error) -> bool,
 {
     let mut attempt = 0;
     loop {
         match operation() {
             Ok(result) => return Ok(result),
-            Err(e) if attempt < max_retries => {
-                let backoff_ms = 2_u64.pow(attempt) * 100; // 100ms, 200ms, 400ms, 800ms...
+            Err(e) if attempt < max_retries && is_retryable(&e) => {
+                let delay = e
+                    .downcast_ref::<TransientError>()
+                    .and_then(|t| t.retry_after)
+                    .unwrap_or_else(|| backoff_delay(attempt));
                 eprintln!(
-                    "⚠ API call failed (attempt {}/{}): {}. Retrying in {}ms...",
+                    "{} API call failed (attempt {}/{}): {}. Retrying in {}ms...",
+                    ui::Symbol::Warn,
                     attempt + 1,
                     max_retries,
                     e,
-                    backoff_ms
+                    delay.as_millis()
                 );
-                sleep(Duration::from_millis(backoff_ms));
+                sleep(delay);
                 attempt += 1;
             }
             Err(e) => {
-                eprintln!("✗ API call failed after {} attempts", max_retries + 1);
+                if attempt > 0 {
+                    eprintln!(
+                        "{} API call failed after {} attempts",
+                        ui::Symbol::Fail,
+                        attempt + 1
+                    );
+                }
                 return Err(e);
             }
         }
@@ -69,4 +130,56 @@ mod tests {
         let result: Result<i32> = with_retry(|| anyhow::bail!("Always fails"), 2);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_transient_retry_skips_permanent_errors() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let result: Result<i32> = with_transient_retry(
+            move || {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+                anyhow::bail!("404 Not Found")
+            },
+            3,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retry_after_is_capped() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "5".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(5)));
+
+        headers.insert(reqwest::header::RETRY_AFTER, "86400".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(MAX_RETRY_AFTER));
+    }
+
+    #[test]
+    fn test_transient_retry_honors_retry_after() {
+        let counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = counter.clone();
+
+        let result = with_transient_retry(
+            move || {
+                if counter_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(TransientError {
+                        message: "429 Too Many Requests".to_string(),
+                        retry_after: Some(Duration::from_millis(1)),
+                        status: Some(reqwest::StatusCode::TOO_MANY_REQUESTS),
+                    }
+                    .into())
+                } else {
+                    Ok(7)
+                }
+            },
+            3,
+        );
+
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
 }
@@ -2,8 +2,11 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
-use std::fs::{self, OpenOptions};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
 use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct State {
@@ -14,6 +17,12 @@ pub struct State {
     /// FR3.3: Mapping between work items and calendar events
     #[serde(default)]
     pub calendar_mappings: Vec<CalendarMapping>,
+    /// Recently worked tasks, most recent last
+    #[serde(default)]
+    pub history: Vec<TaskHistoryEntry>,
+    /// Named `list` filter sets, saved with `list --save` and replayed with `list --run`
+    #[serde(default)]
+    pub saved_queries: HashMap<String, SavedQuery>,
 }
 
 impl Default for State {
@@ -24,6 +33,8 @@ impl Default for State {
             last_sync: SyncTimestamps::default(),
             work_hours: WorkHoursState::default(),
             calendar_mappings: Vec::new(),
+            history: Vec::new(),
+            saved_queries: HashMap::new(),
         }
     }
 }
@@ -38,6 +49,46 @@ pub struct CalendarMapping {
     pub last_synced: Option<DateTime<Utc>>,
 }
 
+/// A finished stint of work on a task
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskHistoryEntry {
+    pub id: u32,
+    pub title: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: DateTime<Utc>,
+}
+
+/// `list` filters as given on the command line; dates and sort are kept as
+/// typed so they are re-validated on every run
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct SavedQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_states: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iteration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(default)]
+    pub include_closed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
+
+/// Oldest history entries are dropped beyond this count
+const MAX_HISTORY_ENTRIES: usize = 500;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CurrentTask {
     pub id: u32,
@@ -45,6 +96,20 @@ pub struct CurrentTask {
     pub started_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
     pub timer_id: Option<String>,
+    /// Set while the task is paused; the 7Pace timer is stopped in the meantime
+    #[serde(default)]
+    pub paused_at: Option<DateTime<Utc>>,
+}
+
+impl CurrentTask {
+    /// Whether the task is past its `expires_at` and likely stale
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -94,6 +159,22 @@ impl State {
         Ok(())
     }
 
+    /// Move the current task (if any) into history, stopped at `stopped_at`
+    pub fn finish_current_task(&mut self, stopped_at: DateTime<Utc>) -> Option<CurrentTask> {
+        let task = self.current_task.take()?;
+        self.history.push(TaskHistoryEntry {
+            id: task.id,
+            title: task.title.clone(),
+            started_at: task.started_at,
+            stopped_at,
+        });
+        if self.history.len() > MAX_HISTORY_ENTRIES {
+            let excess = self.history.len() - MAX_HISTORY_ENTRIES;
+            self.history.drain(..excess);
+        }
+        Some(task)
+    }
+
     // --- FR3.3: Calendar Mapping Operations ---
 
     /// Add or update a mapping between a work item and calendar event
@@ -140,7 +221,47 @@ impl State {
     }
 }
 
+/// How long to wait for another process to release the state lock
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Poll interval while waiting for the state lock
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Acquire an exclusive lock, giving up after `timeout` instead of blocking forever
+fn lock_exclusive(file: &File, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => return Ok(()),
+            Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+                if Instant::now() >= deadline {
+                    anyhow::bail!(
+                        "State is locked by another process (waited {}s). \
+                         If no other ano7 command is running, delete the lock file and retry",
+                        timeout.as_secs_f32()
+                    );
+                }
+                thread::sleep(LOCK_RETRY_INTERVAL);
+            }
+            Err(e) => return Err(e).context("Failed to acquire lock"),
+        }
+    }
+}
+
 pub fn with_state_lock<F, R>(lock_path: &Path, state_path: &Path, f: F) -> Result<R>
+where
+    F: FnOnce(&mut State) -> Result<R>,
+{
+    with_state_lock_timeout(lock_path, state_path, DEFAULT_LOCK_TIMEOUT, f)
+}
+
+/// Like [`with_state_lock`], but waits at most `timeout` for the lock
+pub fn with_state_lock_timeout<F, R>(
+    lock_path: &Path,
+    state_path: &Path,
+    timeout: Duration,
+    f: F,
+) -> Result<R>
 where
     F: FnOnce(&mut State) -> Result<R>,
 {
@@ -156,7 +277,8 @@ where
         .open(lock_path)
         .context("Failed to open lock file")?;
 
-    file.lock_exclusive().context("Failed to acquire lock")?;
+    lock_exclusive(&file, timeout)
+        .with_context(|| format!("Could not lock {}", lock_path.display()))?;
 
     // Load state
     let mut state = State::load(state_path)?;
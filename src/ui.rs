@@ -0,0 +1,85 @@
+//! Status symbols for terminal output, with an ASCII-only fallback.
+//!
+//! Plain mode (`--plain` or a non-empty `NO_COLOR`) swaps emoji and box-drawing
+//! characters for ASCII so logs and limited terminals stay readable.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Enable plain mode if requested by flag or by the `NO_COLOR` convention
+pub fn init(plain_flag: bool) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    set_plain(plain_flag || no_color);
+}
+
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Leading marker for a status line, e.g. `println!("{} Saved", Symbol::Ok)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Ok,
+    Fail,
+    Warn,
+    Skip,
+    Tip,
+    Calendar,
+    Focus,
+    Lock,
+}
+
+impl Symbol {
+    pub fn as_str(self) -> &'static str {
+        if is_plain() {
+            match self {
+                Symbol::Ok => "[OK]",
+                Symbol::Fail => "[X]",
+                Symbol::Warn => "[!]",
+                Symbol::Skip => "[-]",
+                Symbol::Tip => "[i]",
+                Symbol::Calendar | Symbol::Focus | Symbol::Lock => "[*]",
+            }
+        } else {
+            match self {
+                Symbol::Ok => "✓",
+                Symbol::Fail => "❌",
+                Symbol::Warn => "⚠",
+                Symbol::Skip => "⊘",
+                Symbol::Tip => "💡",
+                Symbol::Calendar => "📅",
+                Symbol::Focus => "🎯",
+                Symbol::Lock => "🔐",
+            }
+        }
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Horizontal rule of `width` copies of `fancy` (or `=` in plain mode)
+pub fn rule(fancy: char, width: usize) -> String {
+    let c = if is_plain() { '=' } else { fancy };
+    std::iter::repeat_n(c, width).collect()
+}
+
+/// Ask a yes/no question on stdin; anything but "y"/"yes" (including EOF) is no
+pub fn confirm(prompt: &str) -> io::Result<bool> {
+    print!("{} [y/N]: ", prompt);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
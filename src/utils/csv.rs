@@ -0,0 +1,43 @@
+//! Minimal RFC 4180 CSV helpers for spreadsheet-friendly output.
+
+/// Quote a field if it contains a delimiter, quote, or line break
+pub fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into a single CSV row (without trailing newline)
+pub fn to_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|f| escape_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_plain_field() {
+        assert_eq!(escape_field("Active"), "Active");
+    }
+
+    #[test]
+    fn test_escape_quotes_and_commas() {
+        assert_eq!(
+            escape_field(r#"Fix "login", again"#),
+            r#""Fix ""login"", again""#
+        );
+    }
+
+    #[test]
+    fn test_to_row() {
+        let row = to_row(&["101", "Title, with comma", "Active"]);
+        assert_eq!(row, r#"101,"Title, with comma",Active"#);
+    }
+}
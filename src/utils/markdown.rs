@@ -1,4 +1,5 @@
 use crate::devops::models::WorkItem;
+use crate::ui;
 use anyhow::Result;
 
 // Simple Frontmatter + Body format
@@ -10,6 +11,17 @@ use anyhow::Result;
 // ---
 // Description...
 
+/// Markdown header depth for a work item type (Epic = 1 ... Task/Bug = 4)
+pub fn header_level(work_item_type: &str) -> usize {
+    match work_item_type {
+        "Epic" => 1,
+        "Feature" => 2,
+        "User Story" => 3,
+        "Task" | "Bug" => 4,
+        _ => 3, // Default to User Story level
+    }
+}
+
 /// Generate Markdown for a work item (FR4.1 - Enhanced)
 /// Supports both simple frontmatter and hierarchical header formats
 pub fn to_markdown(item: &WorkItem) -> String {
@@ -20,13 +32,7 @@ pub fn to_markdown(item: &WorkItem) -> String {
     let work_item_type = item
         .get_work_item_type()
         .unwrap_or_else(|| "Work Item".to_string());
-    let header_level = match work_item_type.as_str() {
-        "Epic" => "#",
-        "Feature" => "##",
-        "User Story" => "###",
-        "Task" | "Bug" => "####",
-        _ => "###", // Default to User Story level
-    };
+    let header_level = "#".repeat(header_level(&work_item_type));
 
     // Title line: "# Epic: Title (#ID)"
     let title = item.get_title().unwrap_or("Untitled");
@@ -69,6 +75,11 @@ pub fn to_markdown(item: &WorkItem) -> String {
         metadata.push(format!("**Effort:** {}h", effort));
     }
 
+    // Story points
+    if let Some(points) = item.fields.get(STORY_POINTS_FIELD).and_then(|v| v.as_f64()) {
+        metadata.push(format!("**Points:** {}", points));
+    }
+
     // Tags
     if let Some(tags) = item.get_tags()
         && !tags.is_empty()
@@ -76,6 +87,18 @@ pub fn to_markdown(item: &WorkItem) -> String {
         metadata.push(format!("**Tags:** {}", tags.join(", ")));
     }
 
+    if let Some(iteration) = item
+        .fields
+        .get(ITERATION_PATH_FIELD)
+        .and_then(|v| v.as_str())
+    {
+        metadata.push(format!("**Iteration:** {}", iteration));
+    }
+
+    if let Some(area) = item.fields.get(AREA_PATH_FIELD).and_then(|v| v.as_str()) {
+        metadata.push(format!("**Area:** {}", area));
+    }
+
     if !metadata.is_empty() {
         md.push_str(&format!("{}\n", metadata.join(" | ")));
     }
@@ -92,11 +115,87 @@ pub fn to_markdown(item: &WorkItem) -> String {
         md.push('\n');
     }
 
+    // Acceptance criteria (if exists)
+    if let Some(criteria) = item
+        .fields
+        .get(ACCEPTANCE_CRITERIA_FIELD)
+        .and_then(|v| v.as_str())
+    {
+        md.push_str(&format!("\n{}\n", ACCEPTANCE_CRITERIA_HEADING));
+        md.push_str(&strip_html_tags(criteria));
+        md.push('\n');
+    }
+
     md
 }
 
+const STORY_POINTS_FIELD: &str = "Microsoft.VSTS.Scheduling.StoryPoints";
+const ITERATION_PATH_FIELD: &str = "System.IterationPath";
+const AREA_PATH_FIELD: &str = "System.AreaPath";
+const ACCEPTANCE_CRITERIA_FIELD: &str = "Microsoft.VSTS.Common.AcceptanceCriteria";
+const ACCEPTANCE_CRITERIA_HEADING: &str = "## Acceptance Criteria";
+
+/// Acceptance criteria subsection heading (not a work item header)
+fn is_acceptance_criteria_heading(line: &str) -> bool {
+    line.starts_with('#')
+        && line
+            .trim_start_matches('#')
+            .trim()
+            .eq_ignore_ascii_case("Acceptance Criteria")
+}
+
+/// A Handlebars template for `export --template`, rendered once per work item.
+///
+/// Available values: `id`, `title`, `type`, `state`, `assigned_to`, `priority`,
+/// `parent_id`, `effort`, `story_points`, `tags` (a list), `iteration`, `area`,
+/// `description` (HTML stripped), `url` and the raw `fields` map.
+pub struct ExportTemplate {
+    registry: handlebars::Handlebars<'static>,
+}
+
+impl ExportTemplate {
+    const NAME: &'static str = "export";
+
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut registry = handlebars::Handlebars::new();
+        // The output is markdown, not HTML
+        registry.register_escape_fn(handlebars::no_escape);
+        registry
+            .register_template_string(Self::NAME, source)
+            .map_err(|e| anyhow::anyhow!("Invalid export template: {}", e))?;
+        Ok(Self { registry })
+    }
+
+    pub fn render(&self, item: &WorkItem) -> Result<String> {
+        self.registry
+            .render(Self::NAME, &template_context(item))
+            .map_err(|e| anyhow::anyhow!("Failed to render #{}: {}", item.id, e))
+    }
+}
+
+fn template_context(item: &WorkItem) -> serde_json::Value {
+    let field = |name: &str| item.fields.get(name).cloned();
+    serde_json::json!({
+        "id": item.id,
+        "title": item.get_title(),
+        "type": item.get_type(),
+        "state": item.get_state(),
+        "assigned_to": item.get_assigned_to(),
+        "priority": field("Microsoft.VSTS.Common.Priority"),
+        "parent_id": item.get_parent_id(),
+        "effort": item.get_effort(),
+        "story_points": field(STORY_POINTS_FIELD),
+        "tags": item.get_tags().unwrap_or_default(),
+        "iteration": field(ITERATION_PATH_FIELD),
+        "area": field(AREA_PATH_FIELD),
+        "description": item.get_description().map(strip_html_tags),
+        "url": item.url,
+        "fields": item.fields,
+    })
+}
+
 /// Strip HTML tags from description (simple implementation)
-fn strip_html_tags(html: &str) -> String {
+pub fn strip_html_tags(html: &str) -> String {
     let mut result = String::new();
     let mut in_tag = false;
 
@@ -235,8 +334,18 @@ fn get_header_prefix(work_item_type: &str) -> &'static str {
 pub fn display_validation_errors(errors: &[ValidationError]) {
     for error in errors {
         match error.severity {
-            Severity::Error => println!("❌ Line {}: {}", error.line, error.line_content),
-            Severity::Warning => println!("⚠  Line {}: {}", error.line, error.line_content),
+            Severity::Error => println!(
+                "{} Line {}: {}",
+                ui::Symbol::Fail,
+                error.line,
+                error.line_content
+            ),
+            Severity::Warning => println!(
+                "{}  Line {}: {}",
+                ui::Symbol::Warn,
+                error.line,
+                error.line_content
+            ),
         }
         println!("    Error: {}", error.message);
         if let Some(suggestion) = &error.suggestion {
@@ -250,6 +359,8 @@ pub fn display_validation_errors(errors: &[ValidationError]) {
 #[derive(Debug, Clone)]
 pub struct ParsedWorkItem {
     pub id: Option<u32>,
+    /// Header depth (1 = `#`), used to find enclosing items
+    pub level: usize,
     pub work_item_type: String,
     pub title: String,
     pub fields: std::collections::HashMap<String, String>,
@@ -267,7 +378,7 @@ pub fn from_markdown(content: &str) -> Result<Vec<ParsedWorkItem>> {
         let line = lines[i].trim();
 
         // Check for work item header (# Epic, ## Feature, ### Story, #### Task)
-        if line.starts_with('#') {
+        if line.starts_with('#') && !is_acceptance_criteria_heading(line) {
             let (item, consumed) = parse_work_item(&lines[i..], i + 1)?;
             items.push(item);
             i += consumed;
@@ -302,28 +413,47 @@ fn parse_work_item(lines: &[&str], _start_line: usize) -> Result<(ParsedWorkItem
             parse_metadata(metadata_line, &mut fields, &mut parent_id)?;
             consumed += 1;
 
-            // Collect description (lines after metadata until next header or separator)
+            // Collect description and acceptance criteria (lines after metadata
+            // until next work item header or separator)
             let remaining_lines = &lines[consumed..];
             let mut desc_count = 0;
+            let mut criteria = String::new();
+            let mut in_criteria = false;
             for line in remaining_lines {
                 let trimmed = line.trim();
+                if is_acceptance_criteria_heading(trimmed) {
+                    in_criteria = true;
+                    desc_count += 1;
+                    continue;
+                }
                 if trimmed.starts_with('#') || trimmed.starts_with("---") {
                     break;
                 }
                 if !trimmed.is_empty() {
-                    description.push_str(trimmed);
-                    description.push('\n');
+                    let target = if in_criteria {
+                        &mut criteria
+                    } else {
+                        &mut description
+                    };
+                    target.push_str(trimmed);
+                    target.push('\n');
                 }
                 desc_count += 1;
             }
             consumed += desc_count;
             description = description.trim().to_string();
+
+            let criteria = criteria.trim();
+            if !criteria.is_empty() {
+                fields.insert(ACCEPTANCE_CRITERIA_FIELD.to_string(), criteria.to_string());
+            }
         }
     }
 
     Ok((
         ParsedWorkItem {
             id,
+            level: header_level,
             work_item_type,
             title,
             fields,
@@ -408,7 +538,10 @@ fn parse_metadata(
         if let Some(start) = part.find("**")
             && let Some(end) = part[start + 2..].find("**")
         {
-            let key = part[start + 2..start + 2 + end].trim();
+            // Accept both "**Key:** Value" and "**Key**: Value"
+            let key = part[start + 2..start + 2 + end]
+                .trim()
+                .trim_end_matches(':');
             let value = part[start + 2 + end + 2..].trim_start_matches(':').trim();
 
             match key {
@@ -431,9 +564,18 @@ fn parse_metadata(
                         effort_val.to_string(),
                     );
                 }
+                "Points" => {
+                    fields.insert(STORY_POINTS_FIELD.to_string(), value.to_string());
+                }
                 "Tags" => {
                     fields.insert("System.Tags".to_string(), value.replace(", ", ";"));
                 }
+                "Iteration" => {
+                    fields.insert(ITERATION_PATH_FIELD.to_string(), value.to_string());
+                }
+                "Area" => {
+                    fields.insert(AREA_PATH_FIELD.to_string(), value.to_string());
+                }
                 "Parent" => {
                     if let Some(id_str) = value.strip_prefix('#') {
                         *parent_id = id_str.parse().ok();
@@ -477,6 +619,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_export_template_substitutes_values() {
+        let mut item = create_test_work_item("Task", 42);
+        item.fields
+            .insert("System.Tags".to_string(), json!("backend; urgent"));
+        item.fields.insert(
+            "System.Description".to_string(),
+            json!("<p>Fix the <b>login</b> flow</p>"),
+        );
+
+        let template = ExportTemplate::parse(
+            "- [{{id}}] {{title}} ({{state}}) {{#each tags}}#{{this}} {{/each}}\n  {{description}}\n",
+        )
+        .unwrap();
+        let rendered = template.render(&item).unwrap();
+
+        assert_eq!(
+            rendered,
+            "- [42] Test Task (Active) #backend #urgent \n  Fix the login flow\n"
+        );
+    }
+
+    #[test]
+    fn test_export_template_rejects_bad_syntax() {
+        assert!(ExportTemplate::parse("{{#each tags}}unclosed").is_err());
+    }
+
     #[test]
     fn test_markdown_epic_header() {
         let item = create_test_work_item("Epic", 456);
@@ -562,6 +731,144 @@ mod tests {
         assert!(!md.contains("<strong>"));
     }
 
+    #[test]
+    fn test_markdown_with_acceptance_criteria() {
+        let mut item = create_test_work_item("User Story", 500);
+        item.fields.insert(
+            "System.Description".to_string(),
+            json!("<p>Story description</p>"),
+        );
+        item.fields.insert(
+            "Microsoft.VSTS.Common.AcceptanceCriteria".to_string(),
+            json!("<ul><li>User can log in</li></ul>"),
+        );
+
+        let md = to_markdown(&item);
+
+        assert!(md.contains("## Acceptance Criteria\nUser can log in"));
+        let desc_pos = md.find("Story description").unwrap();
+        let ac_pos = md.find("## Acceptance Criteria").unwrap();
+        assert!(desc_pos < ac_pos);
+    }
+
+    #[test]
+    fn test_parse_acceptance_criteria() {
+        let md = "### User Story: Login (#500)\n\
+                  **State:** Active | **Parent:** #400\n\
+                  \n\
+                  Story description\n\
+                  \n\
+                  ## Acceptance Criteria\n\
+                  User can log in\n\
+                  Errors are shown\n\
+                  #### Task: Build form (#501)\n\
+                  **State:** New | **Parent:** #500\n\
+                  \n\
+                  Form description\n";
+
+        let items = from_markdown(md).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].description, "Story description");
+        assert_eq!(
+            items[0]
+                .fields
+                .get("Microsoft.VSTS.Common.AcceptanceCriteria")
+                .map(|s| s.as_str()),
+            Some("User can log in\nErrors are shown")
+        );
+        assert_eq!(items[1].work_item_type, "Task");
+        assert_eq!(items[1].id, Some(501));
+        assert_eq!(items[1].description, "Form description");
+        assert!(
+            !items[1]
+                .fields
+                .contains_key("Microsoft.VSTS.Common.AcceptanceCriteria")
+        );
+    }
+
+    #[test]
+    fn test_acceptance_criteria_round_trip() {
+        let mut item = create_test_work_item("User Story", 600);
+        item.fields.insert(
+            "Microsoft.VSTS.Common.AcceptanceCriteria".to_string(),
+            json!("Given a user, when they log in, then they see the dashboard"),
+        );
+
+        let parsed = from_markdown(&to_markdown(&item)).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(
+            parsed[0]
+                .fields
+                .get("Microsoft.VSTS.Common.AcceptanceCriteria")
+                .map(|s| s.as_str()),
+            Some("Given a user, when they log in, then they see the dashboard")
+        );
+    }
+
+    #[test]
+    fn test_story_points_round_trip() {
+        let mut item = create_test_work_item("User Story", 700);
+        item.fields
+            .insert("Microsoft.VSTS.Scheduling.Effort".to_string(), json!(3.5));
+        item.fields.insert(
+            "Microsoft.VSTS.Scheduling.StoryPoints".to_string(),
+            json!(8.0),
+        );
+
+        let md = to_markdown(&item);
+        assert!(md.contains("**Effort:** 3.5h | **Points:** 8"));
+
+        let parsed = from_markdown(&md).unwrap();
+        assert_eq!(
+            parsed[0]
+                .fields
+                .get("Microsoft.VSTS.Scheduling.StoryPoints")
+                .map(|s| s.as_str()),
+            Some("8")
+        );
+        assert_eq!(
+            parsed[0]
+                .fields
+                .get("Microsoft.VSTS.Scheduling.Effort")
+                .map(|s| s.as_str()),
+            Some("3.5")
+        );
+    }
+
+    #[test]
+    fn test_iteration_and_area_round_trip() {
+        let mut item = create_test_work_item("Task", 702);
+        item.fields
+            .insert("System.IterationPath".to_string(), json!("Proj\\Sprint 12"));
+        item.fields
+            .insert("System.AreaPath".to_string(), json!("Proj\\Web"));
+
+        let md = to_markdown(&item);
+        assert!(md.contains("**Iteration:** Proj\\Sprint 12 | **Area:** Proj\\Web"));
+
+        let parsed = from_markdown(&md).unwrap();
+        assert_eq!(
+            parsed[0]
+                .fields
+                .get("System.IterationPath")
+                .map(|s| s.as_str()),
+            Some("Proj\\Sprint 12")
+        );
+        assert_eq!(
+            parsed[0].fields.get("System.AreaPath").map(|s| s.as_str()),
+            Some("Proj\\Web")
+        );
+        assert!(!parsed[0].fields.contains_key("Iteration"));
+    }
+
+    #[test]
+    fn test_story_points_omitted_when_unset() {
+        let item = create_test_work_item("User Story", 701);
+        assert!(!to_markdown(&item).contains("**Points:**"));
+    }
+
     #[test]
     fn test_strip_html_tags() {
         assert_eq!(strip_html_tags("<p>Hello</p>"), "Hello");
@@ -572,4 +879,18 @@ mod tests {
             "Multi word text"
         );
     }
+
+    #[test]
+    fn test_parse_metadata_state_and_parent() {
+        let markdown = "#### Task: Build form (#0)\n**State:** New | **Parent:** #42\n";
+
+        let parsed = from_markdown(markdown).unwrap();
+
+        assert_eq!(parsed[0].level, 4);
+        assert_eq!(parsed[0].parent_id, Some(42));
+        assert_eq!(
+            parsed[0].fields.get("System.State").map(|s| s.as_str()),
+            Some("New")
+        );
+    }
 }
@@ -1 +1,3 @@
+pub mod csv;
 pub mod markdown;
+pub mod text;
@@ -0,0 +1,63 @@
+//! Display helpers for fixed-width table output.
+
+/// Shorten `s` to at most `max` characters, ending in "..." when cut.
+///
+/// Counts `char`s rather than bytes, so titles with emoji or accented
+/// letters are never sliced mid-character.
+pub fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let keep = max.saturating_sub(3);
+    let end = s.char_indices().nth(keep).map_or(s.len(), |(i, _)| i);
+    format!("{}...", &s[..end])
+}
+
+/// Human-readable byte count ("512 B", "12.3 KB", "4.0 MB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_unchanged() {
+        assert_eq!(truncate_chars("Fix login", 48), "Fix login");
+    }
+
+    #[test]
+    fn test_ascii_truncation() {
+        let long = "a".repeat(60);
+        let out = truncate_chars(&long, 48);
+        assert_eq!(out, format!("{}...", "a".repeat(45)));
+    }
+
+    #[test]
+    fn test_multibyte_truncation_does_not_panic() {
+        // Byte 45 falls inside an emoji here
+        let title = format!("{}🚀🚀🚀 café déjà vu and some more text", "x".repeat(44));
+        let out = truncate_chars(&title, 48);
+        assert_eq!(out.chars().count(), 48);
+        assert!(out.starts_with(&format!("{}🚀", "x".repeat(44))));
+        assert!(out.ends_with("..."));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(12_595), "12.3 KB");
+        assert_eq!(format_bytes(4 * 1024 * 1024), "4.0 MB");
+    }
+}
@@ -1,9 +1,12 @@
 use ao_no_out7ook::commands::agent;
+use ao_no_out7ook::config::{Config, DevOpsConfig};
 use ao_no_out7ook::state::{CurrentTask, State};
 use chrono::Utc;
 use serde_json::json;
 use std::fs;
 use tempfile::{NamedTempFile, TempDir};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[test]
 fn test_decompose_valid_json_structure() {
@@ -100,6 +103,19 @@ fn test_decompose_task_with_defaults() {
     assert_eq!(task.work_item_type, None);
 }
 
+#[test]
+fn test_decompose_schema_requires_integer_parent_id() {
+    let schema = agent::decompose_schema();
+
+    assert_eq!(schema["properties"]["parent_id"]["type"], "integer");
+    let required = schema["required"].as_array().unwrap();
+    assert!(required.contains(&json!("parent_id")));
+
+    let task = &schema["$defs"]["DecomposeTask"];
+    assert_eq!(task["properties"]["work_item_type"]["default"], "Task");
+    assert!(task["properties"]["title"]["description"].is_string());
+}
+
 #[test]
 fn test_decompose_invalid_parent_id() {
     let json_content = json!({
@@ -136,10 +152,13 @@ fn test_context_state_file_loading() {
             started_at: Utc::now(),
             expires_at: Utc::now() + chrono::Duration::hours(24),
             timer_id: Some("timer-123".to_string()),
+            paused_at: None,
         }),
         last_sync: Default::default(),
         work_hours: Default::default(),
         calendar_mappings: Vec::new(),
+        history: Vec::new(),
+        saved_queries: Default::default(),
     };
 
     // Save state
@@ -174,6 +193,85 @@ fn test_decompose_json_file_parsing() {
     assert_eq!(parsed.tasks[0].title, "File Task 1");
 }
 
+#[tokio::test]
+async fn test_decompose_rejects_unknown_type_before_creating() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 100,
+            "rev": 1,
+            "fields": { "System.Title": "Parent story" },
+            "url": "http://mock/100"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitemtypes/Task"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "Task",
+            "states": []
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex("/_apis/wit/workitemtypes/.+"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitemtypes"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 3,
+            "value": [{ "name": "Bug" }, { "name": "Task" }, { "name": "User Story" }]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let input = NamedTempFile::new().unwrap();
+    fs::write(
+        input.path(),
+        json!({
+            "parent_id": 100,
+            "tasks": [
+                { "title": "Write tests" },
+                { "title": "Draft story", "work_item_type": "Userr Story" }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let config = Config {
+        devops: DevOpsConfig {
+            pat: Some("test-pat".to_string()),
+            organization: "test-org".to_string(),
+            project: "test-project".to_string(),
+            api_url: Some(mock_server.uri()),
+            use_keyring: false,
+            ..DevOpsConfig::default()
+        },
+        ..Config::default()
+    };
+    let input_path = input.path().to_path_buf();
+
+    let result = tokio::task::spawn_blocking(move || {
+        agent::agent_decompose(&config, input_path, None, false)
+    })
+    .await
+    .unwrap();
+
+    let message = format!("{:#}", result.unwrap_err());
+    assert!(message.contains("'Userr Story'"), "{}", message);
+    assert!(message.contains("Bug, Task, User Story"), "{}", message);
+}
+
 #[test]
 fn test_context_no_current_task() {
     // Test state with no current task
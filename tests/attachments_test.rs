@@ -0,0 +1,116 @@
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands::devops;
+use ao_no_out7ook::devops::client::DevOpsClient;
+use serde_json::json;
+use wiremock::matchers::{header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+/// Work item 42 with the given (name, guid) attachments
+async fn mount_item_with_attachments(mock_server: &MockServer, files: &[(&str, &str)]) {
+    let relations: Vec<_> = files
+        .iter()
+        .map(|(name, guid)| {
+            json!({
+                "rel": "AttachedFile",
+                "url": format!("{}/_apis/wit/attachments/{}", mock_server.uri(), guid),
+                "attributes": { "name": name, "resourceSize": 4 }
+            })
+        })
+        .chain(std::iter::once(json!({
+            "rel": "System.LinkTypes.Hierarchy-Reverse",
+            "url": "http://mock/_apis/wit/workItems/7",
+            "attributes": {}
+        })))
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "rev": 1,
+            "fields": { "System.Title": "Spec'd story" },
+            "relations": relations
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_list_and_download_binary_attachment() {
+    let mock_server = MockServer::start().await;
+    mount_item_with_attachments(&mock_server, &[("spec.pdf", "aaa-111")]).await;
+
+    // Not valid UTF-8, so it would be mangled if read as text
+    let content: Vec<u8> = vec![0x25, 0x50, 0xff, 0x00];
+    Mock::given(method("GET"))
+        .and(path("/_apis/wit/attachments/aaa-111"))
+        .and(query_param("api-version", "7.0"))
+        .and(header_exists("Authorization"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(content.clone()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("spec.pdf");
+    let dest_clone = dest.clone();
+    let (attachments, bytes) = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("pat", "test-org", "test-project").with_base_url(&uri);
+        let attachments = client.list_attachments(42).unwrap();
+        let bytes = client
+            .download_attachment(&attachments[0].url, &dest_clone)
+            .unwrap();
+        (attachments, bytes)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].name, "spec.pdf");
+    assert_eq!(attachments[0].size, Some(4));
+    assert_eq!(bytes, 4);
+    assert_eq!(std::fs::read(&dest).unwrap(), content);
+}
+
+#[tokio::test]
+async fn test_attachments_command_downloads_all_with_safe_names() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_item_with_attachments(
+        &mock_server,
+        &[
+            ("notes.txt", "aaa-111"),
+            ("notes.txt", "bbb-222"),
+            ("../escape.txt", "ccc-333"),
+        ],
+    )
+    .await;
+    for guid in ["aaa-111", "bbb-222", "ccc-333"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/_apis/wit/attachments/{}", guid)))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(guid.as_bytes()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let download_dir = dir.path().join("downloads");
+    let target = download_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        devops::attachments(&config, 42, Some(&target), OutputFormat::Text)
+    })
+    .await
+    .unwrap();
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    let read = |name: &str| std::fs::read_to_string(download_dir.join(name)).unwrap();
+    assert_eq!(read("notes.txt"), "aaa-111");
+    assert_eq!(read("notes (2).txt"), "bbb-222");
+    assert_eq!(read("escape.txt"), "ccc-333");
+    assert!(!dir.path().join("escape.txt").exists());
+}
@@ -1,5 +1,11 @@
+use ao_no_out7ook::graph::auth::GraphAuthenticator;
+use ao_no_out7ook::graph::client::GraphClient;
 use ao_no_out7ook::graph::models::{CalendarEvent, DateTimeTimeZone, ItemBody};
+use ao_no_out7ook::graph::scheduler;
 use chrono::Utc;
+use std::path::{Path, PathBuf};
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 
 #[test]
 fn test_calendar_event_model_serialization() {
@@ -17,6 +23,9 @@ fn test_calendar_event_model_serialization() {
         }),
         categories: vec!["Focus Block".to_string()],
         extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
     };
 
     // Verify serialization works
@@ -74,6 +83,9 @@ fn test_calendar_event_minimal_fields() {
         body: None,
         categories: vec![],
         extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
     };
 
     // Should serialize without errors
@@ -97,6 +109,9 @@ fn test_calendar_event_with_html_body() {
         }),
         categories: vec!["Work".to_string()],
         extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
     };
 
     let json = serde_json::to_string(&event).unwrap();
@@ -121,8 +136,510 @@ fn test_calendar_event_multiple_categories() {
             "Priority".to_string(),
         ],
         extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
     };
 
     assert_eq!(event.categories.len(), 3);
     assert!(event.categories.contains(&"Deep Work".to_string()));
 }
+
+#[test]
+fn test_partial_update_event_only_sends_times() {
+    let start = Utc::now();
+    let end = start + chrono::Duration::minutes(45);
+
+    let event = CalendarEvent {
+        id: None,
+        subject: String::new(),
+        start: DateTimeTimeZone::from_utc(start, "UTC"),
+        end: DateTimeTimeZone::from_utc(end, "UTC"),
+        body: None,
+        categories: Vec::new(),
+        extended_properties: None,
+        is_all_day: false,
+        recurrence: None,
+        show_as: None,
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+    let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+    assert_eq!(keys.len(), 2, "Unexpected fields in PATCH body: {:?}", keys);
+    assert!(json.get("start").is_some());
+    assert!(json.get("end").is_some());
+}
+
+#[tokio::test]
+async fn test_reschedule_dry_run_does_not_call_graph() {
+    let config = ao_no_out7ook::config::Config::default();
+
+    let result = ao_no_out7ook::commands::calendar::calendar_reschedule(
+        &config,
+        "evt-123".to_string(),
+        "2026-01-08T14:00:00-07:00".to_string(),
+        Some(30),
+        true,
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_reschedule_rejects_invalid_start() {
+    let config = ao_no_out7ook::config::Config::default();
+
+    let result = ao_no_out7ook::commands::calendar::calendar_reschedule(
+        &config,
+        "evt-123".to_string(),
+        "tomorrow".to_string(),
+        None,
+        true,
+    )
+    .await;
+
+    assert!(result.is_err());
+}
+
+fn write_tokens(path: &Path, access_token: &str) {
+    let cache = serde_json::json!({
+        "access_token": access_token,
+        "refresh_token": null,
+        "expires_at": Utc::now() + chrono::Duration::hours(1)
+    });
+    std::fs::write(path, cache.to_string()).unwrap();
+}
+
+fn graph_client(token_path: PathBuf, uri: &str) -> GraphClient {
+    let auth = GraphAuthenticator::new("client".to_string(), "common".to_string(), token_path)
+        .with_keyring(false);
+    GraphClient::new(auth).with_base_url(uri)
+}
+
+#[tokio::test]
+async fn test_list_events_retries_throttled_request() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let events = client
+        .list_events(Utc::now(), Utc::now() + chrono::Duration::days(1))
+        .await
+        .unwrap();
+
+    assert!(events.is_empty());
+}
+
+/// Rejects the stale token after another process has already rotated it on disk
+struct RotateTokenThen401 {
+    token_path: PathBuf,
+}
+
+impl Respond for RotateTokenThen401 {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        write_tokens(&self.token_path, "token-2");
+        ResponseTemplate::new(401)
+    }
+}
+
+#[tokio::test]
+async fn test_unauthorized_retries_with_refreshed_token() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .and(header("Authorization", "Bearer token-1"))
+        .respond_with(RotateTokenThen401 {
+            token_path: token_path.clone(),
+        })
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .and(header("Authorization", "Bearer token-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let result = client
+        .list_events(Utc::now(), Utc::now() + chrono::Duration::days(1))
+        .await;
+
+    assert!(result.is_ok(), "list_events failed: {:?}", result);
+}
+
+fn mapped_event(id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "subject": "🎯 Focus",
+        "start": { "dateTime": "2026-01-08T14:00:00", "timeZone": "UTC" },
+        "end": { "dateTime": "2026-01-08T14:45:00", "timeZone": "UTC" },
+        "categories": ["Focus Block"]
+    })
+}
+
+#[tokio::test]
+async fn test_sync_prunes_mappings_for_deleted_events() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    let state_dir = dir.path().join("state");
+    let state_path = state_dir.join("state.json");
+    let mut state = ao_no_out7ook::state::State::default();
+    state.upsert_calendar_mapping(100, "evt-live".to_string());
+    state.upsert_calendar_mapping(200, "evt-deleted".to_string());
+    // Booked today for a day past the listed window
+    state.upsert_calendar_mapping(300, "evt-far-ahead".to_string());
+    state.save(&state_path).unwrap();
+
+    // Events missing from the listing are looked up individually
+    Mock::given(method("GET"))
+        .and(path("/me/events/evt-deleted"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/events/evt-far-ahead"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(mapped_event("evt-far-ahead")))
+        .mount(&mock_server)
+        .await;
+
+    // The live event is on the second page, so paging must be followed
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [mapped_event("evt-live")]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [mapped_event("evt-unrelated")],
+            "@odata.nextLink": format!("{}/me/calendar/events?page=2", mock_server.uri())
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = ao_no_out7ook::config::Config {
+        state: ao_no_out7ook::config::StateConfig {
+            state_dir_override: Some(state_dir),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let client = graph_client(token_path, &mock_server.uri());
+
+    // Dry run reports without touching state
+    let stale = ao_no_out7ook::commands::calendar::prune_stale_mappings(&config, &client, 30, true)
+        .await
+        .unwrap();
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].work_item_id, 200);
+    let state = ao_no_out7ook::state::State::load(&state_path).unwrap();
+    assert_eq!(state.calendar_mappings.len(), 3);
+
+    let stale =
+        ao_no_out7ook::commands::calendar::prune_stale_mappings(&config, &client, 30, false)
+            .await
+            .unwrap();
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].event_id, "evt-deleted");
+
+    let state = ao_no_out7ook::state::State::load(&state_path).unwrap();
+    assert_eq!(state.get_calendar_event(100), Some("evt-live"));
+    assert_eq!(state.get_calendar_event(200), None);
+    assert_eq!(state.get_calendar_event(300), Some("evt-far-ahead"));
+}
+
+async fn mount_two_pages(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [mapped_event("evt-3")]
+        })))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [mapped_event("evt-1"), mapped_event("evt-2")],
+            "@odata.nextLink": format!("{}/me/calendar/events?page=2", mock_server.uri())
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_list_events_follows_next_link() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+    mount_two_pages(&mock_server).await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let events = client
+        .list_events(Utc::now(), Utc::now() + chrono::Duration::days(7))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = events.iter().filter_map(|e| e.id.as_deref()).collect();
+    assert_eq!(ids, ["evt-1", "evt-2", "evt-3"]);
+}
+
+#[tokio::test]
+async fn test_list_events_limit_stops_paging() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+    mount_two_pages(&mock_server).await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let events = client
+        .list_events_limited(Utc::now(), Utc::now() + chrono::Duration::days(7), Some(2))
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1, "second page should not be fetched");
+    // The first N are only the earliest N if Graph sorts them
+    assert!(
+        requests[0]
+            .url
+            .query_pairs()
+            .any(|(key, value)| key == "$orderby" && value == "start/dateTime")
+    );
+}
+
+#[tokio::test]
+async fn test_get_event_anchors_start_after_it() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    Mock::given(method("GET"))
+        .and(path("/me/events/standup"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "standup",
+            "subject": "Standup",
+            "start": { "dateTime": "2026-01-08T09:00:00.0000000", "timeZone": "UTC" },
+            "end": { "dateTime": "2026-01-08T09:20:00.0000000", "timeZone": "UTC" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let anchor = client.get_event("standup").await.unwrap();
+    let start = scheduler::start_after(&anchor, 15).unwrap();
+
+    assert_eq!(anchor.subject, "Standup");
+    assert_eq!(start.to_rfc3339(), "2026-01-08T09:30:00+00:00");
+}
+
+#[tokio::test]
+async fn test_get_event_missing_is_an_error() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    Mock::given(method("GET"))
+        .and(path("/me/events/gone"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let err = client.get_event("gone").await.unwrap_err();
+
+    assert!(err.to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_get_me_parses_signed_in_user() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    Mock::given(method("GET"))
+        .and(path("/me"))
+        .and(header("Authorization", "Bearer token-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "displayName": "Me Myself",
+            "mail": null,
+            "userPrincipalName": "me.myself@example.com"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let user = client.get_me().await.unwrap();
+
+    assert_eq!(user.display_name.as_deref(), Some("Me Myself"));
+    assert_eq!(user.mail, None);
+    assert_eq!(
+        user.user_principal_name.as_deref(),
+        Some("me.myself@example.com")
+    );
+}
+
+#[tokio::test]
+async fn test_login_requests_configured_scopes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/contoso.com/oauth2/v2.0/devicecode"))
+        .and(body_string_contains(
+            "scope=Calendars.ReadWrite.Shared+offline_access",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "device_code": "device-123",
+            "user_code": "ABCD-EFGH",
+            "verification_uri": "https://microsoft.com/devicelogin",
+            "expires_in": 900,
+            "interval": 1
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/contoso.com/oauth2/v2.0/token"))
+        .and(body_string_contains("device_code=device-123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "new-access",
+            "refresh_token": "new-refresh",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    let auth = GraphAuthenticator::new(
+        "client".to_string(),
+        "contoso.com".to_string(),
+        token_path.clone(),
+    )
+    .with_keyring(false)
+    .with_login_url(&mock_server.uri())
+    .with_scopes(vec![
+        "Calendars.ReadWrite.Shared".to_string(),
+        "offline_access".to_string(),
+    ]);
+
+    auth.login().await.unwrap();
+
+    assert_eq!(auth.get_access_token().await.unwrap(), "new-access");
+    assert!(!dir.path().join("device_login.json").exists());
+}
+
+#[tokio::test]
+async fn test_login_requires_offline_access_scope() {
+    let dir = tempfile::tempdir().unwrap();
+    let auth = GraphAuthenticator::new(
+        "client".to_string(),
+        "common".to_string(),
+        dir.path().join("tokens.json"),
+    )
+    .with_keyring(false)
+    .with_login_url("http://127.0.0.1:9")
+    .with_scopes(vec!["Calendars.Read".to_string()]);
+
+    let err = auth.login().await.unwrap_err();
+    assert!(err.to_string().contains("offline_access"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_listed_all_day_event_blocks_its_day() {
+    let mock_server = MockServer::start().await;
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("tokens.json");
+    write_tokens(&token_path, "token-1");
+
+    // Graph sends all-day events with midnight datetimes, so only isAllDay marks them
+    Mock::given(method("GET"))
+        .and(path("/me/calendar/events"))
+        .and(query_param(
+            "$select",
+            "id,subject,start,end,categories,isAllDay",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [{
+                "id": "evt-leave",
+                "subject": "Annual leave",
+                "start": { "dateTime": "2026-01-06T00:00:00.0000000", "timeZone": "UTC" },
+                "end": { "dateTime": "2026-01-07T00:00:00.0000000", "timeZone": "UTC" },
+                "isAllDay": true
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = graph_client(token_path, &mock_server.uri());
+    let now = chrono::DateTime::parse_from_rfc3339("2026-01-06T09:50:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let events = client
+        .list_events(now, now + chrono::Duration::days(1))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        events[0].all_day_dates(),
+        Some((
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 6).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 7).unwrap()
+        ))
+    );
+    let work_hours = ao_no_out7ook::config::WorkHoursConfig {
+        start: "09:00".to_string(),
+        end: "17:00".to_string(),
+        timezone: "UTC".to_string(),
+        skip_weekends: false,
+        holidays: vec![],
+    };
+    let (start, _) = scheduler::find_next_slot(&events, now, 45, 0, &work_hours).unwrap();
+    assert_eq!(start.to_rfc3339(), "2026-01-07T09:00:00+00:00");
+}
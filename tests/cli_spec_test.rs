@@ -1,7 +1,9 @@
-use assert_cmd::Command;
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
 use serde_json::Value;
 use std::fs;
-use wiremock::matchers::{method, path};
+use std::path::{Path, PathBuf};
+use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -62,28 +64,17 @@ async fn test_list_json_contract() {
 
     // 3. Prepare Environment
     let temp_home = tempfile::tempdir().unwrap();
-    let config_dir = temp_home.path().join(".ao-no-out7ook");
-    fs::create_dir_all(&config_dir).unwrap();
-
-    let config_content = format!(
+    write_config_with(
+        temp_home.path(),
+        &mock_server.uri(),
         r#"
-[devops]
-organization = "test_org"
-project = "test_proj"
-api_url = "{}"
-pat = "dummy_pat"
-use_keyring = false
-
 [graph]
 client_id = "dummy_client"
 "#,
-        mock_server.uri()
     );
 
-    fs::write(config_dir.join("config.toml"), config_content).unwrap();
-
     // 4. Run CLI Command
-    let mut cmd = Command::cargo_bin("ano7").unwrap();
+    let mut cmd = cargo_bin_cmd!("ano7");
     cmd.env("HOME", temp_home.path())
         .arg("list")
         .arg("--format")
@@ -163,30 +154,20 @@ async fn test_task_lifecycle_json() {
 
     // Config Setup
     let temp_home = tempfile::tempdir().unwrap();
-    let config_dir = temp_home.path().join(".ao-no-out7ook");
-    fs::create_dir_all(&config_dir).unwrap();
-    let config_content = format!(
+    write_config_with(
+        temp_home.path(),
+        &mock_server.uri(),
         r#"
-[devops]
-organization = "test_org"
-project = "test_proj"
-api_url = "{}"
-pace_api_url = "{}"
-pat = "dummy"
-use_keyring = false
 [graph]
 client_id = "dummy"
 "#,
-        mock_server.uri(),
-        mock_server.uri()
     );
-    fs::write(config_dir.join("config.toml"), config_content).unwrap();
 
     // 1. Start Task
-    let mut cmd_start = Command::cargo_bin("ano7").unwrap();
+    let mut cmd_start = cargo_bin_cmd!("ano7");
     let assert_start = cmd_start
         .env("HOME", temp_home.path())
-        .args(&["start", "101", "--format", "json"])
+        .args(["start", "101", "--format", "json"])
         .assert()
         .success();
     let out_start = assert_start.get_output();
@@ -197,10 +178,10 @@ client_id = "dummy"
     assert!(json_start["started_at"].is_string());
 
     // 2. Stop Task
-    let mut cmd_stop = Command::cargo_bin("ano7").unwrap();
+    let mut cmd_stop = cargo_bin_cmd!("ano7");
     let assert_stop = cmd_stop
         .env("HOME", temp_home.path())
-        .args(&["stop", "--format", "json"])
+        .args(["stop", "--format", "json"])
         .assert()
         .success();
     let out_stop = assert_stop.get_output();
@@ -209,3 +190,1503 @@ client_id = "dummy"
     assert_eq!(json_stop["id"], 101);
     assert_eq!(json_stop["status"], "stopped");
 }
+
+#[tokio::test]
+async fn test_switch_checks_timer_once_and_records_history() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/102"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 102,
+            "rev": 1,
+            "fields": { "System.Title": "Task 102", "System.State": "Active" },
+            "url": "http://mock/102"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/_apis/api/tracking/client/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "timer_101",
+            "workItemId": 101,
+            "startedAt": "2026-01-01T09:00:00Z"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/startTracking"))
+        .and(body_string_contains(r#""workItemId":102"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "timer_102",
+            "workItemId": 102,
+            "startedAt": "2026-01-01T10:00:00Z"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = write_config(temp_home.path(), &mock_server.uri());
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 101,
+            "title": "Task 101",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2099-01-01T09:00:00Z",
+            "timer_id": "timer_101"
+        }),
+    );
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["switch", "102"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Switched to task: 102 - Task 102",
+        ));
+
+    let state: Value =
+        serde_json::from_str(&fs::read_to_string(config_dir.join("state.json")).unwrap()).unwrap();
+    assert_eq!(state["current_task"]["id"], 102);
+    assert_eq!(state["current_task"]["timer_id"], "timer_102");
+    assert_eq!(state["history"][0]["id"], 101);
+}
+
+#[tokio::test]
+async fn test_list_csv_contract() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queryType": "flat",
+            "workItems": [{ "id": 101, "url": "http://mock/101" }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 1,
+            "value": [{
+                "id": 101,
+                "rev": 1,
+                "fields": {
+                    "System.Title": "Fix \"login\", again",
+                    "System.State": "Active",
+                    "System.WorkItemType": "Bug",
+                    "Microsoft.VSTS.Common.Priority": 2
+                },
+                "url": "http://mock/101"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let mut cmd = cargo_bin_cmd!("ano7");
+    let assert = cmd
+        .env("HOME", temp_home.path())
+        .args(["list", "--format", "csv"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines[0], "id,title,state,priority,type");
+    assert_eq!(lines[1], r#"101,"Fix ""login"", again",Active,2,Bug"#);
+}
+
+#[tokio::test]
+async fn test_list_summary_counts_by_state() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queryType": "flat",
+            "workItems": [
+                { "id": 101, "url": "http://mock/101" },
+                { "id": 102, "url": "http://mock/102" },
+                { "id": 103, "url": "http://mock/103" }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let item = |id: u32, state: &str, extra: Value| {
+        let mut fields = serde_json::json!({
+            "System.Title": format!("Item {}", id),
+            "System.State": state
+        });
+        fields
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::json!({ "id": id, "rev": 1, "fields": fields, "url": "http://mock" })
+    };
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 3,
+            "value": [
+                item(101, "Active", serde_json::json!({ "Microsoft.VSTS.Scheduling.Effort": 3.0 })),
+                item(102, "Active", serde_json::json!({ "Microsoft.VSTS.Scheduling.StoryPoints": 5.0 })),
+                item(103, "New", serde_json::json!({ "Microsoft.VSTS.Scheduling.Effort": 1.5 }))
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--summary", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(json["items"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        json["summary"],
+        serde_json::json!({
+            "count": 3,
+            "total_effort": 4.5,
+            "total_story_points": 5.0,
+            "by_state": { "Active": 2, "New": 1 }
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_list_select_projects_fields() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queryType": "flat",
+            "workItems": [{ "id": 101, "url": "http://mock/101" }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 1,
+            "value": [{
+                "id": 101,
+                "rev": 1,
+                "fields": {
+                    "System.Title": "Implement Login",
+                    "System.State": "Active",
+                    "System.AssignedTo": {
+                        "displayName": "Alice",
+                        "uniqueName": "alice@example.com"
+                    },
+                    "Microsoft.VSTS.Scheduling.StoryPoints": 5.0
+                },
+                "url": "http://mock/101"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let select =
+        "System.Title,System.AssignedTo,Microsoft.VSTS.Scheduling.StoryPoints,Custom.Missing";
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--select", select, "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(
+        json,
+        serde_json::json!([{
+            "id": 101,
+            "System.Title": "Implement Login",
+            "System.AssignedTo": "Alice",
+            "Microsoft.VSTS.Scheduling.StoryPoints": 5.0,
+            "Custom.Missing": null
+        }])
+    );
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--select", "System.AssignedTo,System.Title"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "ID       AssignedTo Title");
+    assert_eq!(lines[2], "101      Alice      Implement Login");
+}
+
+#[tokio::test]
+async fn test_list_sort_flag_sets_order_clause() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .and(body_string_contains("ORDER BY [System.Title] ASC"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let mut cmd = cargo_bin_cmd!("ano7");
+    cmd.env("HOME", temp_home.path())
+        .args(["list", "--sort", "title"])
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn test_list_mine_and_default_assignee() {
+    let mock_server = MockServer::start().await;
+
+    for clause in [
+        "[System.AssignedTo] = @me",
+        "[System.AssignedTo] = 'bob@example.com'",
+        "[System.AssignedTo] = 'alice@example.com'",
+    ] {
+        Mock::given(method("POST"))
+            .and(path("/test_proj/_apis/wit/wiql"))
+            .and(body_string_contains(clause))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "queryType": "flat",
+                "workItems": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config_with(
+        temp_home.path(),
+        &mock_server.uri(),
+        r#"
+default_assignee = "alice@example.com"
+"#,
+    );
+
+    for args in [
+        vec!["list", "--mine"],
+        vec!["list", "--mine", "--assigned-to", "bob@example.com"],
+        vec!["list"],
+    ] {
+        cargo_bin_cmd!("ano7")
+            .env("HOME", temp_home.path())
+            .args(&args)
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn test_json_error_on_missing_pat() {
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = temp_home.path().join(".ao-no-out7ook");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[devops]
+organization = "test_org"
+project = "test_proj"
+use_keyring = false
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = cargo_bin_cmd!("ano7");
+    let assert = cmd
+        .env("HOME", temp_home.path())
+        .args(["list", "--format", "json"])
+        .assert()
+        .failure()
+        .code(1);
+
+    let error: Value = serde_json::from_slice(&assert.get_output().stdout)
+        .expect("Failure output should be valid JSON");
+    assert!(error["error"].as_str().unwrap().contains("PAT not set"));
+    assert!(error["context"].is_array());
+}
+
+#[test]
+fn test_current_flags_expired_task() {
+    let temp_home = tempfile::tempdir().unwrap();
+    let state_dir = temp_home.path().join(".ao-no-out7ook");
+    fs::create_dir_all(&state_dir).unwrap();
+    write_state(
+        &state_dir,
+        serde_json::json!({
+            "id": 42,
+            "title": "Old task",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2026-01-02T09:00:00Z",
+            "timer_id": null
+        }),
+    );
+
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["current", "--format", "json"])
+        .assert()
+        .success();
+    let output: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(output["id"], 42);
+    assert_eq!(output["expired"], true);
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "This task expired at 2026-01-02 09:00",
+        ));
+}
+
+fn tree_item(id: u32, title: &str, children: &[u32]) -> Value {
+    let relations: Vec<Value> = children
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "rel": "System.LinkTypes.Hierarchy-Forward",
+                "url": format!("http://mock/_apis/wit/workItems/{}", c),
+                "attributes": {}
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "id": id,
+        "rev": 1,
+        "fields": { "System.Title": title, "System.State": "Active" },
+        "relations": relations,
+        "url": format!("http://mock/_apis/wit/workItems/{}", id)
+    })
+}
+
+#[tokio::test]
+async fn test_show_depth_limits_hierarchy_fetches() {
+    let mock_server = MockServer::start().await;
+
+    // Epic 1 -> Feature 2 -> Story 3 -> Task 4
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(1, "Epic", &[2])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    for (id, title, children, calls) in [
+        (2, "Feature", vec![3], 1),
+        (3, "Story", vec![4], 1),
+        // Beyond --depth 2, never fetched
+        (4, "Task", vec![], 0),
+    ] {
+        Mock::given(method("POST"))
+            .and(path("/test_proj/_apis/wit/workitemsbatch"))
+            .and(body_partial_json(serde_json::json!({ "ids": [id] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "count": 1,
+                "value": [tree_item(id, title, &children)]
+            })))
+            .expect(calls)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "1", "--depth", "2"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("#3 Story"))
+        .stdout(predicates::str::contains("#4 Task").not());
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "1", "--depth", "9"])
+        .assert()
+        .failure();
+}
+
+#[tokio::test]
+async fn test_show_effort_rollup_sums_descendants() {
+    let mock_server = MockServer::start().await;
+
+    let with_effort = |id: u32, title: &str, children: &[u32], effort: Option<f64>| {
+        let mut item = tree_item(id, title, children);
+        if let Some(effort) = effort {
+            item["fields"]["Microsoft.VSTS.Scheduling.Effort"] = serde_json::json!(effort);
+        }
+        item
+    };
+
+    // Epic 1 -> Feature 2 (3h) -> Tasks 3 (2.5h) and 4 (no effort); Feature 5 (1h)
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(with_effort(
+            1,
+            "Epic",
+            &[2, 5],
+            Some(40.0),
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(serde_json::json!({ "ids": [2, 5] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 2,
+            "value": [
+                with_effort(2, "Feature A", &[3, 4], Some(3.0)),
+                with_effort(5, "Feature B", &[], Some(1.0))
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(serde_json::json!({ "ids": [3, 4] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 2,
+            "value": [
+                with_effort(3, "Task A", &[], Some(2.5)),
+                with_effort(4, "Task B", &[], None)
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "1", "--depth", "2", "--effort-rollup"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("#4 Task B"))
+        .stdout(predicates::str::contains("Total effort (4 items): 6.5h"));
+}
+
+#[tokio::test]
+async fn test_show_raw_dumps_all_fields() {
+    let mock_server = MockServer::start().await;
+
+    let mut item = tree_item(7, "Story", &[8]);
+    item["fields"]["Microsoft.VSTS.Scheduling.StoryPoints"] = serde_json::json!(5.0);
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(item))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    // --raw skips the hierarchy, so children are never fetched
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "7", "--raw"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let raw: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(raw["fields"]["Microsoft.VSTS.Scheduling.StoryPoints"], 5.0);
+    assert_eq!(
+        raw["relations"][0]["rel"],
+        "System.LinkTypes.Hierarchy-Forward"
+    );
+}
+
+#[tokio::test]
+async fn test_show_comments_json_includes_stripped_discussion() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(7, "Story", &[])))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workItems/7/comments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "comments": [
+                {
+                    "id": 1,
+                    "text": "<div>Older</div>",
+                    "createdBy": { "displayName": "Ada" },
+                    "createdDate": "2025-01-10T09:00:00Z"
+                },
+                {
+                    "id": 2,
+                    "text": "<p>Newer</p>",
+                    "createdBy": { "displayName": "Bob" },
+                    "createdDate": "2025-01-11T09:00:00Z"
+                }
+            ]
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "7", "--comments", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let shown: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(shown["id"], 7);
+    let comments = shown["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0]["author"], "Bob");
+    assert_eq!(comments[0]["text"], "Newer");
+    assert_eq!(comments[1]["text"], "Older");
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["show", "7", "--comments"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Comments (2):"));
+    let newer = stdout.find("Newer").unwrap();
+    let older = stdout.find("Older").unwrap();
+    assert!(newer < older);
+}
+
+#[tokio::test]
+async fn test_calendar_schedule_json_dry_run() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/101"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(101, "Fix login", &[])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config_with(
+        temp_home.path(),
+        &mock_server.uri(),
+        r#"
+[graph]
+client_id = "dummy"
+"#,
+    );
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args([
+            "calendar",
+            "schedule",
+            "101",
+            "--start",
+            "2026-01-08T14:00:00Z",
+            "--duration",
+            "30",
+            "--dry-run",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert!(json["event_id"].is_null());
+    assert_eq!(json["subject"], "🎯 Focus: 101 - Fix login");
+    assert_eq!(json["start"]["dateTime"], "2026-01-08T14:00:00");
+    assert_eq!(json["end"]["dateTime"], "2026-01-08T14:30:00");
+    assert_eq!(json["dry_run"], true);
+}
+
+#[tokio::test]
+async fn test_calendar_schedule_uses_configured_category() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/101"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(101, "Fix login", &[])))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config_with(
+        temp_home.path(),
+        &mock_server.uri(),
+        r#"
+[graph]
+client_id = "dummy"
+
+[focus_blocks]
+duration_minutes = 45
+interval_minutes = 15
+teams_presence_sync = false
+category = "Deep Work"
+show_as = "free"
+"#,
+    );
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args([
+            "calendar",
+            "schedule",
+            "101",
+            "--start",
+            "2026-01-08T14:00:00Z",
+            "--dry-run",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+    assert_eq!(json["categories"], serde_json::json!(["Deep Work"]));
+    assert_eq!(json["show_as"], "free");
+}
+
+#[tokio::test]
+async fn test_context_json_shape() {
+    let mock_server = MockServer::start().await;
+
+    let item = |id: u32, title: &str, wi_type: &str, relations: Value| {
+        serde_json::json!({
+            "id": id,
+            "rev": 1,
+            "fields": {
+                "System.Title": title,
+                "System.State": "Active",
+                "System.WorkItemType": wi_type
+            },
+            "relations": relations,
+            "url": format!("http://mock/_apis/wit/workItems/{}", id)
+        })
+    };
+    let link = |rel: &str, id: u32| {
+        serde_json::json!({
+            "rel": rel,
+            "url": format!("http://mock/_apis/wit/workItems/{}", id),
+            "attributes": {}
+        })
+    };
+
+    for (id, body) in [
+        (
+            5,
+            item(
+                5,
+                "Current",
+                "Task",
+                serde_json::json!([link("System.LinkTypes.Hierarchy-Reverse", 1)]),
+            ),
+        ),
+        (
+            1,
+            item(
+                1,
+                "Story",
+                "User Story",
+                serde_json::json!([
+                    link("System.LinkTypes.Hierarchy-Forward", 5),
+                    link("System.LinkTypes.Hierarchy-Forward", 6)
+                ]),
+            ),
+        ),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/test_proj/_apis/wit/workitems/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(serde_json::json!({ "ids": [6] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": 1,
+            "value": [item(6, "Sibling", "Task", serde_json::json!([]))]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = write_config(temp_home.path(), &mock_server.uri());
+    let now = chrono::Utc::now();
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 5,
+            "title": "Current",
+            "started_at": now,
+            "expires_at": now + chrono::Duration::hours(8),
+            "timer_id": null
+        }),
+    );
+
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["context", "--format", "json"])
+        .assert()
+        .success();
+    let context: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+
+    assert_eq!(
+        context["current_task"],
+        serde_json::json!({ "id": 5, "title": "Current", "state": "Active", "type": "Task" })
+    );
+    assert_eq!(context["expired"], false);
+    assert_eq!(context["parent"]["id"], 1);
+    assert_eq!(context["parent"]["type"], "User Story");
+    let siblings = context["siblings"].as_array().unwrap();
+    assert_eq!(siblings.len(), 1);
+    assert_eq!(siblings[0]["title"], "Sibling");
+}
+
+/// Serve a current task #5 under Story #1 with siblings 10-14, answering the
+/// one sibling batch call with `batch`; returns the sibling ids `context` reports
+async fn context_sibling_ids(batch: Vec<Value>) -> Vec<u64> {
+    let mock_server = MockServer::start().await;
+
+    let mut current = tree_item(5, "Current", &[]);
+    current["relations"] = serde_json::json!([{
+        "rel": "System.LinkTypes.Hierarchy-Reverse",
+        "url": "http://mock/_apis/wit/workItems/1",
+        "attributes": {}
+    }]);
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(current))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(
+            1,
+            "Story",
+            &[10, 11, 5, 12, 13, 14],
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(serde_json::json!({
+            "ids": [10, 11, 12, 13, 14],
+            "errorPolicy": "omit"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "count": batch.len(),
+            "value": batch
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(wiremock::matchers::path_regex(r"/workitems/1[0-4]$"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = write_config(temp_home.path(), &mock_server.uri());
+    let now = chrono::Utc::now();
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 5,
+            "title": "Current",
+            "started_at": now,
+            "expires_at": now + chrono::Duration::hours(8),
+            "timer_id": null
+        }),
+    );
+
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["context", "--format", "json"])
+        .assert()
+        .success();
+    let context: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+
+    context["siblings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["id"].as_u64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_context_fetches_siblings_in_one_batch() {
+    // Returned out of order; the context keeps the parent's link order
+    let batch: Vec<Value> = [14, 12, 10, 13, 11]
+        .iter()
+        .map(|id| tree_item(*id, &format!("Sibling {}", id), &[]))
+        .collect();
+
+    assert_eq!(context_sibling_ids(batch).await, vec![10, 11, 12, 13, 14]);
+}
+
+#[tokio::test]
+async fn test_context_skips_missing_sibling() {
+    // Sibling 12 was deleted; the batch omits it as null
+    let batch: Vec<Value> = [10, 11, 12, 13, 14]
+        .iter()
+        .map(|id| match id {
+            12 => Value::Null,
+            id => tree_item(*id, &format!("Sibling {}", id), &[]),
+        })
+        .collect();
+
+    assert_eq!(context_sibling_ids(batch).await, vec![10, 11, 13, 14]);
+}
+
+#[tokio::test]
+async fn test_delete_without_terminal_requires_yes() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(7, "Story", &[])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["delete", "7"])
+        .write_stdin("y\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[y/N]").not())
+        .stderr(predicate::str::contains("Pass --yes"));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["delete", "7", "--permanent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Pass --yes"));
+}
+
+/// Mocks for `start 101` while a 7Pace timer from two days ago tracks Task 55
+async fn mount_start_with_stale_timer(mock_server: &MockServer, stop_calls: u64) {
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/101"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(101, "Task 101", &[])))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/_apis/api/tracking/client/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "timer-old",
+            "workItemId": 55,
+            "startedAt": (chrono::Utc::now() - chrono::Duration::hours(50)).to_rfc3339(),
+            "comment": null
+        })))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 9,
+            "duration": 180000,
+            "workItemId": 55
+        })))
+        .expect(stop_calls)
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/startTracking"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "timer-new",
+            "startedAt": "2026-01-01T12:00:00Z",
+            "workItemId": 101
+        })))
+        .expect(stop_calls)
+        .mount(mock_server)
+        .await;
+}
+
+/// Write a config pointing DevOps and 7Pace at `api_url` under `home`,
+/// returning the config directory
+fn write_config(home: &Path, api_url: &str) -> PathBuf {
+    write_config_with(home, api_url, "")
+}
+
+/// Like [`write_config`], with `extra` TOML appended (more `[devops]` keys
+/// first, then any other sections)
+fn write_config_with(home: &Path, api_url: &str, extra: &str) -> PathBuf {
+    let config_dir = home.join(".ao-no-out7ook");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[devops]
+organization = "test_org"
+project = "test_proj"
+api_url = "{0}"
+pace_api_url = "{0}"
+pat = "dummy_pat"
+use_keyring = false
+{1}"#,
+            api_url, extra
+        ),
+    )
+    .unwrap();
+    config_dir
+}
+
+/// Write a state file whose current task is `current_task`
+fn write_state(config_dir: &Path, current_task: Value) {
+    fs::write(
+        config_dir.join("state.json"),
+        serde_json::json!({
+            "version": "1.0.0",
+            "current_task": current_task,
+            "last_sync": {},
+            "work_hours": { "start": "", "end": "" }
+        })
+        .to_string(),
+    )
+    .unwrap();
+}
+
+fn pace_home(mock_server: &MockServer) -> tempfile::TempDir {
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+    temp_home
+}
+
+#[tokio::test]
+async fn test_start_refuses_stale_timer_without_terminal() {
+    let mock_server = MockServer::start().await;
+    mount_start_with_stale_timer(&mock_server, 0).await;
+    let temp_home = pace_home(&mock_server);
+
+    // Piped stdin can't answer the prompt, so the command fails instead of
+    // silently starting nothing
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["start", "101"])
+        .write_stdin("y\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Task 55 started 50h 0m ago"))
+        .stdout(predicate::str::contains("[y/N]").not())
+        .stderr(predicate::str::contains("pass --yes to stop it"));
+}
+
+#[tokio::test]
+async fn test_start_yes_stops_stale_timer_without_asking() {
+    let mock_server = MockServer::start().await;
+    mount_start_with_stale_timer(&mock_server, 1).await;
+    let temp_home = pace_home(&mock_server);
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["start", "101", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 55 started"))
+        .stdout(predicate::str::contains("[y/N]").not())
+        .stdout(predicate::str::contains("Timer started for Task 101"));
+}
+
+#[tokio::test]
+async fn test_start_focus_duration_validation() {
+    let mock_server = MockServer::start().await;
+    let temp_home = pace_home(&mock_server);
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["start", "101", "--focus-duration", "90"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--schedule-focus"));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["start", "101", "--schedule-focus", "--focus-duration", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--focus-duration must be at least 1 minute",
+        ));
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_checkin_choice_complete_stops_timer_and_clears_task() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = write_config(temp_home.path(), &mock_server.uri());
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 101,
+            "title": "Task 101",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2099-01-01T09:00:00Z",
+            "timer_id": "timer_101"
+        }),
+    );
+
+    // Without --choice, a non-terminal stdin is refused instead of blocking
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["checkin"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--choice"));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["checkin", "--choice", "complete"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Timer stopped"))
+        .stdout(predicate::str::contains("✓ Task cleared from state"));
+
+    let state: Value =
+        serde_json::from_str(&fs::read_to_string(config_dir.join("state.json")).unwrap()).unwrap();
+    assert!(state["current_task"].is_null());
+    assert_eq!(state["history"][0]["id"], 101);
+}
+
+#[tokio::test]
+async fn test_checkin_complete_note_is_added_as_comment() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workItems/101/comments"))
+        .and(body_partial_json(serde_json::json!({ "text": "done" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 7,
+            "workItemId": 101,
+            "text": "done"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = pace_home(&mock_server);
+    let config_dir = temp_home.path().join(".ao-no-out7ook");
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 101,
+            "title": "Task 101",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2099-01-01T09:00:00Z",
+            "timer_id": "timer_101"
+        }),
+    );
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["checkin", "--choice", "continue", "--note", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("blocked or complete"));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["checkin", "--choice", "complete", "--note", "done"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Note added to Task 101"))
+        .stdout(predicate::str::contains("Task cleared from state"));
+}
+
+#[tokio::test]
+async fn test_checkin_choice_json_prints_result_object() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = pace_home(&mock_server);
+    let config_dir = temp_home.path().join(".ao-no-out7ook");
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 101,
+            "title": "Task 101",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2099-01-01T09:00:00Z",
+            "timer_id": "timer_101"
+        }),
+    );
+
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["checkin", "--choice", "complete", "--format", "json"])
+        .assert()
+        .success();
+    // Nothing but the result object, so the output parses as JSON
+    let result: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+
+    assert_eq!(result["id"], 101);
+    assert_eq!(result["choice"], "complete");
+    assert_eq!(result["timer_stopped"], true);
+    assert_eq!(result["note_added"], false);
+    assert_eq!(result["task_cleared"], true);
+    assert_eq!(result["warnings"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_plain_output_is_ascii_only() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = write_config(temp_home.path(), &mock_server.uri());
+    write_state(
+        &config_dir,
+        serde_json::json!({
+            "id": 101,
+            "title": "Task 101",
+            "started_at": "2026-01-01T09:00:00Z",
+            "expires_at": "2026-01-02T09:00:00Z",
+            "timer_id": "timer_101"
+        }),
+    );
+
+    // NO_COLOR alone switches to plain output
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .env("NO_COLOR", "1")
+        .arg("current")
+        .assert()
+        .success();
+    let stdout = &assert.get_output().stdout;
+    assert!(stdout.is_ascii(), "{}", String::from_utf8_lossy(stdout));
+    assert!(String::from_utf8_lossy(stdout).contains("[!] This task expired"));
+
+    let assert = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .env_remove("NO_COLOR")
+        .args(["--plain", "checkin", "--choice", "complete"])
+        .assert()
+        .success();
+    let stdout = &assert.get_output().stdout;
+    assert!(stdout.is_ascii(), "{}", String::from_utf8_lossy(stdout));
+    assert!(String::from_utf8_lossy(stdout).contains("[OK] Timer stopped"));
+}
+
+#[tokio::test]
+async fn test_decompose_reads_input_from_stdin() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_item(100, "Story", &[])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitemtypes/Task"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Task",
+            "states": []
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let input = serde_json::json!({
+        "parent_id": 100,
+        "tasks": [{ "title": "Piped task" }]
+    });
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["decompose", "--input", "-", "--dry-run"])
+        .write_stdin(input.to_string())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Decomposing under Parent: #100"))
+        .stdout(predicate::str::contains("Piped task"));
+}
+
+#[tokio::test]
+async fn test_worklogs_json_lists_entries_and_total() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/_apis/worklogs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": 1,
+                "workItemId": 100,
+                "userId": "user-1",
+                "duration": 1800,
+                "timestamp": "2026-01-07T10:00:00Z",
+                "comment": "Morning work"
+            },
+            {
+                "id": 2,
+                "workItemId": 101,
+                "userId": "user-1",
+                "duration": 5400,
+                "timestamp": "2026-01-07T14:00:00Z",
+                "comment": null
+            }
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    let output = cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["worklogs", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let worklogs = report["worklogs"].as_array().unwrap();
+    assert_eq!(worklogs.len(), 2);
+    assert_eq!(worklogs[0]["work_item_id"], 100);
+    assert_eq!(worklogs[0]["duration_seconds"], 1800);
+    assert_eq!(worklogs[0]["comment"], "Morning work");
+    assert!(worklogs[1]["comment"].is_null());
+    assert_eq!(report["summary"]["total_seconds"], 7200);
+    assert_eq!(report["summary"]["entries"], 2);
+}
+
+#[tokio::test]
+async fn test_list_save_and_run_replays_filters() {
+    let mock_server = MockServer::start().await;
+
+    // Once for --save, once for --run
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .and(body_string_contains("[System.State] = 'Active'"))
+        .and(body_string_contains("[System.AssignedTo] = @me"))
+        .and(body_string_contains("ORDER BY [System.Title] ASC"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let temp_home = tempfile::tempdir().unwrap();
+    write_config(temp_home.path(), &mock_server.uri());
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args([
+            "list", "--state", "Active", "--mine", "--sort", "title", "--save", "mywork",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved query 'mywork'."));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--run", "mywork"])
+        .assert()
+        .success();
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--queries"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "mywork               --state 'Active' --assigned-to 'me' --sort 'title'",
+        ));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["list", "--run", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No saved query named 'nope'"));
+}
+
+#[test]
+fn test_config_get_pat_is_masked_unless_revealed() {
+    let temp_home = tempfile::tempdir().unwrap();
+    let config_dir = temp_home.path().join(".ao-no-out7ook");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[devops]
+organization = "test_org"
+project = "test_proj"
+pat = "secret-pat-wxyz"
+use_keyring = false
+"#,
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["config", "get", "devops.pat"])
+        .assert()
+        .success()
+        .stdout("****wxyz\n");
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["config", "get", "devops.pat", "--reveal"])
+        .assert()
+        .success()
+        .stdout("secret-pat-wxyz\n");
+}
+
+#[test]
+fn test_log_time_duration_flag_validation() {
+    let temp_home = tempfile::tempdir().unwrap();
+
+    // --hours and --duration are mutually exclusive
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["log-time", "1", "--hours", "1", "--duration", "1h"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    cargo_bin_cmd!("ano7")
+        .env("HOME", temp_home.path())
+        .args(["log-time", "1", "--duration", "banana", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration 'banana'"));
+}
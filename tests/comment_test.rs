@@ -0,0 +1,153 @@
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands::devops;
+use ao_no_out7ook::devops::client::DevOpsClient;
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+#[tokio::test]
+async fn test_add_comment_posts_text() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .and(body_json(json!({ "text": "Halfway there" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 42,
+            "workItemId": 123,
+            "text": "Halfway there"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let comment = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test-pat", "test-org", "test-project").with_base_url(&uri);
+        client.add_comment(123, "Halfway there")
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(comment.id, 42);
+    assert_eq!(comment.work_item_id, 123);
+    assert_eq!(comment.text, "Halfway there");
+}
+
+#[tokio::test]
+async fn test_comment_command_json() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .and(body_json(json!({ "text": "Blocked on review" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7,
+            "workItemId": 123,
+            "text": "Blocked on review"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::comment(&config, 123, "Blocked on review", OutputFormat::Json)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_comment_api_error() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workItems/999/comments"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::comment(&config, 999, "Nope", OutputFormat::Text)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_get_comments_follows_pages_newest_first() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .and(query_param_is_missing("continuationToken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "totalCount": 3,
+            "count": 2,
+            "continuationToken": "page2",
+            "comments": [
+                {
+                    "id": 1,
+                    "workItemId": 123,
+                    "text": "<p>First</p>",
+                    "createdBy": { "displayName": "Ada Lovelace", "uniqueName": "ada@example.com" },
+                    "createdDate": "2025-01-10T09:00:00Z"
+                },
+                {
+                    "id": 3,
+                    "workItemId": 123,
+                    "text": "Third",
+                    "createdBy": { "uniqueName": "bob@example.com" },
+                    "createdDate": "2025-01-12T09:00:00Z"
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .and(query_param("continuationToken", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "totalCount": 3,
+            "count": 1,
+            "comments": [
+                {
+                    "id": 2,
+                    "workItemId": 123,
+                    "text": "Second",
+                    "createdDate": "2025-01-11T09:00:00.123Z"
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let comments = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test-pat", "test-org", "test-project").with_base_url(&uri);
+        client.get_comments(123)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    let ids: Vec<u32> = comments.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+    assert_eq!(comments[0].author(), Some("bob@example.com"));
+    assert_eq!(comments[1].author(), None);
+    assert_eq!(comments[2].author(), Some("Ada Lovelace"));
+    assert_eq!(comments[2].text, "<p>First</p>");
+}
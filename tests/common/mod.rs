@@ -0,0 +1,18 @@
+use ao_no_out7ook::config::{Config, DevOpsConfig};
+
+/// Config pointing DevOps at a mock server, with nothing filtered out by `skip_states`
+pub fn create_test_config(api_url: String) -> Config {
+    Config {
+        devops: DevOpsConfig {
+            pat: Some("test-pat".to_string()),
+            organization: "test-org".to_string(),
+            project: "test-project".to_string(),
+            skip_states: vec![],
+            api_url: Some(api_url),
+            pace_api_url: None,
+            use_keyring: false,
+            ..DevOpsConfig::default()
+        },
+        ..Config::default()
+    }
+}
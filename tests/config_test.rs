@@ -1,4 +1,5 @@
-use ao_no_out7ook::config::load_from_path;
+use ao_no_out7ook::commands::config::{mask_secret, set_at_path};
+use ao_no_out7ook::config::{DevOpsConfig, load_from_path};
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -43,3 +44,131 @@ fn test_load_config_defaults() {
     assert_eq!(config.graph.tenant_id, "common");
     assert_eq!(config.focus_blocks.duration_minutes, 45);
 }
+
+#[test]
+fn test_config_set_preserves_comments() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    let config_content = r#"# My settings
+[devops]
+# Which org to use
+organization = "old_org"
+project = "test_proj"
+"#;
+    temp_file.write_all(config_content.as_bytes()).unwrap();
+
+    set_at_path(temp_file.path(), "devops.organization", "new_org").unwrap();
+
+    let written = std::fs::read_to_string(temp_file.path()).unwrap();
+    assert!(written.contains("# My settings"));
+    assert!(written.contains("# Which org to use"));
+    assert!(written.contains("organization = \"new_org\""));
+
+    let config = load_from_path(temp_file.path()).unwrap();
+    assert_eq!(config.devops.organization, "new_org");
+}
+
+#[test]
+fn test_config_set_creates_missing_section() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    temp_file
+        .write_all(b"[devops]\norganization = \"o\"\nproject = \"p\"\n")
+        .unwrap();
+
+    set_at_path(temp_file.path(), "work_hours.start", "09:00").unwrap();
+    set_at_path(temp_file.path(), "focus_blocks.duration_minutes", "50").unwrap();
+    set_at_path(temp_file.path(), "devops.skip_states", "Closed, Removed").unwrap();
+
+    let written = std::fs::read_to_string(temp_file.path()).unwrap();
+    assert!(written.contains("[work_hours]"));
+    assert!(written.contains("duration_minutes = 50"));
+    assert!(written.contains(r#"skip_states = ["Closed", "Removed"]"#));
+}
+
+#[test]
+fn test_config_set_rejects_type_mismatch() {
+    let temp_file = NamedTempFile::new().unwrap();
+
+    let result = set_at_path(temp_file.path(), "focus_blocks.duration_minutes", "soon");
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("expected a whole number")
+    );
+
+    let result = set_at_path(temp_file.path(), "devops.use_keyring", "maybe");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_work_item_url() {
+    let devops = DevOpsConfig {
+        organization: "contoso".to_string(),
+        project: "My Project".to_string(),
+        ..DevOpsConfig::default()
+    };
+
+    assert_eq!(
+        devops.work_item_url(123),
+        "https://dev.azure.com/contoso/My%20Project/_workitems/edit/123"
+    );
+}
+
+#[test]
+fn test_check_transition_rejects_disallowed_jump() {
+    let devops = DevOpsConfig {
+        transitions: [(
+            "New".to_string(),
+            vec!["Active".to_string(), "Removed".to_string()],
+        )]
+        .into_iter()
+        .collect(),
+        ..DevOpsConfig::default()
+    };
+
+    assert!(devops.check_transition("New", "Active").is_ok());
+    let err = devops.check_transition("New", "Closed").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Reachable from New: Active, Removed")
+    );
+
+    // States without an entry, and configs without a map, are unrestricted
+    assert!(devops.check_transition("Active", "Closed").is_ok());
+    assert!(
+        DevOpsConfig::default()
+            .check_transition("New", "Closed")
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_load_config_with_transitions() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    temp_file
+        .write_all(
+            br#"
+        [devops]
+        organization = "o"
+        project = "p"
+
+        [devops.transitions]
+        New = ["Active"]
+        "#,
+        )
+        .unwrap();
+
+    let config = load_from_path(temp_file.path()).unwrap();
+    assert!(config.devops.check_transition("New", "Active").is_ok());
+    assert!(config.devops.check_transition("New", "Closed").is_err());
+}
+
+#[test]
+fn test_mask_secret_shows_last_four() {
+    assert_eq!(mask_secret("abcdefgh1234"), "****1234");
+    assert_eq!(mask_secret("abcd"), "****");
+    assert_eq!(mask_secret(""), "****");
+    // Counts characters, not bytes
+    assert_eq!(mask_secret("pässwörd"), "****wörd");
+}
@@ -1,4 +1,4 @@
-use ao_no_out7ook::config::{Config, FocusBlocksConfig, WorkHoursConfig};
+use ao_no_out7ook::config::{Config, FocusBlocksConfig, GraphConfig, WorkHoursConfig};
 
 #[test]
 fn test_work_hours_validation_valid() {
@@ -6,6 +6,7 @@ fn test_work_hours_validation_valid() {
         start: "09:00".to_string(),
         end: "17:00".to_string(),
         timezone: "America/Los_Angeles".to_string(),
+        ..Default::default()
     };
 
     assert!(config.validate().is_ok());
@@ -17,6 +18,7 @@ fn test_work_hours_validation_empty() {
         start: "".to_string(),
         end: "".to_string(),
         timezone: "".to_string(),
+        ..Default::default()
     };
 
     // Empty is acceptable (no validation)
@@ -29,6 +31,7 @@ fn test_work_hours_validation_invalid_format() {
         start: "9am".to_string(),
         end: "5pm".to_string(),
         timezone: "America/Los_Angeles".to_string(),
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -47,6 +50,7 @@ fn test_work_hours_validation_invalid_hour() {
         start: "25:00".to_string(),
         end: "17:00".to_string(),
         timezone: "America/Los_Angeles".to_string(),
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -59,6 +63,7 @@ fn test_work_hours_validation_start_after_end() {
         start: "17:00".to_string(),
         end: "09:00".to_string(),
         timezone: "America/Los_Angeles".to_string(),
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -77,6 +82,7 @@ fn test_work_hours_validation_equal_times() {
         start: "09:00".to_string(),
         end: "09:00".to_string(),
         timezone: "America/Los_Angeles".to_string(),
+        ..Default::default()
     };
 
     let result = config.validate();
@@ -89,6 +95,8 @@ fn test_focus_blocks_validation_valid() {
         duration_minutes: 45,
         interval_minutes: 15,
         teams_presence_sync: true,
+        buffer_minutes: 0,
+        ..FocusBlocksConfig::default()
     };
 
     assert!(config.validate().is_ok());
@@ -100,6 +108,8 @@ fn test_focus_blocks_validation_zero_duration() {
         duration_minutes: 0,
         interval_minutes: 15,
         teams_presence_sync: true,
+        buffer_minutes: 0,
+        ..FocusBlocksConfig::default()
     };
 
     let result = config.validate();
@@ -118,6 +128,8 @@ fn test_focus_blocks_validation_unusual_interval() {
         duration_minutes: 45,
         interval_minutes: 17, // Unusual value
         teams_presence_sync: true,
+        buffer_minutes: 0,
+        ..FocusBlocksConfig::default()
     };
 
     // Should succeed but print warning (we can't test stderr easily)
@@ -154,3 +166,36 @@ fn test_config_get_devops_pat_missing() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("PAT not found"));
 }
+
+#[test]
+fn test_graph_scopes_must_include_offline_access() {
+    assert!(GraphConfig::default().validate().is_ok());
+
+    let read_only = GraphConfig {
+        scopes: vec!["Calendars.Read".to_string(), "offline_access".to_string()],
+        ..GraphConfig::default()
+    };
+    assert!(read_only.validate().is_ok());
+
+    let no_refresh = GraphConfig {
+        scopes: vec!["Calendars.Read".to_string()],
+        ..GraphConfig::default()
+    };
+    let err = no_refresh.validate().unwrap_err();
+    assert!(err.to_string().contains("offline_access"));
+}
+
+#[test]
+fn test_focus_blocks_max_per_day_must_be_positive() {
+    let config = FocusBlocksConfig {
+        max_per_day: Some(0),
+        ..FocusBlocksConfig::default()
+    };
+    assert!(config.validate().is_err());
+
+    let config = FocusBlocksConfig {
+        max_per_day: Some(3),
+        ..FocusBlocksConfig::default()
+    };
+    assert!(config.validate().is_ok());
+}
@@ -0,0 +1,90 @@
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands::devops::{self, NewWorkItem};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+#[tokio::test]
+async fn test_create_posts_to_type_path_and_links_parent() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 10,
+            "rev": 1,
+            "fields": { "System.Title": "Parent story" },
+            "url": "http://mock/workItems/10"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workitems/$Bug"))
+        .and(body_string_contains(
+            "\"/fields/System.Title\",\"value\":\"Crash on save\"",
+        ))
+        .and(body_string_contains(
+            "\"/fields/System.Tags\",\"value\":\"ui; crash\"",
+        ))
+        .and(body_string_contains("System.LinkTypes.Hierarchy-Reverse"))
+        .and(body_string_contains("http://mock/workItems/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 77,
+            "rev": 1,
+            "fields": { "System.Title": "Crash on save" },
+            "url": "http://mock/workItems/77"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::create(
+            &config,
+            NewWorkItem {
+                work_item_type: "Bug".to_string(),
+                title: "Crash on save".to_string(),
+                parent: Some(10),
+                priority: Some(2),
+                tags: Some("ui, crash".to_string()),
+                ..Default::default()
+            },
+            false,
+            OutputFormat::Json,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "create failed: {:?}", result);
+}
+
+#[tokio::test]
+async fn test_create_rejects_invalid_priority() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::create(
+            &config,
+            NewWorkItem {
+                work_item_type: "Task".to_string(),
+                title: "Something".to_string(),
+                priority: Some(9),
+                ..Default::default()
+            },
+            false,
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.unwrap_err().to_string().contains("Priority"));
+}
@@ -1,6 +1,8 @@
 use ao_no_out7ook::devops::client::DevOpsClient;
+use ao_no_out7ook::devops::hierarchy::{self, HierarchyNode};
+use ao_no_out7ook::error::ApiError;
 use wiremock::matchers::{header_exists, method, path};
-use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 
 #[tokio::test]
 async fn test_get_work_item() {
@@ -41,3 +43,326 @@ async fn test_get_work_item() {
     assert_eq!(work_item.get_title(), Some("Mocked Task"));
     assert_eq!(work_item.get_state(), Some("Active"));
 }
+
+#[tokio::test]
+async fn test_get_work_items_batch_chunks_large_requests() {
+    let mock_server = MockServer::start().await;
+
+    // Echo back one work item per requested id, preserving request order
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(|req: &wiremock::Request| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            let ids = body["ids"].as_array().unwrap();
+            assert!(ids.len() <= 200, "Chunk exceeded API cap: {}", ids.len());
+            let value: Vec<_> = ids
+                .iter()
+                .map(|id| serde_json::json!({ "id": id, "rev": 1, "fields": {} }))
+                .collect();
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "count": value.len(), "value": value }))
+        })
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let ids: Vec<u32> = (1..=450).collect();
+    let expected = ids.clone();
+
+    let items = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        client.get_work_items_batch(&ids)
+    })
+    .await
+    .expect("Task failed")
+    .expect("Batch fetch failed");
+
+    assert_eq!(items.len(), 450);
+    let returned: Vec<u32> = items.iter().map(|i| i.id).collect();
+    assert_eq!(returned, expected);
+}
+
+#[tokio::test]
+async fn test_get_work_item_retries_service_unavailable() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/777"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .expect(2)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/777"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 777,
+            "rev": 1,
+            "fields": { "System.Title": "Flaky Task" },
+            "url": "http://mock/777"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let item = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        client.get_work_item(777)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(item.id, 777);
+}
+
+#[tokio::test]
+async fn test_get_work_item_does_not_retry_not_found() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitems/404"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        client.get_work_item(404)
+    })
+    .await
+    .unwrap();
+
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err.downcast_ref::<ApiError>(), Some(ApiError::NotFound(_))),
+        "Unexpected error: {:#}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_update_work_item_maps_statuses_to_api_errors() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/test_proj/_apis/wit/workitems/404"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("TF401232: not found"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/test_proj/_apis/wit/workitems/401"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let (missing, unauthorized) = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        (
+            client.update_work_item(404, vec![]).unwrap_err(),
+            client.update_work_item(401, vec![]).unwrap_err(),
+        )
+    })
+    .await
+    .unwrap();
+
+    match missing.downcast_ref::<ApiError>() {
+        Some(ApiError::NotFound(message)) => assert!(message.contains("TF401232")),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+    assert!(matches!(
+        unauthorized.downcast_ref::<ApiError>(),
+        Some(ApiError::Unauthorized { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_wiql_surfaces_server_error_message() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/wiql"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "$id": "1",
+            "innerException": null,
+            "message": "TF51005: The query references a field that does not exist. The error is caused by «[System.Bogus]».",
+            "typeName": "Microsoft.TeamFoundation.WorkItemTracking.Server.Common.WorkItemTrackingQueryException",
+            "errorCode": 0,
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        client.execute_wiql("SELECT [System.Id] FROM WorkItems WHERE [System.Bogus] = 1")
+    })
+    .await
+    .unwrap();
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.starts_with("WIQL API error (400"), "{}", message);
+    assert!(message.contains("TF51005"), "{}", message);
+    assert!(message.contains("[System.Bogus]"), "{}", message);
+    assert!(!message.contains("typeName"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_get_work_item_type_is_cached() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/test_proj/_apis/wit/workitemtypes/Task"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "Task",
+            "states": [
+                { "name": "New", "color": "b2b2b2", "category": "Proposed" },
+                { "name": "Active", "color": "007acc", "category": "InProgress" }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let (first, second) = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        let first = client.get_work_item_type("Task").unwrap();
+        let second = client.get_work_item_type("Task").unwrap();
+        (first, second)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(first.states.len(), 2);
+    assert_eq!(second.name, "Task");
+}
+
+/// Children per work item in a small fixture tree: 1 -> [3, 2], 2 -> [4], 3 -> [6, 5]
+fn fixture_children(id: u32) -> &'static [u32] {
+    match id {
+        1 => &[3, 2],
+        2 => &[4],
+        3 => &[6, 5],
+        _ => &[],
+    }
+}
+
+fn fixture_item(id: u32) -> serde_json::Value {
+    let relations: Vec<_> = fixture_children(id)
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "rel": "System.LinkTypes.Hierarchy-Forward",
+                "url": format!("http://mock/_apis/wit/workItems/{}", c)
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "id": id,
+        "rev": 1,
+        "fields": { "System.Title": format!("Item {}", id) },
+        "relations": relations
+    })
+}
+
+/// Answers workitemsbatch with the requested ids in reverse order
+struct BatchResponder;
+
+impl Respond for BatchResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        let items: Vec<_> = body["ids"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .rev()
+            .map(|id| fixture_item(id.as_u64().unwrap() as u32))
+            .collect();
+        ResponseTemplate::new(200)
+            .set_body_json(serde_json::json!({ "count": items.len(), "value": items }))
+    }
+}
+
+fn shape(node: &HierarchyNode) -> String {
+    let children: Vec<String> = node.children.iter().map(shape).collect();
+    if children.is_empty() {
+        node.item.id.to_string()
+    } else {
+        format!("{}({})", node.item.id, children.join(","))
+    }
+}
+
+#[tokio::test]
+async fn test_parallel_hierarchy_matches_sequential() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/test_proj/_apis/wit/workitemsbatch"))
+        .respond_with(BatchResponder)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let (sequential, parallel) = tokio::task::spawn_blocking(move || {
+        let client = DevOpsClient::new("test_pat", "test_org", "test_proj").with_base_url(&uri);
+        let root: ao_no_out7ook::devops::models::WorkItem =
+            serde_json::from_value(fixture_item(1)).unwrap();
+        (
+            hierarchy::build_tree_from_item(&client, root.clone(), 5).unwrap(),
+            hierarchy::build_tree_parallel(&client, root, 5, 4).unwrap(),
+        )
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(shape(&sequential), "1(2(4),3(5,6))");
+    assert_eq!(shape(&parallel), shape(&sequential));
+}
+
+#[tokio::test]
+async fn test_get_connection_data_parses_authenticated_user() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/_apis/connectionData"))
+        .and(header_exists("Authorization"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "authenticatedUser": {
+                "id": "6f3c0f9e-0000-0000-0000-000000000000",
+                "descriptor": "Microsoft.IdentityModel.Claims.ClaimsIdentity;me",
+                "providerDisplayName": "Me Myself",
+                "properties": {
+                    "Account": { "$type": "System.String", "$value": "me.myself@example.com" }
+                }
+            },
+            "instanceId": "00000000-0000-0000-0000-000000000000"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let data = tokio::task::spawn_blocking(move || {
+        DevOpsClient::new("test_pat", "test_org", "test_proj")
+            .with_base_url(&uri)
+            .get_connection_data()
+    })
+    .await
+    .unwrap()
+    .expect("connection data should parse");
+
+    let user = data.authenticated_user;
+    assert_eq!(user.display_name.as_deref(), Some("Me Myself"));
+    assert_eq!(user.property("Account"), Some("me.myself@example.com"));
+    assert_eq!(user.property("Mail"), None);
+    assert_eq!(user.unique_name(), Some("me.myself@example.com"));
+}
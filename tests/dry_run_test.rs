@@ -1,3 +1,4 @@
+use ao_no_out7ook::OutputFormat;
 use ao_no_out7ook::commands::task;
 use ao_no_out7ook::config::{Config, DevOpsConfig, StateConfig};
 use ao_no_out7ook::devops::models::WorkItem;
@@ -19,11 +20,14 @@ fn create_test_config() -> Config {
         skip_states: vec![],
         api_url: None,
         pace_api_url: None,
+        use_keyring: false,
+        ..DevOpsConfig::default()
     };
     // Default state config
     config.state = StateConfig {
         task_expiry_hours: 24,
         state_dir_override: None,
+        ..StateConfig::default()
     };
     config
 }
@@ -110,9 +114,19 @@ async fn test_start_dry_run_validates_without_starting() {
     // Note: We use the library function directly
     // CRITICAL: task::start uses reqwest::blocking which cannot run inside tokio runtime.
     // We must offload it to a blocking thread.
-    let result = tokio::task::spawn_blocking(move || task::start(&config, 123, true, false))
-        .await
-        .expect("Block execution failed");
+    let result = tokio::task::spawn_blocking(move || {
+        task::start(
+            &config,
+            123,
+            &task::StartOptions {
+                dry_run: true,
+                ..Default::default()
+            },
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .expect("Block execution failed");
 
     assert!(result.is_ok(), "Start command failed: {:?}", result.err());
 
@@ -0,0 +1,332 @@
+use ao_no_out7ook::commands::markdown;
+use serde_json::json;
+use std::fs;
+use tempfile::NamedTempFile;
+use wiremock::matchers::{body_partial_json, body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+#[tokio::test]
+async fn test_export_query_orders_epics_before_features() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains("([System.State] = 'Active')"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": [
+                { "id": 20, "url": "http://mock/workItems/20" },
+                { "id": 10, "url": "http://mock/workItems/10" }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Query returns the Feature first; export must still put the Epic on top
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 2,
+            "value": [
+                {
+                    "id": 20,
+                    "rev": 1,
+                    "fields": {
+                        "System.Title": "Checkout feature",
+                        "System.WorkItemType": "Feature",
+                        "System.State": "Active"
+                    },
+                    "url": "http://mock/workItems/20"
+                },
+                {
+                    "id": 10,
+                    "rev": 1,
+                    "fields": {
+                        "System.Title": "Payments epic",
+                        "System.WorkItemType": "Epic",
+                        "System.State": "Active"
+                    },
+                    "url": "http://mock/workItems/10"
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let file = NamedTempFile::new().unwrap();
+    let output = file.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::export(
+            &config,
+            vec![],
+            Some("[System.State] = 'Active'".to_string()),
+            None,
+            false,
+            &output,
+            false,
+        )
+    })
+    .await
+    .unwrap();
+    assert!(result.is_ok(), "export failed: {:?}", result);
+
+    let content = fs::read_to_string(file.path()).unwrap();
+    let epic = content.find("# Epic: Payments epic (#10)").unwrap();
+    let feature = content.find("## Feature: Checkout feature (#20)").unwrap();
+    assert!(epic < feature);
+}
+
+fn work_item(id: u32, title: &str, work_item_type: &str, children: &[u32]) -> serde_json::Value {
+    let relations: Vec<_> = children
+        .iter()
+        .map(|c| {
+            json!({
+                "rel": "System.LinkTypes.Hierarchy-Forward",
+                "url": format!("http://mock/_apis/wit/workItems/{}", c),
+                "attributes": {}
+            })
+        })
+        .collect();
+    json!({
+        "id": id,
+        "rev": 1,
+        "fields": {
+            "System.Title": title,
+            "System.WorkItemType": work_item_type,
+            "System.State": "New"
+        },
+        "relations": relations,
+        "url": format!("http://mock/_apis/wit/workItems/{}", id)
+    })
+}
+
+#[tokio::test]
+async fn test_export_hierarchy_includes_descendants_depth_first() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(work_item(
+            1,
+            "Platform",
+            "Epic",
+            &[2],
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(work_item(
+            2,
+            "Auth",
+            "Feature",
+            &[3],
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(json!({ "ids": [2] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 1,
+            "value": [work_item(2, "Auth", "Feature", &[3])]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workitemsbatch"))
+        .and(body_partial_json(json!({ "ids": [3] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 1,
+            "value": [work_item(3, "Login", "User Story", &[])]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // The Feature is both a requested root and a descendant of the Epic
+    let file = NamedTempFile::new().unwrap();
+    let output = file.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::export(&config, vec![2, 1], None, None, true, &output, false)
+    })
+    .await
+    .unwrap();
+    assert!(result.is_ok(), "export failed: {:?}", result);
+
+    let content = fs::read_to_string(file.path()).unwrap();
+    let epic = content.find("# Epic: Platform (#1)").unwrap();
+    let feature = content.find("## Feature: Auth (#2)").unwrap();
+    let story = content.find("### User Story: Login (#3)").unwrap();
+    assert!(epic < feature && feature < story);
+    assert_eq!(content.matches("Auth (#2)").count(), 1);
+}
+
+#[tokio::test]
+async fn test_export_since_adds_changed_date_clause() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains("[System.ChangedDate] >= '2026-01-05'"))
+        .and(body_string_contains("([System.State] = 'Active')"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let file = NamedTempFile::new().unwrap();
+    let output = file.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::export(
+            &config,
+            vec![],
+            Some("[System.State] = 'Active'".to_string()),
+            Some("2026-01-05"),
+            false,
+            &output,
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "export failed: {:?}", result);
+}
+
+#[test]
+fn test_export_since_rejects_bad_date() {
+    let config = create_test_config("http://127.0.0.1:9".to_string());
+    let file = NamedTempFile::new().unwrap();
+
+    let err = markdown::export(
+        &config,
+        vec![],
+        None,
+        Some("yesterday"),
+        false,
+        file.path(),
+        false,
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("YYYY-MM-DD"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_export_with_template_renders_each_item() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    for (id, title) in [(1, "Login"), (2, "Logout")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/test-project/_apis/wit/workitems/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item(
+                id,
+                title,
+                "Task",
+                &[],
+            )))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let template = NamedTempFile::new().unwrap();
+    fs::write(template.path(), "* {{id}}: {{title}} [{{state}}]\n").unwrap();
+    let file = NamedTempFile::new().unwrap();
+    let output = file.path().to_path_buf();
+    let template_path = template.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::export_with_options(
+            &config,
+            vec![1, 2],
+            None,
+            None,
+            &markdown::ExportOptions {
+                hierarchy: false,
+                output: Some(&output),
+                dry_run: false,
+                template: Some(&template_path),
+                json: None,
+            },
+        )
+    })
+    .await
+    .unwrap();
+    assert!(result.is_ok(), "export failed: {:?}", result);
+
+    assert_eq!(
+        fs::read_to_string(file.path()).unwrap(),
+        "* 1: Login [New]\n* 2: Logout [New]\n"
+    );
+}
+
+#[tokio::test]
+async fn test_export_json_writes_items_alongside_markdown() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    for (id, title) in [(1, "Login"), (2, "Logout")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/test-project/_apis/wit/workitems/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item(
+                id,
+                title,
+                "Task",
+                &[],
+            )))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let markdown_path = dir.path().join("work.md");
+    let json_path = dir.path().join("work.json");
+    let (output, json) = (markdown_path.clone(), json_path.clone());
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::export_with_options(
+            &config,
+            vec![1, 2],
+            None,
+            None,
+            &markdown::ExportOptions {
+                hierarchy: false,
+                output: Some(&output),
+                dry_run: false,
+                template: None,
+                json: Some(&json),
+            },
+        )
+    })
+    .await
+    .unwrap();
+    assert!(result.is_ok(), "export failed: {:?}", result);
+
+    let exported: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    let ids: Vec<u64> = exported
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(exported[0]["fields"]["System.Title"], "Login");
+    assert!(
+        fs::read_to_string(&markdown_path)
+            .unwrap()
+            .contains("Logout")
+    );
+}
@@ -0,0 +1,240 @@
+use ao_no_out7ook::commands::markdown;
+use serde_json::json;
+use std::fs;
+use tempfile::NamedTempFile;
+use wiremock::matchers::{body_string_contains, method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+#[tokio::test]
+async fn test_import_links_created_items_to_parents() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "rev": 1,
+            "fields": { "System.Title": "Existing Feature" },
+            "url": "http://mock/workItems/50"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Story links to the existing feature
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$User"))
+        .and(body_string_contains("System.LinkTypes.Hierarchy-Reverse"))
+        .and(body_string_contains("http://mock/workItems/50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 200,
+            "rev": 1,
+            "fields": { "System.Title": "Login flow" },
+            "url": "http://mock/workItems/200"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Task with parent #0 links to the story created just before it
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$Task"))
+        .and(body_string_contains("System.LinkTypes.Hierarchy-Reverse"))
+        .and(body_string_contains("http://mock/workItems/200"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 201,
+            "rev": 1,
+            "fields": { "System.Title": "Build form" },
+            "url": "http://mock/workItems/201"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let file = NamedTempFile::new().unwrap();
+    fs::write(
+        file.path(),
+        r#"### User Story: Login flow (#0)
+**State:** New | **Parent:** #50
+
+Users can sign in.
+
+#### Task: Build form (#0)
+**State:** New | **Parent:** #0
+"#,
+    )
+    .unwrap();
+
+    let path = file.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::import(&config, &path, false, false, false, None, false)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Import failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_import_creates_missing_parents_first() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    let created = |id: u32, title: &str| {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "id": id,
+            "rev": 1,
+            "fields": { "System.Title": title },
+            "url": format!("http://mock/workItems/{}", id)
+        }))
+    };
+
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$Epic"))
+        .respond_with(created(300, "Platform"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$Feature"))
+        .and(body_string_contains("http://mock/workItems/300"))
+        .respond_with(created(301, "Auth"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$User"))
+        .and(body_string_contains("http://mock/workItems/301"))
+        .respond_with(created(302, "Login flow"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Children are listed before the parents they link to
+    let file = NamedTempFile::new().unwrap();
+    fs::write(
+        file.path(),
+        r#"### User Story: Login flow (#0)
+**State:** New | **Parent:** #0
+
+## Feature: Auth (#0)
+**State:** New | **Parent:** #0
+
+# Epic: Platform (#0)
+**State:** New
+"#,
+    )
+    .unwrap();
+
+    let path = file.path().to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::import(&config, &path, false, false, false, None, true)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Import failed: {:?}", result.err());
+}
+
+/// An existing feature with a new story under it
+const MIXED_IMPORT: &str = r#"## Feature: Auth (#50)
+**State:** Active | **Parent:** #10
+
+### User Story: Login flow (#0)
+**State:** New | **Parent:** #0
+"#;
+
+#[tokio::test]
+async fn test_import_update_only_skips_new_items() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("PATCH"))
+        .and(path("/test-project/_apis/wit/workitems/50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "rev": 2,
+            "fields": { "System.Title": "Auth" },
+            "url": "http://mock/workItems/50"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), MIXED_IMPORT).unwrap();
+
+    let path = file.path().to_path_buf();
+    let options = markdown::ImportOptions {
+        mode: markdown::ImportMode::UpdateOnly,
+        ..Default::default()
+    };
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::import_with_options(&config, &path, &options)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Import failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_import_create_only_skips_existing_items() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    // The skipped feature is still the story's parent, linked by id
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 50,
+            "rev": 1,
+            "fields": { "System.Title": "Auth" },
+            "url": "http://mock/workItems/50"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path_regex(r"/test-project/_apis/wit/workitems/\$User"))
+        .and(body_string_contains("http://mock/workItems/50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 200,
+            "rev": 1,
+            "fields": { "System.Title": "Login flow" },
+            "url": "http://mock/workItems/200"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), MIXED_IMPORT).unwrap();
+
+    let path = file.path().to_path_buf();
+    let options = markdown::ImportOptions {
+        mode: markdown::ImportMode::CreateOnly,
+        ..Default::default()
+    };
+    let result = tokio::task::spawn_blocking(move || {
+        markdown::import_with_options(&config, &path, &options)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Import failed: {:?}", result.err());
+}
@@ -0,0 +1,141 @@
+use ao_no_out7ook::commands::devops::{self, LinkType};
+use serde_json::json;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::create_test_config;
+
+fn work_item(id: u32, relations: serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": id,
+        "rev": 3,
+        "fields": { "System.Title": format!("Item {}", id) },
+        "relations": relations,
+        "url": format!("http://mock/_apis/wit/workItems/{}", id)
+    })
+}
+
+#[test]
+fn test_link_type_rel_names() {
+    assert_eq!(LinkType::Related.rel(), "System.LinkTypes.Related");
+    assert_eq!(
+        LinkType::Predecessor.rel(),
+        "System.LinkTypes.Dependency-Reverse"
+    );
+    assert_eq!(
+        LinkType::Successor.rel(),
+        "System.LinkTypes.Dependency-Forward"
+    );
+    assert_eq!(LinkType::Parent.rel(), "System.LinkTypes.Hierarchy-Reverse");
+}
+
+#[tokio::test]
+async fn test_link_adds_relation_with_rel_for_each_type() {
+    for link_type in [
+        LinkType::Related,
+        LinkType::Predecessor,
+        LinkType::Successor,
+        LinkType::Parent,
+    ] {
+        let mock_server = MockServer::start().await;
+        let config = create_test_config(mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/test-project/_apis/wit/workitems/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item(1, json!([]))))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test-project/_apis/wit/workitems/2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item(2, json!([]))))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/test-project/_apis/wit/workitems/1"))
+            .and(body_json(json!([{
+                "op": "add",
+                "path": "/relations/-",
+                "value": {
+                    "rel": link_type.rel(),
+                    "url": "http://mock/_apis/wit/workItems/2"
+                }
+            }])))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item(1, json!([]))))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = tokio::task::spawn_blocking(move || {
+            devops::link(&config, 1, 2, link_type, false, false)
+        })
+        .await
+        .unwrap();
+
+        assert!(result.is_ok(), "{:?}: {:?}", link_type, result.err());
+    }
+}
+
+#[tokio::test]
+async fn test_link_remove_deletes_matching_relation_by_index() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    let relations = json!([
+        { "rel": "System.LinkTypes.Related", "url": "http://mock/_apis/wit/workItems/7", "attributes": {} },
+        { "rel": "System.LinkTypes.Dependency-Reverse", "url": "http://mock/_apis/wit/workItems/2", "attributes": {} },
+        { "rel": "System.LinkTypes.Related", "url": "http://mock/_apis/wit/workItems/2", "attributes": {} }
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(work_item(1, relations)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/test-project/_apis/wit/workitems/1"))
+        .and(body_json(
+            json!([{ "op": "remove", "path": "/relations/2" }]),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(work_item(1, json!([]))))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::link(&config, 1, 2, LinkType::Related, true, false)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_link_remove_missing_relation_is_an_error() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(work_item(1, json!([]))))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::link(&config, 1, 2, LinkType::Successor, true, false)
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("no successor link")
+    );
+}
@@ -1,22 +1,13 @@
-use ao_no_out7ook::commands::devops;
-use ao_no_out7ook::config::{Config, DevOpsConfig};
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands::devops::{self, ListFilters, ListSort, WiqlDate};
+use ao_no_out7ook::config::{Config, StateConfig};
+use ao_no_out7ook::state::SavedQuery;
 use serde_json::json;
-use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::matchers::{body_string_contains, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-#[allow(clippy::field_reassign_with_default)]
-fn create_test_config(api_url: String) -> Config {
-    let mut config = Config::default();
-    config.devops = DevOpsConfig {
-        pat: Some("test-pat".to_string()),
-        organization: "test-org".to_string(),
-        project: "test-project".to_string(),
-        skip_states: vec![],
-        api_url: Some(api_url),
-        pace_api_url: None,
-    };
-    config
-}
+mod common;
+use common::create_test_config;
 
 #[tokio::test]
 async fn test_list_with_search_term() {
@@ -40,11 +31,12 @@ async fn test_list_with_search_term() {
     let result = tokio::task::spawn_blocking(move || {
         devops::list(
             &config,
-            None,
-            None,
-            Some("login".to_string()),
-            None,
+            ListFilters {
+                search: Some("login".to_string()),
+                ..Default::default()
+            },
             Some(50),
+            OutputFormat::Text,
         )
     })
     .await
@@ -75,11 +67,12 @@ async fn test_list_with_tags_filter() {
     let result = tokio::task::spawn_blocking(move || {
         devops::list(
             &config,
-            None,
-            None,
-            None,
-            Some("urgent".to_string()),
+            ListFilters {
+                tags: Some("urgent".to_string()),
+                ..Default::default()
+            },
             Some(50),
+            OutputFormat::Text,
         )
     })
     .await
@@ -107,7 +100,12 @@ async fn test_list_sort_by_priority() {
         .await;
 
     let result = tokio::task::spawn_blocking(move || {
-        devops::list(&config, None, None, None, None, Some(50))
+        devops::list(
+            &config,
+            ListFilters::default(),
+            Some(50),
+            OutputFormat::Text,
+        )
     })
     .await
     .unwrap();
@@ -130,6 +128,7 @@ async fn test_list_sort_by_changed_date() {
         .and(body_string_contains("ORDER BY"))
         .and(body_string_contains("System.ChangedDate"))
         .and(body_string_contains("DESC"))
+        .and(body_string_contains("ORDER BY [System.ChangedDate] DESC"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
             "queryType": "flat",
             "workItems": []
@@ -139,8 +138,15 @@ async fn test_list_sort_by_changed_date() {
         .await;
 
     let result = tokio::task::spawn_blocking(move || {
-        // Add sort parameter when we implement it
-        devops::list_with_sort(&config, None, None, None, None, "changed", Some(50))
+        devops::list_with_sort(
+            &config,
+            ListFilters::default(),
+            Some(ListSort::Changed),
+            Some(50),
+            &[],
+            false,
+            OutputFormat::Text,
+        )
     })
     .await
     .unwrap();
@@ -173,11 +179,14 @@ async fn test_list_combined_filters() {
     let result = tokio::task::spawn_blocking(move || {
         devops::list(
             &config,
-            Some("Active".to_string()),
-            None,
-            Some("login".to_string()),
-            Some("backend".to_string()),
+            ListFilters {
+                state: Some("Active".to_string()),
+                search: Some("login".to_string()),
+                tags: Some("backend".to_string()),
+                ..Default::default()
+            },
             Some(50),
+            OutputFormat::Text,
         )
     })
     .await
@@ -186,6 +195,293 @@ async fn test_list_combined_filters() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_list_with_iteration_and_area() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains(
+            r"[System.IterationPath] UNDER 'Proj\\Sprint 12'",
+        ))
+        .and(body_string_contains(
+            r"[System.AreaPath] UNDER 'Proj\\Team''s Area'",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters {
+                iteration: Some(r"Proj\Sprint 12".to_string()),
+                area: Some(r"Proj\Team's Area".to_string()),
+                ..Default::default()
+            },
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_list_exclude_state_adds_not_equal_clause_per_state() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains("[System.State] <> 'Done'"))
+        .and(body_string_contains("[System.State] <> 'Won''t Fix'"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters {
+                exclude_states: vec!["Done".to_string(), "Won't Fix".to_string()],
+                ..Default::default()
+            },
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_list_created_after_adds_created_date_clause() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains("[System.CreatedDate] >= '2026-01-05'"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters {
+                created_since: Some(WiqlDate::parse("2026-01-05").unwrap()),
+                ..Default::default()
+            },
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_list_changed_after_timestamp_uses_time_precision() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(query_param("timePrecision", "true"))
+        .and(body_string_contains(
+            "[System.ChangedDate] >= '2026-01-05T07:30:00Z'",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters {
+                changed_since: Some(WiqlDate::parse("2026-01-05T09:30:00+02:00").unwrap()),
+                ..Default::default()
+            },
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_wiql_date_rejects_invalid_input() {
+    assert!(WiqlDate::parse("2026-13-01").is_err());
+    assert!(WiqlDate::parse("last week").is_err());
+    assert!(matches!(
+        WiqlDate::parse("2026-01-05T09:00"),
+        Ok(WiqlDate::Instant(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_list_excludes_skip_states_by_default() {
+    let mock_server = MockServer::start().await;
+    let mut config = create_test_config(mock_server.uri());
+    config.devops.skip_states = vec!["Closed".to_string(), "Completed".to_string()];
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains(
+            "[System.State] NOT IN ('Closed', 'Completed')",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters::default(),
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_list_state_filter_keeps_skipped_state() {
+    let mock_server = MockServer::start().await;
+    let mut config = create_test_config(mock_server.uri());
+    config.devops.skip_states = vec!["Closed".to_string(), "Completed".to_string()];
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .and(body_string_contains("[System.State] = 'Closed'"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters {
+                state: Some("Closed".to_string()),
+                ..Default::default()
+            },
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok());
+    let requests = mock_server.received_requests().await.unwrap();
+    let body = String::from_utf8_lossy(&requests[0].body);
+    assert!(!body.contains("NOT IN"), "{}", body);
+}
+
+#[tokio::test]
+async fn test_list_text_with_multibyte_title() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/wiql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "queryType": "flat",
+            "workItems": [{ "id": 101, "url": "http://mock/101" }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // Byte 45 of this title lands inside the first emoji
+    let title = format!(
+        "{}🚀🚀🚀 café rollout and more trailing words",
+        "x".repeat(44)
+    );
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workitemsbatch"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 1,
+            "value": [{
+                "id": 101,
+                "rev": 1,
+                "fields": {
+                    "System.Title": title,
+                    "System.State": "Active",
+                    "System.WorkItemType": "Task"
+                },
+                "url": "http://mock/101"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::list(
+            &config,
+            ListFilters::default(),
+            Some(50),
+            OutputFormat::Text,
+        )
+    })
+    .await
+    .expect("list should not panic on multibyte titles");
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_include_closed_keeps_skip_states() {
+    let skip_states = vec!["Closed".to_string()];
+    let filters = ListFilters {
+        include_closed: true,
+        ..Default::default()
+    };
+
+    let wiql = filters.to_wiql(None, &skip_states);
+
+    assert!(!wiql.contains("NOT IN"));
+    assert!(wiql.contains("[System.State] <> 'Removed'"));
+}
+
 #[test]
 fn test_search_term_sql_injection_prevention() {
     // Test that search terms with single quotes are escaped
@@ -199,3 +495,49 @@ fn test_search_term_sql_injection_prevention() {
     assert!(!search_term.contains("''")); // Original had single quotes
     assert!(escaped.contains("''")); // Escaped has doubled quotes
 }
+
+#[test]
+fn test_saved_query_round_trips_filters() {
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        state: StateConfig {
+            state_dir_override: Some(state_dir.path().to_path_buf()),
+            ..StateConfig::default()
+        },
+        ..Config::default()
+    };
+    let query = SavedQuery {
+        state: Some("Active".to_string()),
+        exclude_states: vec!["Blocked".to_string()],
+        assigned_to: Some("me".to_string()),
+        tags: Some("backend".to_string()),
+        iteration: Some(r"Proj\Sprint 12".to_string()),
+        include_closed: true,
+        changed_after: Some("2026-01-05".to_string()),
+        sort: Some(ListSort::Changed.name()),
+        ..SavedQuery::default()
+    };
+
+    devops::save_query(&config, "active", query.clone()).unwrap();
+    let loaded = devops::saved_query(&config, "active").unwrap();
+    assert_eq!(loaded, query);
+
+    let filters = ListFilters::from_saved(&loaded).unwrap();
+    assert_eq!(filters.state.as_deref(), Some("Active"));
+    assert_eq!(filters.exclude_states, vec!["Blocked".to_string()]);
+    assert_eq!(filters.assigned_to.as_deref(), Some("me"));
+    assert_eq!(filters.tags.as_deref(), Some("backend"));
+    assert_eq!(filters.iteration.as_deref(), Some(r"Proj\Sprint 12"));
+    assert!(filters.include_closed);
+    assert_eq!(
+        filters.changed_since,
+        Some(WiqlDate::parse("2026-01-05").unwrap())
+    );
+    assert_eq!(
+        ListSort::from_name(loaded.sort.as_deref().unwrap()).unwrap(),
+        ListSort::Changed
+    );
+
+    let err = devops::saved_query(&config, "missing").unwrap_err();
+    assert!(err.to_string().contains("Saved queries: active"), "{}", err);
+}
@@ -1,6 +1,9 @@
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands;
+use ao_no_out7ook::config::{Config, DevOpsConfig, PaceConfig};
 use ao_no_out7ook::pace::client::PaceClient;
 use chrono::Utc;
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_string_contains, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -54,13 +57,60 @@ async fn test_stop_timer_success() {
     })
     .await
     .unwrap()
-    .unwrap();
+    .unwrap()
+    .expect("worklog details should be parsed");
 
     assert_eq!(response.worklog_id, 789);
     assert_eq!(response.duration, 3600);
     assert_eq!(response.work_item_id, 456);
 }
 
+#[tokio::test]
+async fn test_stop_timer_tolerates_empty_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let response = tokio::task::spawn_blocking(move || {
+        let client = PaceClient::new("TEST_PAT", "test-org").with_base_url(&uri);
+        client.stop_timer(0)
+    })
+    .await
+    .unwrap()
+    .expect("an empty body still means the timer stopped");
+
+    assert!(response.is_none());
+}
+
+#[tokio::test]
+async fn test_stop_timer_tolerates_null_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("null"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let response = tokio::task::spawn_blocking(move || {
+        let client = PaceClient::new("TEST_PAT", "test-org").with_base_url(&uri);
+        client.stop_timer(0)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert!(response.is_none());
+}
+
 #[tokio::test]
 async fn test_get_current_timer_active() {
     let mock_server = MockServer::start().await;
@@ -133,7 +183,7 @@ async fn test_create_worklog_success() {
     let uri = mock_server.uri();
     let worklog = tokio::task::spawn_blocking(move || {
         let client = PaceClient::new("TEST_PAT", "test-org").with_base_url(&uri);
-        client.create_worklog(123, 7200, Some("Manual entry".to_string()))
+        client.create_worklog(123, 7200, Some("Manual entry".to_string()), None)
     })
     .await
     .unwrap()
@@ -188,3 +238,244 @@ async fn test_get_worklogs_success() {
     assert_eq!(worklogs[1].id, 2);
     assert_eq!(worklogs[1].duration, 3600);
 }
+
+#[tokio::test]
+async fn test_pace_stop_stops_untracked_timer() {
+    let mock_server = MockServer::start().await;
+
+    // Timer started from the 7Pace web UI, unknown to local state
+    Mock::given(method("GET"))
+        .and(path("/_apis/api/tracking/client/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "timer-web",
+            "workItemId": 321,
+            "startedAt": "2026-01-07T17:00:00Z",
+            "comment": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "worklogId": 999,
+            "duration": 1800,
+            "workItemId": 321
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = Config {
+        devops: DevOpsConfig {
+            pat: Some("TEST_PAT".to_string()),
+            organization: "test-org".to_string(),
+            pace_api_url: Some(mock_server.uri()),
+            use_keyring: false,
+            ..DevOpsConfig::default()
+        },
+        ..Config::default()
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        commands::pace::stop_timer(&config, 2, OutputFormat::Json)
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "pace stop failed: {:?}", result);
+}
+
+#[tokio::test]
+async fn test_client_uses_configured_pace_host() {
+    let mock_server = MockServer::start().await;
+
+    // Self-hosted instance: organization is appended to the custom host
+    Mock::given(method("GET"))
+        .and(path("/acme/_apis/api/tracking/client/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(null)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let devops = DevOpsConfig {
+        organization: "acme".to_string(),
+        pace_host: format!("{}/", mock_server.uri()),
+        ..DevOpsConfig::default()
+    };
+    assert_eq!(
+        devops.pace_base_url(),
+        format!("{}/acme", mock_server.uri())
+    );
+
+    let timer = tokio::task::spawn_blocking(move || {
+        PaceClient::from_config(&devops, &PaceConfig::default(), "TEST_PAT").get_current_timer()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert!(timer.is_none());
+}
+
+#[test]
+fn test_pace_api_url_overrides_host() {
+    let devops = DevOpsConfig {
+        organization: "acme".to_string(),
+        pace_host: "https://pace.example.com".to_string(),
+        pace_api_url: Some("http://localhost:9999".to_string()),
+        ..DevOpsConfig::default()
+    };
+    assert_eq!(devops.pace_base_url(), "http://localhost:9999");
+    assert_eq!(
+        DevOpsConfig {
+            organization: "acme".to_string(),
+            ..DevOpsConfig::default()
+        }
+        .pace_base_url(),
+        "https://api.timehub.7pace.com/acme"
+    );
+}
+
+#[tokio::test]
+async fn test_log_time_sends_backdated_timestamp() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/worklogs"))
+        .and(body_string_contains(
+            r#""timestamp":"2026-01-05T09:00:00Z""#,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 1000,
+            "workItemId": 123,
+            "userId": "user-123",
+            "duration": 3600,
+            "timestamp": "2026-01-05T09:00:00Z",
+            "comment": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = Config {
+        devops: DevOpsConfig {
+            pat: Some("TEST_PAT".to_string()),
+            organization: "test-org".to_string(),
+            pace_api_url: Some(mock_server.uri()),
+            ..DevOpsConfig::default()
+        },
+        ..Config::default()
+    };
+
+    tokio::task::spawn_blocking(move || {
+        commands::pace::log_time(
+            &config,
+            123,
+            3600,
+            None,
+            Some("2026-01-05T09:00:00Z"),
+            false,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_create_worklogs_attempts_every_entry() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/worklogs"))
+        .and(body_string_contains("\"workItemId\":2"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    for id in [1, 3] {
+        Mock::given(method("POST"))
+            .and(path("/_apis/worklogs"))
+            .and(body_string_contains(format!("\"workItemId\":{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 900 + id,
+                "workItemId": id,
+                "userId": "user-123",
+                "duration": 1800,
+                "timestamp": "2026-01-07T18:00:00Z"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let uri = mock_server.uri();
+    let results = tokio::task::spawn_blocking(move || {
+        let client = PaceClient::new("TEST_PAT", "test-org")
+            .with_base_url(&uri)
+            .with_max_retries(0)
+            .with_request_delay(std::time::Duration::from_millis(10));
+        client.create_worklogs(&[(1, 1800, None), (2, 1800, None), (3, 1800, None)])
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().id, 901);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().id, 903);
+}
+
+#[tokio::test]
+async fn test_from_config_applies_pace_retry_count() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/worklogs"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let devops = DevOpsConfig {
+        pace_api_url: Some(mock_server.uri()),
+        ..DevOpsConfig::default()
+    };
+    let pace = PaceConfig {
+        request_delay_ms: 1,
+        max_retries: 1,
+    };
+    let results = tokio::task::spawn_blocking(move || {
+        PaceClient::from_config(&devops, &pace, "TEST_PAT").create_worklogs(&[(1, 1800, None)])
+    })
+    .await
+    .unwrap();
+
+    assert!(results[0].is_err());
+}
+
+#[tokio::test]
+async fn test_create_worklogs_does_not_retry_rejected_entry() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/worklogs"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let results = tokio::task::spawn_blocking(move || {
+        PaceClient::new("TEST_PAT", "test-org")
+            .with_base_url(&uri)
+            .with_max_retries(3)
+            .create_worklogs(&[(1, 1800, None)])
+    })
+    .await
+    .unwrap();
+
+    assert!(results[0].is_err());
+}
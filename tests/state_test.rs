@@ -1,4 +1,4 @@
-use ao_no_out7ook::state::{State, with_state_lock};
+use ao_no_out7ook::state::{CurrentTask, State, with_state_lock, with_state_lock_timeout};
 use std::thread;
 use std::time::Duration;
 use tempfile::tempdir;
@@ -65,3 +65,85 @@ fn test_concurrent_lock() {
     let final_state = State::load(&state_path).unwrap();
     assert_eq!(final_state.version, "updated");
 }
+
+#[test]
+fn test_lock_times_out_instead_of_hanging() {
+    let dir = tempdir().unwrap();
+    let state_path = dir.path().join("state.json");
+    let lock_path = dir.path().join("state.lock");
+
+    let lock_path_clone = lock_path.clone();
+    let state_path_clone = state_path.clone();
+
+    // Hold the lock well past the second caller's timeout
+    let handle = thread::spawn(move || {
+        with_state_lock(&lock_path_clone, &state_path_clone, |_| {
+            thread::sleep(Duration::from_millis(1000));
+            Ok(())
+        })
+        .unwrap();
+    });
+
+    thread::sleep(Duration::from_millis(100));
+
+    let start = std::time::Instant::now();
+    let result =
+        with_state_lock_timeout(&lock_path, &state_path, Duration::from_millis(200), |_| {
+            Ok(())
+        });
+
+    let err = result.unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("locked by another process"),
+        "unexpected error: {:#}",
+        err
+    );
+    assert!(start.elapsed() < Duration::from_millis(800));
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_state_without_history_loads() {
+    let dir = tempdir().unwrap();
+    let state_path = dir.path().join("state.json");
+
+    // State file written before task history existed
+    std::fs::write(
+        &state_path,
+        r#"{
+            "version": "1.0.0",
+            "current_task": null,
+            "last_sync": { "devops": null, "sevenpace": null, "calendar": null },
+            "work_hours": { "start": "", "end": "" }
+        }"#,
+    )
+    .unwrap();
+
+    let loaded = State::load(&state_path).unwrap();
+    assert!(loaded.history.is_empty());
+}
+
+#[test]
+fn test_finish_current_task_records_history() {
+    let now = chrono::Utc::now();
+    let mut state = State {
+        current_task: Some(CurrentTask {
+            id: 42,
+            title: "Write docs".to_string(),
+            started_at: now - chrono::Duration::hours(1),
+            expires_at: now + chrono::Duration::hours(23),
+            timer_id: None,
+            paused_at: None,
+        }),
+        ..State::default()
+    };
+
+    let finished = state.finish_current_task(now);
+
+    assert_eq!(finished.map(|t| t.id), Some(42));
+    assert!(state.current_task.is_none());
+    assert_eq!(state.history.len(), 1);
+    assert_eq!(state.history[0].id, 42);
+    assert_eq!(state.history[0].stopped_at, now);
+}
@@ -0,0 +1,288 @@
+use ao_no_out7ook::OutputFormat;
+use ao_no_out7ook::commands::task;
+use ao_no_out7ook::config::Config;
+use ao_no_out7ook::state::{CurrentTask, State};
+use chrono::Utc;
+use serde_json::json;
+use tempfile::TempDir;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+fn create_test_config(pace_url: String, state_dir: &TempDir) -> Config {
+    let mut config = common::create_test_config(pace_url.clone());
+    config.devops.pace_api_url = Some(pace_url);
+    config.state.state_dir_override = Some(state_dir.path().to_path_buf());
+    config
+}
+
+fn seed_current_task(state_dir: &TempDir, timer_id: Option<&str>) {
+    let now = Utc::now();
+    let state = State {
+        current_task: Some(CurrentTask {
+            id: 101,
+            title: "Task 101".to_string(),
+            started_at: now,
+            expires_at: now + chrono::Duration::hours(24),
+            timer_id: timer_id.map(|s| s.to_string()),
+            paused_at: None,
+        }),
+        ..State::default()
+    };
+    state.save(state_dir.path().join("state.json")).unwrap();
+}
+
+#[tokio::test]
+async fn test_stop_calls_pace_and_clears_state() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let config = create_test_config(mock_server.uri(), &state_dir);
+    seed_current_task(&state_dir, Some("timer-123"));
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "worklogId": 999,
+            "duration": 3600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || task::stop(&config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok(), "Stop failed: {:?}", result.err());
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    assert!(state.current_task.is_none());
+    assert_eq!(state.history.last().map(|e| e.id), Some(101));
+}
+
+#[tokio::test]
+async fn test_stop_failure_keeps_state() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let config = create_test_config(mock_server.uri(), &state_dir);
+    seed_current_task(&state_dir, Some("timer-123"));
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || task::stop(&config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+
+    assert!(result.is_err());
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    assert_eq!(state.current_task.map(|t| t.id), Some(101));
+}
+
+#[tokio::test]
+async fn test_stop_without_timer_skips_pace() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let config = create_test_config(mock_server.uri(), &state_dir);
+    seed_current_task(&state_dir, None);
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || task::stop(&config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok());
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    assert!(state.current_task.is_none());
+}
+
+#[tokio::test]
+async fn test_start_sends_comment_to_pace() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let mut config = create_test_config(mock_server.uri(), &state_dir);
+    config.devops.api_url = Some(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitems/101"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 101,
+            "rev": 1,
+            "fields": { "System.Title": "Task 101" },
+            "url": "http://mock/101"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/_apis/api/tracking/client/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::Value::Null))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/startTracking"))
+        .and(body_partial_json(json!({
+            "workItemId": 101,
+            "comment": "Pairing on the login bug"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "timer-1",
+            "startedAt": "2026-01-01T12:00:00Z",
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        task::start(
+            &config,
+            101,
+            &task::StartOptions {
+                comment: Some("Pairing on the login bug".to_string()),
+                ..Default::default()
+            },
+            OutputFormat::Json,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Start failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_keep_current_task() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let config = create_test_config(mock_server.uri(), &state_dir);
+    seed_current_task(&state_dir, Some("timer-123"));
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/stopTracking/0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "worklogId": 999,
+            "duration": 600,
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/startTracking"))
+        .and(body_partial_json(json!({ "workItemId": 101 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "timer-456",
+            "startedAt": "2026-01-01T12:00:00Z",
+            "workItemId": 101
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pause_config = config.clone();
+    let result =
+        tokio::task::spawn_blocking(move || task::pause(&pause_config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+    assert!(result.is_ok(), "Pause failed: {:?}", result.err());
+
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    let paused = state.current_task.expect("task should stay current");
+    assert_eq!(paused.id, 101);
+    assert!(paused.paused_at.is_some());
+    assert!(paused.timer_id.is_none());
+    assert!(state.history.is_empty());
+
+    let result =
+        tokio::task::spawn_blocking(move || task::resume(&config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+    assert!(result.is_ok(), "Resume failed: {:?}", result.err());
+
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    let resumed = state.current_task.expect("task should stay current");
+    assert_eq!(resumed.id, 101);
+    assert!(resumed.paused_at.is_none());
+    assert_eq!(resumed.timer_id.as_deref(), Some("timer-456"));
+}
+
+#[tokio::test]
+async fn test_resume_without_pause_skips_pace() {
+    let mock_server = MockServer::start().await;
+    let state_dir = TempDir::new().unwrap();
+    let config = create_test_config(mock_server.uri(), &state_dir);
+    seed_current_task(&state_dir, Some("timer-123"));
+
+    Mock::given(method("POST"))
+        .and(path("/_apis/api/tracking/client/startTracking"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || task::resume(&config, false, OutputFormat::Text))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok());
+    let state = State::load(state_dir.path().join("state.json")).unwrap();
+    assert_eq!(
+        state.current_task.and_then(|t| t.timer_id).as_deref(),
+        Some("timer-123")
+    );
+}
+
+#[test]
+fn test_focus_block_event_uses_overridden_duration() {
+    let state_dir = TempDir::new().unwrap();
+    let mut config = create_test_config("http://unused".to_string(), &state_dir);
+    config.work_hours.start = "09:00".to_string();
+    config.work_hours.end = "17:00".to_string();
+    config.work_hours.timezone = "UTC".to_string();
+    config.focus_blocks.buffer_minutes = 0;
+    // A Tuesday morning, inside work hours
+    let now = chrono::DateTime::parse_from_rfc3339("2026-01-06T09:50:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+
+    let default = task::focus_block_event(&config, 101, "Big refactor", &[], now, None).unwrap();
+    let deep = task::focus_block_event(&config, 101, "Big refactor", &[], now, Some(90)).unwrap();
+
+    let at = |value: &str| {
+        chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+            .unwrap()
+            .and_utc()
+    };
+    let span = |event: &ao_no_out7ook::graph::models::CalendarEvent| {
+        at(&event.end.date_time) - at(&event.start.date_time)
+    };
+    assert_eq!(
+        span(&default),
+        chrono::Duration::minutes(config.focus_blocks.duration_minutes.into())
+    );
+    assert_eq!(span(&deep), chrono::Duration::minutes(90));
+    assert_eq!(
+        at(&deep.start.date_time),
+        at("2026-01-06T10:00:00"),
+        "Starts at the next interval"
+    );
+    assert_eq!(deep.subject, "🎯 Focus: 101 - Big refactor");
+}
@@ -1,22 +1,12 @@
-use ao_no_out7ook::commands::devops;
-use ao_no_out7ook::config::{Config, DevOpsConfig};
+use ao_no_out7ook::commands::devops::{self, WorkItemUpdate};
 use serde_json::json;
-use wiremock::matchers::{method, path_regex};
+use wiremock::matchers::{
+    body_partial_json, body_string_contains, method, path, path_regex, query_param,
+};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-#[allow(clippy::field_reassign_with_default)]
-fn create_test_config(api_url: String) -> Config {
-    let mut config = Config::default();
-    config.devops = DevOpsConfig {
-        pat: Some("test-pat".to_string()),
-        organization: "test-org".to_string(),
-        project: "test-project".to_string(),
-        skip_states: vec![],
-        api_url: Some(api_url),
-        pace_api_url: None,
-    };
-    config
-}
+mod common;
+use common::create_test_config;
 
 #[tokio::test]
 async fn test_update_assigned_to() {
@@ -57,9 +47,10 @@ async fn test_update_assigned_to() {
         devops::update(
             &config,
             123,
-            Some("testuser@example.com".to_string()),
-            None,
-            None,
+            WorkItemUpdate {
+                assigned_to: Some("testuser@example.com".to_string()),
+                ..Default::default()
+            },
             false,
         )
     })
@@ -100,7 +91,15 @@ async fn test_update_priority() {
         .await;
 
     let result = tokio::task::spawn_blocking(move || {
-        devops::update(&config, 123, None, Some(1), None, false)
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                priority: Some(1),
+                ..Default::default()
+            },
+            false,
+        )
     })
     .await
     .unwrap();
@@ -142,9 +141,10 @@ async fn test_update_tags() {
         devops::update(
             &config,
             123,
-            None,
-            None,
-            Some("urgent,backend".to_string()),
+            WorkItemUpdate {
+                tags: Some("urgent,backend".to_string()),
+                ..Default::default()
+            },
             false,
         )
     })
@@ -188,9 +188,11 @@ async fn test_update_multiple_fields() {
         devops::update(
             &config,
             123,
-            Some("testuser@example.com".to_string()),
-            Some(2),
-            None,
+            WorkItemUpdate {
+                assigned_to: Some("testuser@example.com".to_string()),
+                priority: Some(2),
+                ..Default::default()
+            },
             false,
         )
     })
@@ -200,6 +202,88 @@ async fn test_update_multiple_fields() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_update_effort_and_remaining_work_in_one_patch() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_string_contains(
+            r#""path":"/fields/Microsoft.VSTS.Scheduling.Effort","value":5.0"#,
+        ))
+        .and(body_string_contains(
+            r#""path":"/fields/Microsoft.VSTS.Scheduling.RemainingWork","value":2.5"#,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                effort: Some(5.0),
+                remaining_work: Some(2.5),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "update failed: {:?}", result);
+}
+
+#[tokio::test]
+async fn test_update_rejects_negative_remaining_work() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                remaining_work: Some(-1.0),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.unwrap_err().to_string().contains("--remaining-work"));
+}
+
 #[tokio::test]
 async fn test_update_dry_run() {
     let mock_server = MockServer::start().await;
@@ -231,9 +315,11 @@ async fn test_update_dry_run() {
         devops::update(
             &config,
             123,
-            Some("test@example.com".to_string()),
-            Some(1),
-            None,
+            WorkItemUpdate {
+                assigned_to: Some("test@example.com".to_string()),
+                priority: Some(1),
+                ..Default::default()
+            },
             true,
         )
     })
@@ -267,3 +353,689 @@ fn test_tags_formatting() {
     let formatted = input.split(',').collect::<Vec<_>>().join("; ");
     assert_eq!(formatted, expected_output);
 }
+
+#[test]
+fn test_merge_tags_add_and_remove() {
+    let existing = vec!["urgent".to_string(), "Backend".to_string()];
+
+    assert_eq!(
+        devops::merge_tags(&existing, "frontend, backend", ""),
+        "urgent; Backend; frontend"
+    );
+    assert_eq!(devops::merge_tags(&existing, "", "URGENT"), "Backend");
+    assert_eq!(
+        devops::merge_tags(&existing, "api", "urgent,missing"),
+        "Backend; api"
+    );
+}
+
+async fn assert_incremental_tags(existing: &str, changes: WorkItemUpdate, expected: &str) {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Tags": existing }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_string_contains(format!(
+            r#""path":"/fields/System.Tags","value":"{}""#,
+            expected
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.Tags": expected }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    tokio::task::spawn_blocking(move || devops::update(&config, 123, changes, false))
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_update_tags_add_keeps_existing() {
+    assert_incremental_tags(
+        "urgent; backend",
+        WorkItemUpdate {
+            tags_add: Some("frontend,urgent".to_string()),
+            ..Default::default()
+        },
+        "urgent; backend; frontend",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_update_tags_remove_keeps_rest() {
+    assert_incremental_tags(
+        "urgent; backend; frontend",
+        WorkItemUpdate {
+            tags_add: Some("api".to_string()),
+            tags_remove: Some("Urgent".to_string()),
+            ..Default::default()
+        },
+        "backend; frontend; api",
+    )
+    .await;
+}
+
+#[test]
+fn test_update_rejects_tags_with_incremental_edits() {
+    let config = create_test_config("http://127.0.0.1:9".to_string());
+    let err = devops::update(
+        &config,
+        123,
+        WorkItemUpdate {
+            tags: Some("a".to_string()),
+            tags_remove: Some("b".to_string()),
+            ..Default::default()
+        },
+        false,
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("--tags-add/--tags-remove"));
+}
+
+#[tokio::test]
+async fn test_update_resolves_display_name() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/_apis/identities"))
+        .and(query_param("filterValue", "Jane Smith"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 1,
+            "value": [{
+                "descriptor": "Microsoft.IdentityModel.Claims.ClaimsIdentity;jane",
+                "providerDisplayName": "Jane Smith",
+                "properties": {
+                    "Account": { "$type": "System.String", "$value": "jane@example.com" }
+                }
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_partial_json(json!([{
+            "op": "add",
+            "path": "/fields/System.AssignedTo",
+            "value": "jane@example.com"
+        }])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                assigned_to: Some("Jane Smith".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "Update failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_update_resolves_me_to_authenticated_user() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // Resolved once, then cached on Config for the second update
+    Mock::given(method("GET"))
+        .and(path("/_apis/connectionData"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "authenticatedUser": {
+                "descriptor": "Microsoft.IdentityModel.Claims.ClaimsIdentity;me",
+                "providerDisplayName": "Me Myself",
+                "properties": {
+                    "Account": { "$type": "System.String", "$value": "me.myself@example.com" }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_partial_json(json!([{
+            "op": "add",
+            "path": "/fields/System.AssignedTo",
+            "value": "me.myself@example.com"
+        }])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                assigned_to: Some("me".to_string()),
+                ..Default::default()
+            },
+            false,
+        )?;
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                assigned_to: Some("me".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "update failed: {:?}", result);
+}
+
+#[tokio::test]
+async fn test_update_ambiguous_display_name_fails() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/_apis/identities"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 2,
+            "value": [
+                { "providerDisplayName": "Jane Smith", "properties": { "Account": { "$value": "jane.smith@example.com" } } },
+                { "providerDisplayName": "Jane Smithers", "properties": { "Account": { "$value": "jane.smithers@example.com" } } }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                assigned_to: Some("Jane".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("matches 2 users"), "Unexpected error: {}", err);
+}
+
+#[tokio::test]
+async fn test_state_refuses_disallowed_transition() {
+    let mock_server = MockServer::start().await;
+    let mut config = create_test_config(mock_server.uri());
+    config
+        .devops
+        .transitions
+        .insert("New".to_string(), vec!["Active".to_string()]);
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 1,
+            "fields": {
+                "System.Title": "Test Task",
+                "System.State": "New",
+                "System.WorkItemType": "Task"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitemtypes/Task"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "Task",
+            "states": [
+                { "name": "New", "color": "b2b2b2", "category": "Proposed" },
+                { "name": "Active", "color": "007acc", "category": "InProgress" },
+                { "name": "Closed", "color": "339933", "category": "Completed" }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::state(&config, 123, Some("Closed".to_string()), None, false)
+    })
+    .await
+    .unwrap();
+
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("New -> Closed is not allowed"), "{}", err);
+}
+
+async fn mount_state_transition(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 1,
+            "fields": {
+                "System.Title": "Test Task",
+                "System.State": "Active",
+                "System.WorkItemType": "Task"
+            }
+        })))
+        .mount(mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/test-project/_apis/wit/workitemtypes/Task"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "Task",
+            "states": [
+                { "name": "Active", "color": "007acc", "category": "InProgress" },
+                { "name": "Blocked", "color": "cc293d", "category": "InProgress" }
+            ]
+        })))
+        .mount(mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_string_contains("Blocked"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 2,
+            "fields": { "System.State": "Blocked" }
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_state_posts_comment_after_transition() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_state_transition(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .and(body_partial_json(json!({ "text": "Waiting on API keys" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 7,
+            "workItemId": 123,
+            "text": "Waiting on API keys"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    tokio::task::spawn_blocking(move || {
+        devops::state(
+            &config,
+            123,
+            Some("Blocked".to_string()),
+            Some("Waiting on API keys".to_string()),
+            false,
+        )
+    })
+    .await
+    .unwrap()
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_state_comment_failure_keeps_transition() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_state_transition(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/test-project/_apis/wit/workItems/123/comments"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::state(
+            &config,
+            123,
+            Some("Blocked".to_string()),
+            Some("Waiting on API keys".to_string()),
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        result.is_ok(),
+        "comment failure should only warn: {:?}",
+        result
+    );
+}
+
+async fn mount_deletable_item(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 4,
+            "fields": {
+                "System.Title": "Obsolete Task",
+                "System.State": "New",
+                "System.WorkItemType": "Task"
+            }
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_delete_moves_item_to_removed() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_deletable_item(&mock_server).await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/test-project/_apis/wit/workitems/123"))
+        .and(body_partial_json(json!([{
+            "op": "add",
+            "path": "/fields/System.State",
+            "value": "Removed"
+        }])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.State": "Removed" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || devops::delete(&config, 123, false, true, false))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_delete_permanent_calls_delete_endpoint() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_deletable_item(&mock_server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/test-project/_apis/wit/workitems/123"))
+        .and(query_param("api-version", "7.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "deletedBy": "Me Myself"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || devops::delete(&config, 123, true, true, false))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_delete_dry_run_changes_nothing() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+    mount_deletable_item(&mock_server).await;
+
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        tokio::task::spawn_blocking(move || devops::delete(&config, 123, true, false, true))
+            .await
+            .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_update_iteration_and_area() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Test Task" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_partial_json(json!([
+            {
+                "op": "add",
+                "path": "/fields/System.IterationPath",
+                "value": r"Proj\Sprint 12"
+            },
+            {
+                "op": "add",
+                "path": "/fields/System.AreaPath",
+                "value": r"Proj\Web"
+            }
+        ])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.IterationPath": r"Proj\Sprint 12" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                iteration: Some(r"Proj\Sprint 12".to_string()),
+                area: Some(r"Proj\Web".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_update_title_is_trimmed_into_patch() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 5,
+            "fields": { "System.Title": "Old name" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path_regex(r"^/test-project/_apis/wit/workitems/123"))
+        .and(body_partial_json(json!([
+            {
+                "op": "add",
+                "path": "/fields/System.Title",
+                "value": "New name"
+            },
+            {
+                "op": "add",
+                "path": "/fields/Microsoft.VSTS.Common.Priority",
+                "value": 2
+            }
+        ])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 123,
+            "rev": 6,
+            "fields": { "System.Title": "New name" }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                title: Some("  New name ".to_string()),
+                priority: Some(2),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_update_rejects_blank_title() {
+    let mock_server = MockServer::start().await;
+    let config = create_test_config(mock_server.uri());
+
+    Mock::given(method("PATCH"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        devops::update(
+            &config,
+            123,
+            WorkItemUpdate {
+                title: Some("   ".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+    })
+    .await
+    .unwrap();
+
+    assert!(result.unwrap_err().to_string().contains("--title"));
+}